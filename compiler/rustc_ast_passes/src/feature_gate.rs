@@ -311,7 +311,10 @@ macro_rules! gate_doc { ($($name:ident => $feature:ident)*) => {
 
                 gate_doc!(
                     include => external_doc
+                    auto_cfg => doc_auto_cfg
                     cfg => doc_cfg
+                    cfg_hide => doc_cfg_hide
+                    hidden_arms => doc_hidden_arms
                     masked => doc_masked
                     spotlight => doc_spotlight
                     keyword => doc_keyword
@@ -21,6 +21,7 @@ pub fn expand_deriving_copy(
         generics: Bounds::empty(),
         is_unsafe: false,
         supports_unions: true,
+        cmp_attrs: CmpAttrs::Ignore,
         methods: Vec::new(),
         associated_types: Vec::new(),
     };
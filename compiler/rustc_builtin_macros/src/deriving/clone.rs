@@ -82,6 +82,7 @@ pub fn expand_deriving_clone(
         generics: Bounds::empty(),
         is_unsafe: false,
         supports_unions: true,
+        cmp_attrs: CmpAttrs::Ignore,
         methods: vec![MethodDef {
             name: sym::clone,
             generics: Bounds::empty(),
@@ -27,6 +27,7 @@ pub fn expand_deriving_eq(
         generics: Bounds::empty(),
         is_unsafe: false,
         supports_unions: true,
+        cmp_attrs: CmpAttrs::SkipAndOrder,
         methods: vec![MethodDef {
             name: sym::assert_receiver_is_total_eq,
             generics: Bounds::empty(),
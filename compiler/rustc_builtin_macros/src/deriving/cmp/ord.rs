@@ -25,6 +25,7 @@ pub fn expand_deriving_ord(
         generics: Bounds::empty(),
         is_unsafe: false,
         supports_unions: false,
+        cmp_attrs: CmpAttrs::SkipAndOrder,
         methods: vec![MethodDef {
             name: sym::cmp,
             generics: Bounds::empty(),
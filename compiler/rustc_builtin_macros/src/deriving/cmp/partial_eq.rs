@@ -105,6 +105,7 @@ macro_rules! md {
         generics: Bounds::empty(),
         is_unsafe: false,
         supports_unions: false,
+        cmp_attrs: CmpAttrs::SkipAndOrder,
         methods,
         associated_types: Vec::new(),
     };
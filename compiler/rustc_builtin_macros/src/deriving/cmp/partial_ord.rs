@@ -48,6 +48,7 @@ pub fn expand_deriving_partial_ord(
         generics: Bounds::empty(),
         is_unsafe: false,
         supports_unions: false,
+        cmp_attrs: CmpAttrs::SkipAndOrder,
         methods: vec![partial_cmp_def],
         associated_types: Vec::new(),
     };
@@ -31,6 +31,7 @@ pub fn expand_deriving_debug(
         generics: Bounds::empty(),
         is_unsafe: false,
         supports_unions: false,
+        cmp_attrs: CmpAttrs::Skip,
         methods: vec![MethodDef {
             name: sym::fmt,
             generics: Bounds::empty(),
@@ -28,6 +28,7 @@ pub fn expand_deriving_rustc_decodable(
         generics: Bounds::empty(),
         is_unsafe: false,
         supports_unions: false,
+        cmp_attrs: CmpAttrs::Ignore,
         methods: vec![MethodDef {
             name: sym::decode,
             generics: Bounds {
@@ -113,6 +113,7 @@ pub fn expand_deriving_rustc_encodable(
         generics: Bounds::empty(),
         is_unsafe: false,
         supports_unions: false,
+        cmp_attrs: CmpAttrs::Ignore,
         methods: vec![MethodDef {
             name: sym::encode,
             generics: Bounds {
@@ -187,6 +187,7 @@
 use rustc_attr as attr;
 use rustc_data_structures::map_in_place::MapInPlace;
 use rustc_expand::base::{Annotatable, ExtCtxt};
+use rustc_session::parse::feature_err;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::Span;
 
@@ -218,11 +219,30 @@ pub struct TraitDef<'a> {
     /// Can this trait be derived for unions?
     pub supports_unions: bool,
 
+    /// How the `#[cmp(..)]` attributes on the fields affect the derived methods.
+    pub cmp_attrs: CmpAttrs,
+
     pub methods: Vec<MethodDef<'a>>,
 
     pub associated_types: Vec<(Ident, Ty)>,
 }
 
+/// How a derive treats the `#[cmp(..)]` helper attributes on the fields of a type.
+///
+/// The attributes are applied here rather than by the individual derives, so that `PartialEq`,
+/// `Eq`, `PartialOrd`, `Ord` and `Hash` always agree on which fields take part in the comparison
+/// and in which order they are visited.
+#[derive(Clone, Copy, PartialEq)]
+pub enum CmpAttrs {
+    /// The attributes are ignored, e.g. by `Clone`, which has to produce every field.
+    Ignore,
+    /// Fields marked `#[cmp(skip)]` are left out, e.g. by `Debug`.
+    Skip,
+    /// Fields marked `#[cmp(skip)]` are left out, and the fields marked `#[cmp(order = N)]` are
+    /// visited first, in ascending order of `N`, followed by the others in declaration order.
+    SkipAndOrder,
+}
+
 pub struct MethodDef<'a> {
     /// name of the method
     pub name: Symbol,
@@ -413,6 +433,7 @@ pub fn expand_ext(
                 let container_id = cx.current_expansion.id.expn_data().parent;
                 let always_copy = has_no_type_params && cx.resolver.has_derive_copy(container_id);
                 let use_temporaries = is_packed && always_copy;
+                self.check_cmp_attrs(cx, item);
 
                 let newitem = match item.kind {
                     ast::ItemKind::Struct(ref struct_def, ref generics) => self.expand_struct_def(
@@ -474,6 +495,65 @@ pub fn expand_ext(
         }
     }
 
+    /// Reports unstable and invalid `#[cmp(..)]` attributes on the fields of `item`. Every derive
+    /// of the item sees the same attributes, so only the first one to be expanded checks them,
+    /// and marks them as used for the others.
+    fn check_cmp_attrs(&self, cx: &ExtCtxt<'_>, item: &ast::Item) {
+        if self.cmp_attrs == CmpAttrs::Ignore {
+            return;
+        }
+        let variants: Vec<&VariantData> = match item.kind {
+            ast::ItemKind::Struct(ref struct_def, _) | ast::ItemKind::Union(ref struct_def, _) => {
+                vec![struct_def]
+            }
+            ast::ItemKind::Enum(ref enum_def, _) => {
+                enum_def.variants.iter().map(|variant| &variant.data).collect()
+            }
+            _ => return,
+        };
+        let cmp_attrs = || {
+            variants
+                .iter()
+                .flat_map(|data| data.fields())
+                .flat_map(|field| field.attrs.iter())
+                .filter(|attr| attr.has_name(sym::cmp))
+        };
+        if cmp_attrs().all(|attr| cx.sess.is_attr_used(attr)) {
+            return;
+        }
+        for attr in cmp_attrs() {
+            cx.sess.mark_attr_used(attr);
+            if !cx.sess.features_untracked().derive_cmp_attrs {
+                feature_err(
+                    cx.parse_sess(),
+                    sym::derive_cmp_attrs,
+                    attr.span,
+                    "the `#[cmp]` attribute is experimental",
+                )
+                .emit();
+            }
+        }
+
+        for data in variants {
+            let mut orders: Vec<_> = data
+                .fields()
+                .iter()
+                .filter_map(|field| {
+                    let mut report = |span: Span, msg: &str| cx.span_err(span, msg);
+                    let attrs = parse_cmp_attrs(&field.attrs, &mut report);
+                    Some((attrs.order?, field.span))
+                })
+                .collect();
+            orders.sort_by_key(|&(order, _)| order);
+            for pair in orders.windows(2) {
+                if pair[0].0 == pair[1].0 {
+                    let msg = format!("multiple fields have `#[cmp(order = {})]`", pair[1].0);
+                    cx.span_err(pair[1].1, &msg);
+                }
+            }
+        }
+    }
+
     /// Given that we are deriving a trait `DerivedTrait` for a type like:
     ///
     /// ```ignore (only-for-syntax-highlight)
@@ -1020,6 +1100,7 @@ fn expand_struct_method_body<'b>(
         } else {
             cx.span_bug(trait_.span, "no `self` parameter for method in generic `derive`")
         };
+        let fields = trait_.select_fields(fields);
 
         // body of the inner most destructuring match
         let mut body = self.call_substructure_method(
@@ -1271,6 +1352,7 @@ fn build_enum_match_tuple<'b>(
                         }
                     })
                     .collect::<Vec<FieldInfo<'_>>>();
+                let field_tuples = trait_.select_fields(field_tuples);
 
                 // Now, for some given VariantK, we have built up
                 // expressions for referencing every field of every
@@ -1598,6 +1680,25 @@ fn create_struct_pattern(
         (pattern, ident_exprs)
     }
 
+    /// Drops and reorders the fields of a struct or variant according to their `#[cmp(..)]`
+    /// attributes, see `CmpAttrs`. Invalid attributes are reported by `check_cmp_attrs`.
+    fn select_fields<'b>(&self, fields: Vec<FieldInfo<'b>>) -> Vec<FieldInfo<'b>> {
+        if self.cmp_attrs == CmpAttrs::Ignore {
+            return fields;
+        }
+        let mut fields: Vec<_> = fields
+            .into_iter()
+            .map(|field| (parse_cmp_attrs(field.attrs, &mut |_: Span, _: &str| {}), field))
+            .filter(|(attrs, _)| !attrs.skip)
+            .collect();
+        if self.cmp_attrs == CmpAttrs::SkipAndOrder {
+            // The sort is stable, so the fields without an explicit order keep their declaration
+            // order.
+            fields.sort_by_key(|(attrs, _)| attrs.order.map_or((1, 0), |order| (0, order)));
+        }
+        fields.into_iter().map(|(_, field)| field).collect()
+    }
+
     fn create_enum_variant_pattern(
         &self,
         cx: &mut ExtCtxt<'_>,
@@ -1613,6 +1714,41 @@ fn create_enum_variant_pattern(
     }
 }
 
+/// The `#[cmp(..)]` attributes of a field.
+#[derive(Default)]
+struct CmpFieldAttrs {
+    skip: bool,
+    order: Option<u128>,
+}
+
+/// Parses the `#[cmp(..)]` attributes of a field, passing the span and message of every error
+/// to `report`.
+fn parse_cmp_attrs(attrs: &[ast::Attribute], report: &mut dyn FnMut(Span, &str)) -> CmpFieldAttrs {
+    let mut field_attrs = CmpFieldAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.has_name(sym::cmp)) {
+        let items = match attr.meta_item_list() {
+            Some(items) if !items.is_empty() => items,
+            _ => {
+                report(attr.span, "expected `#[cmp(skip)]` or `#[cmp(order = N)]`");
+                continue;
+            }
+        };
+        for item in items {
+            match item.meta_item() {
+                Some(mi) if mi.is_word() && mi.has_name(sym::skip) => field_attrs.skip = true,
+                Some(mi) if mi.has_name(sym::order) => match mi.name_value_literal() {
+                    Some(ast::Lit { kind: ast::LitKind::Int(order, _), .. }) => {
+                        field_attrs.order = Some(*order);
+                    }
+                    _ => report(mi.span, "expected an integer, like `order = 0`"),
+                },
+                _ => report(item.span(), "expected `skip` or `order = N`"),
+            }
+        }
+    }
+    field_attrs
+}
+
 // helpful premade recipes
 
 pub fn cs_fold_fields<'a, F>(
@@ -528,6 +528,24 @@ fn verify_arg_type(&mut self, arg: Position, ty: ArgumentType) {
                         self.verify_arg_type(Exact(idx), ty)
                     }
                     None => {
+                        if let Some(root) = self.named_field_root(name) {
+                            let sp = if self.is_literal {
+                                *self.arg_spans.get(self.curpiece).unwrap_or(&self.fmtsp)
+                            } else {
+                                self.fmtsp
+                            };
+                            let msg =
+                                format!("cannot access fields of the named argument `{}`", root);
+                            self.ecx
+                                .struct_span_err(sp, &msg)
+                                .help(&format!(
+                                    "pass `{}` as a named argument of its own instead",
+                                    name
+                                ))
+                                .emit();
+                            return;
+                        }
+
                         let capture_feature_enabled = self
                             .ecx
                             .ecfg
@@ -548,7 +566,7 @@ fn verify_arg_type(&mut self, arg: Position, ty: ArgumentType) {
                             } else {
                                 self.fmtsp
                             };
-                            self.args.push(self.ecx.expr_ident(span, Ident::new(name, span)));
+                            self.args.push(self.capture_expr(span, name));
                             self.names.insert(name, idx);
                             self.verify_arg_type(Exact(idx), ty)
                         } else {
@@ -586,6 +604,31 @@ fn verify_arg_type(&mut self, arg: Position, ty: ArgumentType) {
         }
     }
 
+    /// If `name` is a field path, like `a.b`, whose root is one of the named arguments, returns
+    /// that root. Such a path does not refer to a variable of the surrounding scope.
+    fn named_field_root(&self, name: Symbol) -> Option<Symbol> {
+        let name = name.as_str();
+        if !name.contains('.') {
+            return None;
+        }
+        let root = Symbol::intern(name.split('.').next()?);
+        if self.names.contains_key(&root) { Some(root) } else { None }
+    }
+
+    /// Builds the expression capturing `name` from the surrounding scope. Names with fields, like
+    /// `self.config.timeout`, are captured as the corresponding field access expression.
+    fn capture_expr(&self, span: Span, name: Symbol) -> P<ast::Expr> {
+        let name = name.as_str();
+        let mut segments = name.split('.');
+        let root = segments.next().expect("empty argument name");
+        let mut expr = self.ecx.expr_ident(span, Ident::from_str_and_span(root, span));
+        for field in segments {
+            let field = Ident::from_str_and_span(field, span);
+            expr = self.ecx.expr(span, ast::ExprKind::Field(expr, field));
+        }
+        expr
+    }
+
     /// Builds the mapping between format placeholders and argument objects.
     fn build_index_map(&mut self) {
         // NOTE: Keep the ordering the same as `into_expr`'s expansion would do!
@@ -91,6 +91,32 @@ pub fn iter_globals(llmod: &'ll llvm::Module) -> ValueIter<'ll> {
     unsafe { ValueIter { cur: llvm::LLVMGetFirstGlobal(llmod), step: llvm::LLVMGetNextGlobal } }
 }
 
+pub fn iter_functions(llmod: &'ll llvm::Module) -> ValueIter<'ll> {
+    unsafe { ValueIter { cur: llvm::LLVMGetFirstFunction(llmod), step: llvm::LLVMGetNextFunction } }
+}
+
+/// Counts the instructions of every function defined in `module`, for
+/// `-Z dump-mono-stats`.
+pub fn function_instruction_counts(module: &ModuleLlvm) -> Vec<(String, usize)> {
+    iter_functions(module.llmod())
+        .filter(|&llfn| unsafe { llvm::LLVMIsDeclaration(llfn) } == llvm::False)
+        .map(|llfn| {
+            let mut count = 0;
+            let mut bb = Some(unsafe { llvm::LLVMGetFirstBasicBlock(llfn) });
+            while let Some(llbb) = bb {
+                let instructions = ValueIter {
+                    cur: unsafe { llvm::LLVMGetFirstInstruction(llbb) },
+                    step: llvm::LLVMGetNextInstruction,
+                };
+                count += instructions.count();
+                bb = unsafe { llvm::LLVMGetNextBasicBlock(llbb) };
+            }
+            let name = String::from_utf8_lossy(llvm::get_value_name(llfn)).into_owned();
+            (name, count)
+        })
+        .collect()
+}
+
 pub fn compile_codegen_unit(
     tcx: TyCtxt<'tcx>,
     cgu_name: Symbol,
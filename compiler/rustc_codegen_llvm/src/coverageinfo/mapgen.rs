@@ -28,12 +28,16 @@
 /// this Rust version, and though the format documentation is very explicit and detailed, some
 /// undocumented details in Clang's implementation (that may or may not be important) were also
 /// replicated for Rust's Coverage Map.
+///
+/// When built with LLVM 12 or later, the Coverage Map complies with version 5 (zero-based encoded
+/// as 4) instead, which adds the branch regions of `-Z coverage-options=branch`.
 pub fn finalize<'ll, 'tcx>(cx: &CodegenCx<'ll, 'tcx>) {
     let tcx = cx.tcx;
-    // Ensure LLVM supports Coverage Map Version 4 (encoded as a zero-based value: 3).
-    // If not, the LLVM Version must be less than 11.
+    // Ensure LLVM supports Coverage Map Version 4 (encoded as a zero-based value: 3), or Version
+    // 5 (encoded as 4), which only adds branch regions. If not, the LLVM Version must be less
+    // than 11.
     let version = coverageinfo::mapping_version();
-    if version != 3 {
+    if version != 3 && version != 4 {
         tcx.sess.fatal("rustc option `-Z instrument-coverage` requires LLVM 11 or higher.");
     }
 
@@ -58,11 +62,16 @@ pub fn finalize<'ll, 'tcx>(cx: &CodegenCx<'ll, 'tcx>) {
         debug!("Generate function coverage for {}, {:?}", cx.codegen_unit.name(), instance);
         let mangled_function_name = tcx.symbol_name(instance).to_string();
         let function_source_hash = function_coverage.source_hash();
-        let (expressions, counter_regions) =
+        let (expressions, counter_regions, branch_regions) =
             function_coverage.get_expressions_and_counter_regions();
 
         let coverage_mapping_buffer = llvm::build_byte_buffer(|coverage_mapping_buffer| {
-            mapgen.write_coverage_mapping(expressions, counter_regions, coverage_mapping_buffer);
+            mapgen.write_coverage_mapping(
+                expressions,
+                counter_regions,
+                branch_regions,
+                coverage_mapping_buffer,
+            );
         });
         debug_assert!(
             coverage_mapping_buffer.len() > 0,
@@ -115,13 +124,25 @@ fn write_coverage_mapping(
         &mut self,
         expressions: Vec<CounterExpression>,
         counter_regions: impl Iterator<Item = (Counter, &'a CodeRegion)>,
+        branch_regions: impl Iterator<Item = (Counter, Counter, &'a CodeRegion)>,
         coverage_mapping_buffer: &RustString,
     ) {
-        let mut counter_regions = counter_regions.collect::<Vec<_>>();
+        // Each region is paired with its counter, and with the counter of the false branch if it
+        // is a branch region.
+        let mut counter_regions =
+            counter_regions.map(|(counter, region)| (counter, None, region)).collect::<Vec<_>>();
         if counter_regions.is_empty() {
             return;
         }
 
+        // Branch regions require Coverage Map Version 5 (encoded as 4), written by LLVM 12 and
+        // later.
+        if coverageinfo::mapping_version() >= 4 {
+            counter_regions.extend(branch_regions.map(|(true_counter, false_counter, region)| {
+                (true_counter, Some(false_counter), region)
+            }));
+        }
+
         let mut virtual_file_mapping = Vec::new();
         let mut mapping_regions = Vec::new();
         let mut current_file_name = None;
@@ -132,8 +153,8 @@ fn write_coverage_mapping(
         // `file_id` (indexing files referenced by the current function), and construct the
         // function-specific `virtual_file_mapping` from `file_id` to its index in the module's
         // `filenames` array.
-        counter_regions.sort_unstable_by_key(|(_counter, region)| *region);
-        for (counter, region) in counter_regions {
+        counter_regions.sort_unstable_by_key(|(_counter, _false_counter, region)| *region);
+        for (counter, false_counter, region) in counter_regions {
             let CodeRegion { file_name, start_line, start_col, end_line, end_col } = *region;
             let same_file = current_file_name.as_ref().map_or(false, |p| *p == file_name);
             if !same_file {
@@ -147,15 +168,31 @@ fn write_coverage_mapping(
                 let (filenames_index, _) = self.filenames.insert_full(c_filename);
                 virtual_file_mapping.push(filenames_index as u32);
             }
-            debug!("Adding counter {:?} to map for {:?}", counter, region);
-            mapping_regions.push(CounterMappingRegion::code_region(
-                counter,
-                current_file_id,
-                start_line,
-                start_col,
-                end_line,
-                end_col,
-            ));
+            if let Some(false_counter) = false_counter {
+                debug!(
+                    "Adding branch counters {:?}, {:?} to map for {:?}",
+                    counter, false_counter, region
+                );
+                mapping_regions.push(CounterMappingRegion::branch_region(
+                    counter,
+                    false_counter,
+                    current_file_id,
+                    start_line,
+                    start_col,
+                    end_line,
+                    end_col,
+                ));
+            } else {
+                debug!("Adding counter {:?} to map for {:?}", counter, region);
+                mapping_regions.push(CounterMappingRegion::code_region(
+                    counter,
+                    current_file_id,
+                    start_line,
+                    start_col,
+                    end_line,
+                    end_col,
+                ));
+            }
         }
 
         // Encode and append the current function's coverage mapping data
@@ -180,7 +217,8 @@ fn generate_coverage_map(
         debug!("cov map: filenames_size = {}, 0-based version = {}", filenames_size, version);
 
         // Create the coverage data header (Note, fields 0 and 2 are now always zero,
-        // as of `llvm::coverage::CovMapVersion::Version4`.)
+        // as of `llvm::coverage::CovMapVersion::Version4`, and the header is unchanged in
+        // `Version5`.)
         let zero_was_n_records_val = cx.const_u32(0);
         let filenames_size_val = cx.const_u32(filenames_size as u32);
         let zero_was_coverage_size_val = cx.const_u32(0);
@@ -143,6 +143,30 @@ fn add_coverage_unreachable(&mut self, instance: Instance<'tcx>, region: CodeReg
             false
         }
     }
+
+    fn add_coverage_branch(
+        &mut self,
+        instance: Instance<'tcx>,
+        true_count: ExpressionOperandId,
+        false_count: ExpressionOperandId,
+        region: CodeRegion,
+    ) -> bool {
+        if let Some(coverage_context) = self.coverage_context() {
+            debug!(
+                "adding branch to coverage_map: instance={:?}, true_count={:?}, \
+                false_count={:?} at {:?}",
+                instance, true_count, false_count, region,
+            );
+            let mut coverage_map = coverage_context.function_coverage_map.borrow_mut();
+            coverage_map
+                .entry(instance)
+                .or_insert_with(|| FunctionCoverage::new(self.tcx, instance))
+                .add_branch_region(true_count, false_count, region);
+            true
+        } else {
+            false
+        }
+    }
 }
 
 pub(crate) fn write_filenames_section_to_buffer<'a>(
@@ -106,6 +106,9 @@ fn compile_codegen_unit(
     ) -> (ModuleCodegen<ModuleLlvm>, u64) {
         base::compile_codegen_unit(tcx, cgu_name)
     }
+    fn function_instruction_counts(&self, module: &ModuleLlvm) -> Vec<(String, usize)> {
+        base::function_instruction_counts(module)
+    }
     fn target_machine_factory(
         &self,
         sess: &Session,
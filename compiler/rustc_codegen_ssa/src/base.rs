@@ -7,6 +7,7 @@
 use crate::mir;
 use crate::mir::operand::OperandValue;
 use crate::mir::place::PlaceRef;
+use crate::mono_stats::MonoStats;
 use crate::traits::*;
 use crate::{CachedModuleCodegen, CrateInfo, MemFlags, ModuleCodegen, ModuleKind};
 
@@ -495,6 +496,8 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     let ongoing_codegen = start_async_codegen(backend.clone(), tcx, metadata, codegen_units.len());
     let ongoing_codegen = AbortCodegenOnDrop::<B>(Some(ongoing_codegen));
 
+    let mut mono_stats = MonoStats::new(tcx, codegen_units);
+
     // Codegen an allocator shim, if necessary.
     //
     // If the crate doesn't have an `allocator_kind` set then there's definitely
@@ -644,6 +647,10 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
                 // compilation hang on post-monomorphization errors.
                 tcx.sess.abort_if_errors();
 
+                if let Some(mono_stats) = &mut mono_stats {
+                    mono_stats.record_module(&backend, &module.module_llvm);
+                }
+
                 submit_codegened_module_to_llvm(
                     &backend,
                     &ongoing_codegen.coordinator_send,
@@ -680,6 +687,10 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
 
     ongoing_codegen.codegen_finished(tcx);
 
+    if let Some(mono_stats) = mono_stats {
+        mono_stats.write();
+    }
+
     // Since the main thread is sometimes blocked during codegen, we keep track
     // -Ztime-passes output manually.
     if tcx.sess.time_passes() {
@@ -16,11 +16,19 @@ pub struct Expression {
     region: Option<CodeRegion>,
 }
 
+#[derive(Clone, Debug)]
+pub struct BranchRegion {
+    true_count: ExpressionOperandId,
+    false_count: ExpressionOperandId,
+    region: CodeRegion,
+}
+
 /// Collects all of the coverage regions associated with (a) injected counters, (b) counter
-/// expressions (additions or subtraction), and (c) unreachable regions (always counted as zero),
-/// for a given Function. Counters and counter expressions have non-overlapping `id`s because they
-/// can both be operands in an expression. This struct also stores the `function_source_hash`,
-/// computed during instrumentation, and forwarded with counters.
+/// expressions (additions or subtraction), (c) unreachable regions (always counted as zero), and
+/// (d) branch regions (counted by a pair of counters or expressions), for a given Function.
+/// Counters and counter expressions have non-overlapping `id`s because they can both be operands
+/// in an expression. This struct also stores the `function_source_hash`, computed during
+/// instrumentation, and forwarded with counters.
 ///
 /// Note, it may be important to understand LLVM's definitions of `unreachable` regions versus "gap
 /// regions" (or "gap areas"). A gap region is a code region within a counted region (either counter
@@ -34,6 +42,7 @@ pub struct FunctionCoverage<'tcx> {
     counters: IndexVec<CounterValueReference, Option<CodeRegion>>,
     expressions: IndexVec<InjectedExpressionIndex, Option<Expression>>,
     unreachable_regions: Vec<CodeRegion>,
+    branch_regions: Vec<BranchRegion>,
 }
 
 impl<'tcx> FunctionCoverage<'tcx> {
@@ -49,6 +58,7 @@ pub fn new(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Self {
             counters: IndexVec::from_elem_n(None, coverageinfo.num_counters as usize),
             expressions: IndexVec::from_elem_n(None, coverageinfo.num_expressions as usize),
             unreachable_regions: Vec::new(),
+            branch_regions: Vec::new(),
         }
     }
 
@@ -104,18 +114,34 @@ pub fn add_unreachable_region(&mut self, region: CodeRegion) {
         self.unreachable_regions.push(region)
     }
 
+    /// Add a branch region, counted by the given counters or expressions for the number of times
+    /// the branch was and was not taken.
+    pub fn add_branch_region(
+        &mut self,
+        true_count: ExpressionOperandId,
+        false_count: ExpressionOperandId,
+        region: CodeRegion,
+    ) {
+        self.branch_regions.push(BranchRegion { true_count, false_count, region })
+    }
+
     /// Return the source hash, generated from the HIR node structure, and used to indicate whether
     /// or not the source code structure changed between different compilations.
     pub fn source_hash(&self) -> u64 {
         self.source_hash
     }
 
-    /// Generate an array of CounterExpressions, and an iterator over all `Counter`s and their
-    /// associated `Regions` (from which the LLVM-specific `CoverageMapGenerator` will create
+    /// Generate an array of CounterExpressions, an iterator over all `Counter`s and their
+    /// associated `Regions`, and an iterator over all branch regions with their "true" and "false"
+    /// `Counter`s (from which the LLVM-specific `CoverageMapGenerator` will create
     /// `CounterMappingRegion`s.
     pub fn get_expressions_and_counter_regions<'a>(
         &'a self,
-    ) -> (Vec<CounterExpression>, impl Iterator<Item = (Counter, &'a CodeRegion)>) {
+    ) -> (
+        Vec<CounterExpression>,
+        impl Iterator<Item = (Counter, &'a CodeRegion)>,
+        impl Iterator<Item = (Counter, Counter, &'a CodeRegion)>,
+    ) {
         assert!(
             self.source_hash != 0,
             "No counters provided the source_hash for function: {:?}",
@@ -123,12 +149,13 @@ pub fn get_expressions_and_counter_regions<'a>(
         );
 
         let counter_regions = self.counter_regions();
-        let (counter_expressions, expression_regions) = self.expressions_with_regions();
+        let (counter_expressions, expression_regions, branch_regions) =
+            self.expressions_with_regions();
         let unreachable_regions = self.unreachable_regions();
 
         let counter_regions =
             counter_regions.chain(expression_regions.into_iter().chain(unreachable_regions));
-        (counter_expressions, counter_regions)
+        (counter_expressions, counter_regions, branch_regions)
     }
 
     fn counter_regions<'a>(&'a self) -> impl Iterator<Item = (Counter, &'a CodeRegion)> {
@@ -143,7 +170,11 @@ fn counter_regions<'a>(&'a self) -> impl Iterator<Item = (Counter, &'a CodeRegio
 
     fn expressions_with_regions(
         &'a self,
-    ) -> (Vec<CounterExpression>, impl Iterator<Item = (Counter, &'a CodeRegion)>) {
+    ) -> (
+        Vec<CounterExpression>,
+        impl Iterator<Item = (Counter, &'a CodeRegion)>,
+        impl Iterator<Item = (Counter, Counter, &'a CodeRegion)>,
+    ) {
         let mut counter_expressions = Vec::with_capacity(self.expressions.len());
         let mut expression_regions = Vec::with_capacity(self.expressions.len());
         let mut new_indexes = IndexVec::from_elem_n(None, self.expressions.len());
@@ -247,7 +278,21 @@ fn expressions_with_regions(
                 )
             }
         }
-        (counter_expressions, expression_regions.into_iter())
+
+        // Branch regions are not expressions, so their counts can be mapped once all expressions
+        // have been assigned a `new_index`. A count that was optimized out is zero.
+        let branch_regions: Vec<_> = self
+            .branch_regions
+            .iter()
+            .map(|branch| {
+                let true_counter =
+                    id_to_counter(&new_indexes, branch.true_count).unwrap_or(Counter::zero());
+                let false_counter =
+                    id_to_counter(&new_indexes, branch.false_count).unwrap_or(Counter::zero());
+                (true_counter, false_counter, &branch.region)
+            })
+            .collect();
+        (counter_expressions, expression_regions.into_iter(), branch_regions.into_iter())
     }
 
     fn unreachable_regions<'a>(&'a self) -> impl Iterator<Item = (Counter, &'a CodeRegion)> {
@@ -45,6 +45,7 @@
 pub mod meth;
 pub mod mir;
 pub mod mono_item;
+pub mod mono_stats;
 pub mod target_features;
 pub mod traits;
 
@@ -0,0 +1,169 @@
+//! Support for `-Z dump-mono-stats`, which writes a per-crate report about how
+//! many monomorphizations every generic definition produced, which codegen
+//! units they ended up in and how much MIR and LLVM IR they account for.
+
+use crate::traits::*;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_middle::mir::mono::{CodegenUnit, MonoItem};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_serialize::{json, Encodable};
+use rustc_session::config::{MonoStatsFormat, SwitchWithOptPath};
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+/// Statistics about all the instantiations of a single generic definition.
+#[derive(Encodable)]
+struct MonoItemStats {
+    /// The path of the generic definition.
+    item: String,
+    /// The number of distinct monomorphizations of the definition.
+    instantiation_count: usize,
+    /// The sum of MIR statements over all monomorphizations.
+    mir_statements: usize,
+    /// The names of all codegen units containing one of the monomorphizations.
+    codegen_units: BTreeSet<String>,
+    /// The sum of LLVM IR instructions over all monomorphizations that were
+    /// codegened in this session. This is `None` if the backend doesn't
+    /// report instruction counts, or if all the containing codegen units were
+    /// reused from the incremental cache. Items with `LocalCopy`
+    /// instantiation mode are only counted in the first codegen unit they
+    /// were codegened in.
+    llvm_instructions: Option<usize>,
+}
+
+pub struct MonoStats<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    stats: FxHashMap<DefId, MonoItemStats>,
+    /// Maps the symbol name of every collected function to its definition.
+    /// Symbols are removed once their instruction count was recorded.
+    symbols: FxHashMap<String, DefId>,
+}
+
+impl<'tcx> MonoStats<'tcx> {
+    /// Returns `None` when `-Z dump-mono-stats` is disabled.
+    pub fn new(tcx: TyCtxt<'tcx>, codegen_units: &[CodegenUnit<'tcx>]) -> Option<Self> {
+        if !tcx.sess.opts.debugging_opts.dump_mono_stats.enabled() {
+            return None;
+        }
+
+        let mut stats: FxHashMap<DefId, MonoItemStats> = Default::default();
+        let mut symbols = FxHashMap::default();
+
+        for cgu in codegen_units {
+            for &mono_item in cgu.items().keys() {
+                let instance = match mono_item {
+                    MonoItem::Fn(instance) if mono_item.is_generic_fn() => instance,
+                    _ => continue,
+                };
+                let def_id = instance.def_id();
+                let entry = stats.entry(def_id).or_insert_with(|| MonoItemStats {
+                    item: with_no_trimmed_paths(|| tcx.def_path_str(def_id)),
+                    instantiation_count: 0,
+                    mir_statements: 0,
+                    codegen_units: BTreeSet::new(),
+                    llvm_instructions: None,
+                });
+                entry.codegen_units.insert(cgu.name().to_string());
+
+                // Items with `LocalCopy` instantiation mode are placed into more
+                // than one codegen unit, but are still a single instantiation.
+                let symbol_name = mono_item.symbol_name(tcx).name.to_string();
+                if symbols.insert(symbol_name, def_id).is_none() {
+                    entry.instantiation_count += 1;
+                    entry.mir_statements += mono_item.size_estimate(tcx);
+                }
+            }
+        }
+
+        Some(MonoStats { tcx, stats, symbols })
+    }
+
+    /// Records the LLVM IR instruction counts of a freshly codegened module.
+    pub fn record_module<B: ExtraBackendMethods>(&mut self, backend: &B, module: &B::Module) {
+        for (symbol_name, count) in backend.function_instruction_counts(module) {
+            // Removing the symbol makes sure that `LocalCopy` items, which are
+            // codegened into every codegen unit using them, are counted once.
+            if let Some(def_id) = self.symbols.remove(&symbol_name) {
+                let stats = self.stats.get_mut(&def_id).unwrap();
+                *stats.llvm_instructions.get_or_insert(0) += count;
+            }
+        }
+    }
+
+    /// Writes the report into the directory given to `-Z dump-mono-stats`.
+    pub fn write(self) {
+        let sess = self.tcx.sess;
+        let dir = match sess.opts.debugging_opts.dump_mono_stats {
+            SwitchWithOptPath::Enabled(ref dir) => {
+                dir.clone().unwrap_or_else(|| PathBuf::from("."))
+            }
+            SwitchWithOptPath::Disabled => return,
+        };
+        let format = sess.opts.debugging_opts.dump_mono_stats_format;
+        let path = dir.join(format!(
+            "{}.mono_items.{}",
+            self.tcx.crate_name(LOCAL_CRATE),
+            format.extension()
+        ));
+
+        let mut stats: Vec<_> = self.stats.into_iter().map(|(_, stats)| stats).collect();
+        // Sort by decreasing instantiation count, with the largest items first.
+        stats.sort_by(|a, b| {
+            (b.instantiation_count, b.mir_statements, &a.item).cmp(&(
+                a.instantiation_count,
+                a.mir_statements,
+                &b.item,
+            ))
+        });
+
+        let result = File::create(&path).and_then(|file| {
+            let mut file = BufWriter::new(file);
+            match format {
+                MonoStatsFormat::Json => write_json(&mut file, &stats)?,
+                MonoStatsFormat::Csv => write_csv(&mut file, &stats)?,
+            }
+            file.flush()
+        });
+
+        if let Err(err) = result {
+            sess.err(&format!("failed to write mono stats to `{}`: {}", path.display(), err));
+        }
+    }
+}
+
+fn write_json(file: &mut impl Write, stats: &[MonoItemStats]) -> io::Result<()> {
+    let mut json = String::new();
+    stats
+        .encode(&mut json::PrettyEncoder::new(&mut json))
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    writeln!(file, "{}", json)
+}
+
+fn write_csv(file: &mut impl Write, stats: &[MonoItemStats]) -> io::Result<()> {
+    fn quote(field: &str) -> String {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    }
+
+    writeln!(file, "item,instantiation_count,mir_statements,codegen_units,llvm_instructions")?;
+    for stats in stats {
+        let codegen_units: Vec<_> = stats.codegen_units.iter().map(|cgu| &cgu[..]).collect();
+        let llvm_instructions =
+            stats.llvm_instructions.map(|count| count.to_string()).unwrap_or_default();
+        writeln!(
+            file,
+            "{},{},{},{},{}",
+            quote(&stats.item),
+            stats.instantiation_count,
+            stats.mir_statements,
+            quote(&codegen_units.join(";")),
+            llvm_instructions,
+        )?;
+    }
+    Ok(())
+}
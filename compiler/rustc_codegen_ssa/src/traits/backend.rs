@@ -119,6 +119,11 @@ fn compile_codegen_unit(
         tcx: TyCtxt<'_>,
         cgu_name: Symbol,
     ) -> (ModuleCodegen<Self::Module>, u64);
+    /// Returns the number of instructions in each function defined in the
+    /// module, keyed by symbol name. Used by `-Z dump-mono-stats`.
+    fn function_instruction_counts(&self, _module: &Self::Module) -> Vec<(String, usize)> {
+        Vec::new()
+    }
     fn target_machine_factory(
         &self,
         sess: &Session,
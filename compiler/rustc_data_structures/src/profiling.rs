@@ -79,8 +79,18 @@
 //! invocation) and allocate the corresponding strings together with a mapping
 //! for `DepNodeIndex as StringId`.
 //!
+//!
+//! ## In-memory Summaries
+//!
+//! With `-Z self-profile-summary` and `-Z self-profile-chrome-trace`, events
+//! are additionally kept in memory by the `summary::EventRecorder`, so that the
+//! compiler can print a table of the most expensive queries and activities or
+//! write a Chrome trace itself, without going through the `measureme` tools.
+//!
 //! [mm]: https://github.com/rust-lang/measureme/
 
+mod summary;
+
 use crate::cold_path;
 use crate::fx::FxHashMap;
 
@@ -89,6 +99,7 @@
 use std::convert::Into;
 use std::error::Error;
 use std::fs;
+use std::io;
 use std::path::Path;
 use std::process;
 use std::sync::Arc;
@@ -97,6 +108,8 @@
 use measureme::{EventId, EventIdBuilder, Profiler, SerializableString, StringId};
 use parking_lot::RwLock;
 
+use self::summary::{EventKind, EventLabel, EventRecorder};
+
 bitflags::bitflags! {
     struct EventFilter: u32 {
         const GENERIC_ACTIVITIES = 1 << 0;
@@ -242,9 +255,16 @@ pub fn extra_verbose_generic_activity<'a, A>(
     #[inline(always)]
     pub fn generic_activity(&self, event_label: &'static str) -> TimingGuard<'_> {
         self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
+            let label = EventLabel::Activity(event_label);
             let event_label = profiler.get_or_alloc_cached_string(event_label);
             let event_id = EventId::from_label(event_label);
-            TimingGuard::start(profiler, profiler.generic_activity_event_kind, event_id)
+            TimingGuard::start_recorded(
+                profiler,
+                profiler.generic_activity_event_kind,
+                event_id,
+                EventKind::GenericActivity,
+                Some(label),
+            )
         })
     }
 
@@ -260,15 +280,23 @@ pub fn generic_activity_with_arg<A>(
         A: Borrow<str> + Into<String>,
     {
         self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
-            let builder = EventIdBuilder::new(&profiler.profiler);
+            let label = EventLabel::Activity(event_label);
             let event_label = profiler.get_or_alloc_cached_string(event_label);
-            let event_id = if profiler.event_filter_mask.contains(EventFilter::FUNCTION_ARGS) {
-                let event_arg = profiler.get_or_alloc_cached_string(event_arg);
-                builder.from_label_and_arg(event_label, event_arg)
-            } else {
-                builder.from_label(event_label)
+            let with_args = profiler.event_filter_mask.contains(EventFilter::FUNCTION_ARGS);
+            let event_id = match profiler.event_id_builder() {
+                Some(builder) if with_args => {
+                    let event_arg = profiler.get_or_alloc_cached_string(event_arg);
+                    builder.from_label_and_arg(event_label, event_arg)
+                }
+                _ => EventId::from_label(event_label),
             };
-            TimingGuard::start(profiler, profiler.generic_activity_event_kind, event_id)
+            TimingGuard::start_recorded(
+                profiler,
+                profiler.generic_activity_event_kind,
+                event_id,
+                EventKind::GenericActivity,
+                Some(label),
+            )
         })
     }
 
@@ -279,18 +307,26 @@ pub fn generic_activity_with_args(
         event_args: &[String],
     ) -> TimingGuard<'_> {
         self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
-            let builder = EventIdBuilder::new(&profiler.profiler);
+            let label = EventLabel::Activity(event_label);
             let event_label = profiler.get_or_alloc_cached_string(event_label);
-            let event_id = if profiler.event_filter_mask.contains(EventFilter::FUNCTION_ARGS) {
-                let event_args: Vec<_> = event_args
-                    .iter()
-                    .map(|s| profiler.get_or_alloc_cached_string(&s[..]))
-                    .collect();
-                builder.from_label_and_args(event_label, &event_args)
-            } else {
-                builder.from_label(event_label)
+            let with_args = profiler.event_filter_mask.contains(EventFilter::FUNCTION_ARGS);
+            let event_id = match profiler.event_id_builder() {
+                Some(builder) if with_args => {
+                    let event_args: Vec<_> = event_args
+                        .iter()
+                        .map(|s| profiler.get_or_alloc_cached_string(&s[..]))
+                        .collect();
+                    builder.from_label_and_args(event_label, &event_args)
+                }
+                _ => EventId::from_label(event_label),
             };
-            TimingGuard::start(profiler, profiler.generic_activity_event_kind, event_id)
+            TimingGuard::start_recorded(
+                profiler,
+                profiler.generic_activity_event_kind,
+                event_id,
+                EventKind::GenericActivity,
+                Some(label),
+            )
         })
     }
 
@@ -299,7 +335,15 @@ pub fn generic_activity_with_args(
     #[inline(always)]
     pub fn query_provider(&self) -> TimingGuard<'_> {
         self.exec(EventFilter::QUERY_PROVIDERS, |profiler| {
-            TimingGuard::start(profiler, profiler.query_event_kind, EventId::INVALID)
+            // The label is only known once the query invocation id is known,
+            // see `TimingGuard::finish_with_query_invocation_id`.
+            TimingGuard::start_recorded(
+                profiler,
+                profiler.query_event_kind,
+                EventId::INVALID,
+                EventKind::Query,
+                None,
+            )
         })
     }
 
@@ -308,6 +352,7 @@ pub fn query_provider(&self) -> TimingGuard<'_> {
     pub fn query_cache_hit(&self, query_invocation_id: QueryInvocationId) {
         self.instant_query_event(
             |profiler| profiler.query_cache_hit_event_kind,
+            EventKind::QueryCacheHit,
             query_invocation_id,
             EventFilter::QUERY_CACHE_HITS,
         );
@@ -319,7 +364,13 @@ pub fn query_cache_hit(&self, query_invocation_id: QueryInvocationId) {
     #[inline(always)]
     pub fn query_blocked(&self) -> TimingGuard<'_> {
         self.exec(EventFilter::QUERY_BLOCKED, |profiler| {
-            TimingGuard::start(profiler, profiler.query_blocked_event_kind, EventId::INVALID)
+            TimingGuard::start_recorded(
+                profiler,
+                profiler.query_blocked_event_kind,
+                EventId::INVALID,
+                EventKind::QueryBlocked,
+                Some(EventLabel::Parent),
+            )
         })
     }
 
@@ -329,10 +380,12 @@ pub fn query_blocked(&self) -> TimingGuard<'_> {
     #[inline(always)]
     pub fn incr_cache_loading(&self) -> TimingGuard<'_> {
         self.exec(EventFilter::INCR_CACHE_LOADS, |profiler| {
-            TimingGuard::start(
+            TimingGuard::start_recorded(
                 profiler,
                 profiler.incremental_load_result_event_kind,
                 EventId::INVALID,
+                EventKind::IncrCacheLoad,
+                Some(EventLabel::Parent),
             )
         })
     }
@@ -341,18 +394,26 @@ pub fn incr_cache_loading(&self) -> TimingGuard<'_> {
     fn instant_query_event(
         &self,
         event_kind: fn(&SelfProfiler) -> StringId,
+        recorded_kind: EventKind,
         query_invocation_id: QueryInvocationId,
         event_filter: EventFilter,
     ) {
         drop(self.exec(event_filter, |profiler| {
             let event_id = StringId::new_virtual(query_invocation_id.0);
-            let thread_id = std::thread::current().id().as_u64().get() as u32;
+            let thread_id = get_thread_id();
 
-            profiler.profiler.record_instant_event(
-                event_kind(profiler),
-                EventId::from_virtual(event_id),
-                thread_id,
-            );
+            if let Some(raw_profiler) = &profiler.profiler {
+                raw_profiler.record_instant_event(
+                    event_kind(profiler),
+                    EventId::from_virtual(event_id),
+                    thread_id,
+                );
+            }
+
+            if let Some(recorder) = &profiler.recorder {
+                let label = EventLabel::Query(query_invocation_id.0);
+                recorder.record_instant(recorded_kind, label, thread_id);
+            }
 
             TimingGuard::none()
         }));
@@ -380,7 +441,10 @@ pub fn get_self_profiler(&self) -> Option<Arc<SelfProfiler>> {
 }
 
 pub struct SelfProfiler {
-    profiler: Profiler,
+    /// The `measureme` profiler writing the raw event data. This is `None` if
+    /// events are only kept in memory, i.e. with `-Z self-profile-summary` or
+    /// `-Z self-profile-chrome-trace` but without `-Z self-profile`.
+    profiler: Option<Profiler>,
     event_filter_mask: EventFilter,
 
     string_cache: RwLock<FxHashMap<String, StringId>>,
@@ -390,26 +454,44 @@ pub struct SelfProfiler {
     incremental_load_result_event_kind: StringId,
     query_blocked_event_kind: StringId,
     query_cache_hit_event_kind: StringId,
+
+    /// Keeps a copy of all events in memory. This is only `Some` with
+    /// `-Z self-profile-summary` or `-Z self-profile-chrome-trace`.
+    recorder: Option<EventRecorder>,
 }
 
 impl SelfProfiler {
+    /// Creates a profiler writing the raw event data into `output_directory`.
+    /// If there is no output directory, `record_events` must be set and the
+    /// events are only kept in memory.
     pub fn new(
-        output_directory: &Path,
+        output_directory: Option<&Path>,
         crate_name: Option<&str>,
         event_filters: &Option<Vec<String>>,
+        record_events: bool,
     ) -> Result<SelfProfiler, Box<dyn Error + Send + Sync>> {
-        fs::create_dir_all(output_directory)?;
+        assert!(output_directory.is_some() || record_events);
 
-        let crate_name = crate_name.unwrap_or("unknown-crate");
-        let filename = format!("{}-{}.rustc_profile", crate_name, process::id());
-        let path = output_directory.join(&filename);
-        let profiler = Profiler::new(&path)?;
+        let profiler = match output_directory {
+            Some(output_directory) => {
+                fs::create_dir_all(output_directory)?;
 
-        let query_event_kind = profiler.alloc_string("Query");
-        let generic_activity_event_kind = profiler.alloc_string("GenericActivity");
-        let incremental_load_result_event_kind = profiler.alloc_string("IncrementalLoadResult");
-        let query_blocked_event_kind = profiler.alloc_string("QueryBlocked");
-        let query_cache_hit_event_kind = profiler.alloc_string("QueryCacheHit");
+                let crate_name = crate_name.unwrap_or("unknown-crate");
+                let filename = format!("{}-{}.rustc_profile", crate_name, process::id());
+                let path = output_directory.join(&filename);
+                Some(Profiler::new(&path)?)
+            }
+            None => None,
+        };
+
+        let alloc_string = |s: &str| {
+            profiler.as_ref().map_or(StringId::INVALID, |profiler| profiler.alloc_string(s))
+        };
+        let query_event_kind = alloc_string("Query");
+        let generic_activity_event_kind = alloc_string("GenericActivity");
+        let incremental_load_result_event_kind = alloc_string("IncrementalLoadResult");
+        let query_blocked_event_kind = alloc_string("QueryBlocked");
+        let query_cache_hit_event_kind = alloc_string("QueryCacheHit");
 
         let mut event_filter_mask = EventFilter::empty();
 
@@ -444,6 +526,12 @@ pub fn new(
             event_filter_mask = EventFilter::DEFAULT;
         }
 
+        // The summary reports cache hits, even though they aren't recorded by
+        // default.
+        if record_events {
+            event_filter_mask |= EventFilter::QUERY_CACHE_HITS;
+        }
+
         Ok(SelfProfiler {
             profiler,
             event_filter_mask,
@@ -453,13 +541,17 @@ pub fn new(
             incremental_load_result_event_kind,
             query_blocked_event_kind,
             query_cache_hit_event_kind,
+            recorder: if record_events { Some(EventRecorder::new()) } else { None },
         })
     }
 
     /// Allocates a new string in the profiling data. Does not do any caching
     /// or deduplication.
     pub fn alloc_string<STR: SerializableString + ?Sized>(&self, s: &STR) -> StringId {
-        self.profiler.alloc_string(s)
+        match &self.profiler {
+            Some(profiler) => profiler.alloc_string(s),
+            None => StringId::INVALID,
+        }
     }
 
     /// Gets a `StringId` for the given string. This method makes sure that
@@ -469,6 +561,11 @@ pub fn get_or_alloc_cached_string<A>(&self, s: A) -> StringId
     where
         A: Borrow<str> + Into<String>,
     {
+        let profiler = match &self.profiler {
+            Some(profiler) => profiler,
+            None => return StringId::INVALID,
+        };
+
         // Only acquire a read-lock first since we assume that the string is
         // already present in the common case.
         {
@@ -485,36 +582,99 @@ pub fn get_or_alloc_cached_string<A>(&self, s: A) -> StringId
         match string_cache.entry(s.into()) {
             Entry::Occupied(e) => *e.get(),
             Entry::Vacant(e) => {
-                let string_id = self.profiler.alloc_string(&e.key()[..]);
+                let string_id = profiler.alloc_string(&e.key()[..]);
                 *e.insert(string_id)
             }
         }
     }
 
     pub fn map_query_invocation_id_to_string(&self, from: QueryInvocationId, to: StringId) {
-        let from = StringId::new_virtual(from.0);
-        self.profiler.map_virtual_to_concrete_string(from, to);
+        if let Some(profiler) = &self.profiler {
+            let from = StringId::new_virtual(from.0);
+            profiler.map_virtual_to_concrete_string(from, to);
+        }
     }
 
     pub fn bulk_map_query_invocation_id_to_single_string<I>(&self, from: I, to: StringId)
     where
         I: Iterator<Item = QueryInvocationId> + ExactSizeIterator,
     {
-        let from = from.map(|qid| StringId::new_virtual(qid.0));
-        self.profiler.bulk_map_virtual_to_single_concrete_string(from, to);
+        if let Some(profiler) = &self.profiler {
+            let from = from.map(|qid| StringId::new_virtual(qid.0));
+            profiler.bulk_map_virtual_to_single_concrete_string(from, to);
+        }
     }
 
     pub fn query_key_recording_enabled(&self) -> bool {
         self.event_filter_mask.contains(EventFilter::QUERY_KEYS)
     }
 
-    pub fn event_id_builder(&self) -> EventIdBuilder<'_> {
-        EventIdBuilder::new(&self.profiler)
+    /// Returns `None` if no raw event data is written, in which case there is
+    /// no need to build any event ids.
+    pub fn event_id_builder(&self) -> Option<EventIdBuilder<'_>> {
+        self.profiler.as_ref().map(EventIdBuilder::new)
     }
+
+    pub fn event_recording_enabled(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Associates query invocation ids with the name of the query, for the
+    /// events kept in memory.
+    pub fn record_query_names<I>(&self, query_invocation_ids: I, query_name: &'static str)
+    where
+        I: Iterator<Item = QueryInvocationId>,
+    {
+        if let Some(recorder) = &self.recorder {
+            recorder.record_query_names(query_invocation_ids.map(|id| id.0), query_name);
+        }
+    }
+
+    /// Prints the `n` most expensive queries and activities to stderr. Must
+    /// only be called after all query names have been recorded.
+    pub fn print_summary(&self, n: usize) {
+        if let Some(recorder) = &self.recorder {
+            recorder.summarize().print(n);
+        }
+    }
+
+    /// Writes all events kept in memory to `path` as a Chrome trace. Must
+    /// only be called after all query names have been recorded.
+    pub fn write_chrome_trace(&self, path: &Path) -> io::Result<()> {
+        match &self.recorder {
+            Some(recorder) => recorder.write_chrome_trace(path),
+            None => Ok(()),
+        }
+    }
+}
+
+fn get_thread_id() -> u32 {
+    std::thread::current().id().as_u64().get() as u32
 }
 
 #[must_use]
-pub struct TimingGuard<'a>(Option<measureme::TimingGuard<'a>>);
+pub struct TimingGuard<'a> {
+    raw: Option<measureme::TimingGuard<'a>>,
+    recording: Option<RecordingGuard<'a>>,
+}
+
+/// Records an interval event into the `EventRecorder` when dropped.
+struct RecordingGuard<'a> {
+    recorder: &'a EventRecorder,
+    kind: EventKind,
+    // Events without a label are not recorded.
+    label: Option<EventLabel>,
+    thread_id: u32,
+    start: Duration,
+}
+
+impl Drop for RecordingGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(label) = self.label {
+            self.recorder.record_interval(self.kind, label, self.thread_id, self.start);
+        }
+    }
+}
 
 impl<'a> TimingGuard<'a> {
     #[inline]
@@ -523,27 +683,60 @@ pub fn start(
         event_kind: StringId,
         event_id: EventId,
     ) -> TimingGuard<'a> {
-        let thread_id = std::thread::current().id().as_u64().get() as u32;
-        let raw_profiler = &profiler.profiler;
-        let timing_guard =
-            raw_profiler.start_recording_interval_event(event_kind, event_id, thread_id);
-        TimingGuard(Some(timing_guard))
+        let raw = TimingGuard::start_raw(profiler, event_kind, event_id, get_thread_id());
+        TimingGuard { raw, recording: None }
+    }
+
+    #[inline]
+    fn start_raw(
+        profiler: &'a SelfProfiler,
+        event_kind: StringId,
+        event_id: EventId,
+        thread_id: u32,
+    ) -> Option<measureme::TimingGuard<'a>> {
+        let raw_profiler = profiler.profiler.as_ref()?;
+        Some(raw_profiler.start_recording_interval_event(event_kind, event_id, thread_id))
+    }
+
+    /// Like `start`, but also keeps the event in memory if
+    /// `SelfProfiler::event_recording_enabled` is set.
+    #[inline]
+    fn start_recorded(
+        profiler: &'a SelfProfiler,
+        event_kind: StringId,
+        event_id: EventId,
+        recorded_kind: EventKind,
+        label: Option<EventLabel>,
+    ) -> TimingGuard<'a> {
+        let thread_id = get_thread_id();
+        let raw = TimingGuard::start_raw(profiler, event_kind, event_id, thread_id);
+        let recording = profiler.recorder.as_ref().map(|recorder| RecordingGuard {
+            recorder,
+            kind: recorded_kind,
+            label,
+            thread_id,
+            start: recorder.now(),
+        });
+        TimingGuard { raw, recording }
     }
 
     #[inline]
     pub fn finish_with_query_invocation_id(self, query_invocation_id: QueryInvocationId) {
-        if let Some(guard) = self.0 {
+        if let Some(guard) = self.raw {
             cold_path(|| {
                 let event_id = StringId::new_virtual(query_invocation_id.0);
                 let event_id = EventId::from_virtual(event_id);
                 guard.finish_with_override_event_id(event_id);
             });
         }
+        if let Some(mut recording) = self.recording {
+            recording.label = Some(EventLabel::Query(query_invocation_id.0));
+        }
     }
 
     #[inline]
     pub fn none() -> TimingGuard<'a> {
-        TimingGuard(None)
+        TimingGuard { raw: None, recording: None }
     }
 
     #[inline(always)]
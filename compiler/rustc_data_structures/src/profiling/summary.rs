@@ -0,0 +1,323 @@
+//! In-memory recording of self-profiling events.
+//!
+//! The events written by `measureme` can only be read back with the external
+//! `analyzeme` tools. When `-Z self-profile-summary` or
+//! `-Z self-profile-chrome-trace` is given, the `SelfProfiler` additionally
+//! keeps a copy of every event it records in memory. At the end of the
+//! compilation session, once query invocation ids have been associated with
+//! query names, these events are aggregated into a summary table or written out
+//! as a Chrome trace.
+
+use crate::fx::FxHashMap;
+
+use std::cmp::Reverse;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) enum EventKind {
+    GenericActivity,
+    Query,
+    QueryBlocked,
+    IncrCacheLoad,
+    QueryCacheHit,
+}
+
+impl EventKind {
+    /// The name of the kind, as used for the `measureme` event kinds.
+    fn name(self) -> &'static str {
+        match self {
+            EventKind::GenericActivity => "GenericActivity",
+            EventKind::Query => "Query",
+            EventKind::QueryBlocked => "QueryBlocked",
+            EventKind::IncrCacheLoad => "IncrementalLoadResult",
+            EventKind::QueryCacheHit => "QueryCacheHit",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) enum EventLabel {
+    /// The label of a generic activity.
+    Activity(&'static str),
+    /// A query invocation, resolved to the name of the query at the end of
+    /// the session.
+    Query(u32),
+    /// The event is accounted to the innermost enclosing event on the same
+    /// thread. This is used for blocking on a query and for loading a query
+    /// result from the incremental cache.
+    Parent,
+}
+
+#[derive(Debug)]
+struct Event {
+    kind: EventKind,
+    label: EventLabel,
+    thread_id: u32,
+    start: Duration,
+    /// Equal to `start` for instant events.
+    end: Duration,
+}
+
+pub(super) struct EventRecorder {
+    start: Instant,
+    events: Mutex<Vec<Event>>,
+    query_names: Mutex<FxHashMap<u32, &'static str>>,
+}
+
+impl EventRecorder {
+    pub(super) fn new() -> EventRecorder {
+        EventRecorder {
+            start: Instant::now(),
+            events: Mutex::new(Vec::new()),
+            query_names: Mutex::new(FxHashMap::default()),
+        }
+    }
+
+    /// The current time, relative to the start of the recording.
+    #[inline]
+    pub(super) fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    pub(super) fn record_interval(
+        &self,
+        kind: EventKind,
+        label: EventLabel,
+        thread_id: u32,
+        start: Duration,
+    ) {
+        let end = self.now();
+        self.events.lock().push(Event { kind, label, thread_id, start, end });
+    }
+
+    pub(super) fn record_instant(&self, kind: EventKind, label: EventLabel, thread_id: u32) {
+        let now = self.now();
+        self.events.lock().push(Event { kind, label, thread_id, start: now, end: now });
+    }
+
+    pub(super) fn record_query_names(
+        &self,
+        query_invocation_ids: impl Iterator<Item = u32>,
+        query_name: &'static str,
+    ) {
+        let mut query_names = self.query_names.lock();
+        for id in query_invocation_ids {
+            query_names.insert(id, query_name);
+        }
+    }
+
+    /// Computes the per-query and per-activity statistics of all events
+    /// recorded so far.
+    pub(super) fn summarize(&self) -> Summary {
+        let events = self.events.lock();
+        let query_names = self.query_names.lock();
+        summarize(&events, &query_names)
+    }
+
+    /// Writes all events recorded so far in the Chrome trace event format,
+    /// which can be loaded into `chrome://tracing` or Perfetto.
+    pub(super) fn write_chrome_trace(&self, path: &Path) -> io::Result<()> {
+        let events = self.events.lock();
+        let query_names = self.query_names.lock();
+
+        let pid = std::process::id();
+        let mut trace_events: Vec<_> = events
+            .iter()
+            .map(|event| {
+                let name = match event.label {
+                    EventLabel::Parent => event.kind.name(),
+                    label => label_name(label, &query_names),
+                };
+                let instant = event.kind == EventKind::QueryCacheHit;
+                TraceEvent {
+                    name,
+                    cat: event.kind.name(),
+                    ph: if instant { "i" } else { "X" },
+                    ts: event.start.as_secs_f64() * 1_000_000.0,
+                    dur: (event.end - event.start).as_secs_f64() * 1_000_000.0,
+                    s: if instant { Some("t") } else { None },
+                    pid,
+                    tid: event.thread_id,
+                }
+            })
+            .collect();
+        trace_events.sort_by(|a, b| a.ts.partial_cmp(&b.ts).unwrap());
+
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "{}", rustc_serialize::json::as_json(&trace_events))?;
+        file.flush()
+    }
+}
+
+/// An event in the Chrome trace event format.
+#[derive(Encodable)]
+struct TraceEvent {
+    name: &'static str,
+    cat: &'static str,
+    /// `X` for complete events, `i` for instant events.
+    ph: &'static str,
+    /// Start time in microseconds.
+    ts: f64,
+    /// Duration in microseconds.
+    dur: f64,
+    /// The scope of instant events.
+    s: Option<&'static str>,
+    pid: u32,
+    tid: u32,
+}
+
+fn label_name(label: EventLabel, query_names: &FxHashMap<u32, &'static str>) -> &'static str {
+    match label {
+        EventLabel::Activity(name) => name,
+        EventLabel::Query(id) => query_names.get(&id).copied().unwrap_or("<unknown>"),
+        EventLabel::Parent => "<unknown>",
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct ItemStats {
+    /// The time spent in the query provider or activity itself, excluding the
+    /// time spent in nested queries and activities, blocked on other threads
+    /// and loading from the incremental cache.
+    pub self_time: Duration,
+    pub invocation_count: usize,
+    pub cache_hits: usize,
+    /// The time spent waiting on other threads executing a query.
+    pub blocked_time: Duration,
+    /// The time spent loading query results from the incremental cache.
+    pub incr_load_time: Duration,
+}
+
+pub struct Summary {
+    pub items: Vec<(&'static str, ItemStats)>,
+    /// The sum of the self times of all items.
+    pub total_time: Duration,
+}
+
+fn summarize(events: &[Event], query_names: &FxHashMap<u32, &'static str>) -> Summary {
+    let mut stats: FxHashMap<&'static str, ItemStats> = FxHashMap::default();
+
+    let mut events_by_thread: FxHashMap<u32, Vec<&Event>> = FxHashMap::default();
+    for event in events {
+        if event.kind == EventKind::QueryCacheHit {
+            stats.entry(label_name(event.label, query_names)).or_default().cache_hits += 1;
+        } else {
+            events_by_thread.entry(event.thread_id).or_default().push(event);
+        }
+    }
+
+    for (_, mut events) in events_by_thread {
+        // Parents come before their children, which they enclose.
+        events.sort_by_key(|event| (event.start, Reverse(event.end)));
+
+        // The labelled events enclosing the current one, with their end time
+        // and their self time computed so far.
+        let mut stack: Vec<(&'static str, Duration, Duration)> = Vec::new();
+
+        for event in events {
+            while let Some(&(_, end, _)) = stack.last() {
+                if end > event.start {
+                    break;
+                }
+                let (name, _, self_time) = stack.pop().unwrap();
+                stats.entry(name).or_default().self_time += self_time;
+            }
+
+            // Time spent in children, including blocking on a query or
+            // loading from the incremental cache, is not self time.
+            let duration = event.end - event.start;
+            if let Some((_, _, parent_self_time)) = stack.last_mut() {
+                *parent_self_time = parent_self_time.checked_sub(duration).unwrap_or_default();
+            }
+
+            if let EventLabel::Parent = event.label {
+                let name = stack.last().map_or("<unknown>", |&(name, _, _)| name);
+                let item = stats.entry(name).or_default();
+                match event.kind {
+                    EventKind::QueryBlocked => item.blocked_time += duration,
+                    EventKind::IncrCacheLoad => item.incr_load_time += duration,
+                    _ => {}
+                }
+                continue;
+            }
+
+            let name = label_name(event.label, query_names);
+            stats.entry(name).or_default().invocation_count += 1;
+            stack.push((name, event.end, duration));
+        }
+
+        for (name, _, self_time) in stack {
+            stats.entry(name).or_default().self_time += self_time;
+        }
+    }
+
+    let total_time = stats.values().map(|item| item.self_time).sum();
+    let mut items: Vec<_> = stats.into_iter().collect();
+    items.sort_by_key(|&(name, item)| (Reverse(item.self_time), name));
+    Summary { items, total_time }
+}
+
+impl Summary {
+    /// Prints the `n` items with the highest self time, invocation count and
+    /// number of cache hits to stderr.
+    pub fn print(&self, n: usize) {
+        let mut items = self.items.clone();
+
+        eprintln!("\nTop {} queries and activities by self time:", n);
+        self.print_table(&items[..n.min(items.len())]);
+
+        items.sort_by_key(|&(name, item)| (Reverse(item.invocation_count), name));
+        eprintln!("\nTop {} queries and activities by invocation count:", n);
+        self.print_table(&items[..n.min(items.len())]);
+
+        items.retain(|(_, item)| item.cache_hits > 0);
+        items.sort_by_key(|&(name, item)| (Reverse(item.cache_hits), name));
+        eprintln!("\nTop {} queries by cache hits:", n);
+        self.print_table(&items[..n.min(items.len())]);
+    }
+
+    fn print_table(&self, items: &[(&'static str, ItemStats)]) {
+        let name_width = items.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max(4);
+        eprintln!(
+            "   {:<name_width$}  {:>10}  {:>7}  {:>11}  {:>10}  {:>12}  {:>15}",
+            "Item",
+            "Self time",
+            "% total",
+            "Invocations",
+            "Cache hits",
+            "Blocked time",
+            "Incr. load time",
+            name_width = name_width,
+        );
+        for (name, item) in items {
+            let percent = if self.total_time == Duration::default() {
+                0.0
+            } else {
+                item.self_time.as_secs_f64() * 100.0 / self.total_time.as_secs_f64()
+            };
+            eprintln!(
+                "   {:<name_width$}  {:>10}  {:>6.2}%  {:>11}  {:>10}  {:>12}  {:>15}",
+                name,
+                format_duration(item.self_time),
+                percent,
+                item.invocation_count,
+                item.cache_hits,
+                format_duration(item.blocked_time),
+                format_duration(item.incr_load_time),
+                name_width = name_width,
+            );
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
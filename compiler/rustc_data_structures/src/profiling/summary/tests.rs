@@ -0,0 +1,49 @@
+use super::*;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+fn interval(kind: EventKind, label: EventLabel, start: u64, end: u64) -> Event {
+    Event { kind, label, thread_id: 0, start: ms(start), end: ms(end) }
+}
+
+fn stats<'a>(summary: &'a Summary, name: &str) -> &'a ItemStats {
+    &summary.items.iter().find(|(n, _)| *n == name).unwrap().1
+}
+
+#[test]
+fn test_summarize_self_time() {
+    let mut query_names = FxHashMap::default();
+    query_names.insert(1, "typeck");
+    query_names.insert(2, "type_of");
+
+    let events = vec![
+        interval(EventKind::GenericActivity, EventLabel::Activity("analysis"), 0, 100),
+        interval(EventKind::Query, EventLabel::Query(1), 10, 60),
+        interval(EventKind::Query, EventLabel::Query(2), 20, 30),
+        interval(EventKind::IncrCacheLoad, EventLabel::Parent, 40, 45),
+        interval(EventKind::QueryBlocked, EventLabel::Parent, 90, 93),
+        interval(EventKind::Query, EventLabel::Query(2), 70, 75),
+        interval(EventKind::QueryCacheHit, EventLabel::Query(2), 80, 80),
+        interval(EventKind::QueryCacheHit, EventLabel::Query(2), 81, 81),
+    ];
+
+    let summary = summarize(&events, &query_names);
+    assert_eq!(summary.total_time, ms(92));
+    assert_eq!(summary.items[0].0, "analysis");
+
+    let analysis = stats(&summary, "analysis");
+    assert_eq!(analysis.self_time, ms(42));
+    assert_eq!(analysis.invocation_count, 1);
+    assert_eq!(analysis.blocked_time, ms(3));
+
+    let typeck = stats(&summary, "typeck");
+    assert_eq!(typeck.self_time, ms(35));
+    assert_eq!(typeck.incr_load_time, ms(5));
+
+    let type_of = stats(&summary, "type_of");
+    assert_eq!(type_of.self_time, ms(15));
+    assert_eq!(type_of.invocation_count, 2);
+    assert_eq!(type_of.cache_hits, 2);
+}
@@ -1,4 +1,5 @@
 use crate::expand::{self, AstFragment, Invocation};
+use crate::explorer::ExpansionExplorer;
 use crate::module::DirOwnership;
 
 use rustc_ast::ptr::P;
@@ -350,6 +351,14 @@ fn expand<'cx>(
         span: Span,
         input: TokenStream,
     ) -> Box<dyn MacResult + 'cx>;
+
+    /// Expands an invocation into the tokens it is replaced with, without parsing them or
+    /// expanding the invocations they contain, for tools that show what an invocation expands
+    /// to. Only `macro_rules!` macros support this, others return `None`. Errors in the
+    /// invocation are reported to `sess`.
+    fn expand_tokens(&self, _sess: &ParseSess, _input: TokenStream) -> Option<TokenStream> {
+        None
+    }
 }
 
 pub type MacroExpanderFn =
@@ -784,9 +793,17 @@ pub fn new(
             }
         }
 
-        let builtin_name = sess
-            .find_by_name(attrs, sym::rustc_builtin_macro)
-            .map(|a| a.value_str().unwrap_or(name));
+        let builtin_attr = sess.find_by_name(attrs, sym::rustc_builtin_macro);
+        let (builtin_name, helper_attrs) = match builtin_attr {
+            // `#[rustc_builtin_macro(Name, attributes(helper, ...))]` declares a built-in derive
+            // macro with helper attributes, overriding the `helper_attrs` passed above.
+            Some(attr) if attr.meta_item_list().is_some() => {
+                parse_macro_name_and_helper_attrs(sess.diagnostic(), attr, "built-in")
+                    .map_or_else(|| (Some(name), Vec::new()), |(name, attrs)| (Some(name), attrs))
+            }
+            Some(attr) => (Some(attr.value_str().unwrap_or(name)), helper_attrs),
+            None => (None, helper_attrs),
+        };
         let (stability, const_stability) = attr::find_stability(&sess, attrs, span);
         if let Some((_, sp)) = const_stability {
             sess.parse_sess
@@ -913,6 +930,8 @@ fn take_derive_resolutions(
     ) -> Option<Vec<(Lrc<SyntaxExtension>, ast::Path)>>;
     /// Path resolution logic for `#[cfg_accessible(path)]`.
     fn cfg_accessible(&mut self, expn_id: ExpnId, path: &ast::Path) -> Result<bool, Indeterminate>;
+    /// Whether `tool` was declared with `#![register_tool]` or is predefined.
+    fn is_registered_tool(&self, tool: Symbol) -> bool;
 }
 
 #[derive(Clone, Default)]
@@ -963,6 +982,8 @@ pub struct ExtCtxt<'a> {
     /// (or during eager expansion, but that's a hack).
     pub force_mode: bool,
     pub expansions: FxHashMap<Span, Vec<String>>,
+    /// Records of all expansions, with `-Z expansion-explorer`.
+    pub expansion_explorer: Option<ExpansionExplorer>,
     /// Called directly after having parsed an external `mod foo;` in expansion.
     ///
     /// `Ident` is the module name.
@@ -992,6 +1013,12 @@ pub fn new(
             },
             force_mode: false,
             expansions: FxHashMap::default(),
+            expansion_explorer: sess
+                .opts
+                .debugging_opts
+                .expansion_explorer
+                .is_some()
+                .then(ExpansionExplorer::default),
         }
     }
 
@@ -1241,3 +1268,86 @@ pub fn get_exprs_from_tts(
     }
     Some(es)
 }
+
+/// Parses the arguments of `#[proc_macro_derive(Name, attributes(helper, ...))]` or
+/// `#[rustc_builtin_macro(Name, attributes(helper, ...))]` into the name of the derive macro and
+/// the names of its helper attributes. Returns `None` if the arguments are malformed, after
+/// reporting an error (except if there is no argument list at all).
+pub fn parse_macro_name_and_helper_attrs(
+    diag: &rustc_errors::Handler,
+    attr: &Attribute,
+    descr: &str,
+) -> Option<(Symbol, Vec<Symbol>)> {
+    // Once we've located the `#[proc_macro_derive]` attribute, verify
+    // that it's of the form `#[proc_macro_derive(Foo)]` or
+    // `#[proc_macro_derive(Foo, attributes(A, ..))]`
+    let list = attr.meta_item_list()?;
+    if list.len() != 1 && list.len() != 2 {
+        diag.span_err(attr.span, "attribute must have either one or two arguments");
+        return None;
+    }
+    let trait_attr = match list[0].meta_item() {
+        Some(meta_item) => meta_item,
+        _ => {
+            diag.span_err(list[0].span(), "not a meta item");
+            return None;
+        }
+    };
+    let trait_ident = match trait_attr.ident() {
+        Some(trait_ident) if trait_attr.is_word() => trait_ident,
+        _ => {
+            diag.span_err(trait_attr.span, "must only be one word");
+            return None;
+        }
+    };
+
+    if !trait_ident.name.can_be_raw() {
+        diag.span_err(
+            trait_attr.span,
+            &format!("`{}` cannot be a name of {} macro", trait_ident, descr),
+        );
+    }
+
+    let attributes_attr = list.get(1);
+    let proc_attrs: Vec<_> = if let Some(attr) = attributes_attr {
+        if !attr.has_name(sym::attributes) {
+            diag.span_err(attr.span(), "second argument must be `attributes`")
+        }
+        attr.meta_item_list()
+            .unwrap_or_else(|| {
+                diag.span_err(attr.span(), "attribute must be of form: `attributes(foo, bar)`");
+                &[]
+            })
+            .iter()
+            .filter_map(|attr| {
+                let attr = match attr.meta_item() {
+                    Some(meta_item) => meta_item,
+                    _ => {
+                        diag.span_err(attr.span(), "not a meta item");
+                        return None;
+                    }
+                };
+
+                let ident = match attr.ident() {
+                    Some(ident) if attr.is_word() => ident,
+                    _ => {
+                        diag.span_err(attr.span, "must only be one word");
+                        return None;
+                    }
+                };
+                if !ident.name.can_be_raw() {
+                    diag.span_err(
+                        attr.span,
+                        &format!("`{}` cannot be a name of derive helper attribute", ident),
+                    );
+                }
+
+                Some(ident.name)
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Some((trait_ident.name, proc_attrs))
+}
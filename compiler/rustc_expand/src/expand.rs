@@ -485,6 +485,10 @@ pub fn fully_expand_fragment(&mut self, input_fragment: AstFragment) -> AstFragm
             let fragment_kind = invoc.fragment_kind;
             let (expanded_fragment, new_invocations) = match self.expand_invoc(invoc, &ext.kind) {
                 ExpandResult::Ready(fragment) => {
+                    if let Some(explorer) = &mut self.cx.expansion_explorer {
+                        explorer.record(self.cx.sess.source_map(), expn_id, depth, &fragment);
+                    }
+
                     let derive_placeholders = self
                         .cx
                         .resolver
@@ -0,0 +1,124 @@
+//! Recording of macro expansions for `-Z expansion-explorer`.
+//!
+//! Every expansion performed by `MacroExpander::fully_expand_fragment` is recorded together with
+//! the expansion that produced its invocation, so that editors can replay the expansion of a
+//! crate step by step. The records are written out as a JSON array once the crate is expanded.
+
+use crate::expand::AstFragment;
+
+use rustc_ast_pretty::pprust;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_serialize::json;
+use rustc_span::hygiene::{ExpnId, ExpnKind, MacroKind};
+use rustc_span::source_map::SourceMap;
+use rustc_span::Span;
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Default)]
+pub struct ExpansionExplorer {
+    records: Vec<ExpansionRecord>,
+    /// The index of the `macro_rules!` arm that matched, for expansions of declarative macros.
+    matched_arms: FxHashMap<ExpnId, usize>,
+}
+
+#[derive(Encodable)]
+struct ExpansionRecord {
+    /// The id of this expansion, which is the `parent` of the expansions it produced.
+    id: u32,
+    /// The expansion that produced the invocation, or the id of the root expansion (`0`) for
+    /// invocations written in the source.
+    parent: u32,
+    depth: usize,
+    /// One of `bang`, `attr` or `derive`.
+    kind: String,
+    name: String,
+    call_site: SpanLocation,
+    /// The definition of the macro, if it has one in the source.
+    def_site: Option<SpanLocation>,
+    arm: Option<usize>,
+    /// The pretty-printed output of the expansion, still containing the nested invocations.
+    output: Option<String>,
+}
+
+#[derive(Encodable)]
+struct SpanLocation {
+    file: String,
+    lo_line: usize,
+    lo_col: usize,
+    hi_line: usize,
+    hi_col: usize,
+}
+
+impl SpanLocation {
+    fn new(source_map: &SourceMap, span: Span) -> SpanLocation {
+        let lo = source_map.lookup_char_pos(span.lo());
+        let hi = source_map.lookup_char_pos(span.hi());
+        SpanLocation {
+            file: lo.file.name.to_string(),
+            lo_line: lo.line,
+            lo_col: lo.col.0 + 1,
+            hi_line: hi.line,
+            hi_col: hi.col.0 + 1,
+        }
+    }
+}
+
+impl ExpansionExplorer {
+    crate fn record_arm(&mut self, expn_id: ExpnId, arm: usize) {
+        self.matched_arms.insert(expn_id, arm);
+    }
+
+    crate fn record(
+        &mut self,
+        source_map: &SourceMap,
+        expn_id: ExpnId,
+        depth: usize,
+        fragment: &AstFragment,
+    ) {
+        let expn_data = expn_id.expn_data();
+        let (kind, name) = match expn_data.kind {
+            ExpnKind::Macro(MacroKind::Bang, name) => ("bang", name),
+            ExpnKind::Macro(MacroKind::Attr, name) => ("attr", name),
+            ExpnKind::Macro(MacroKind::Derive, name) => ("derive", name),
+            _ => return,
+        };
+        let def_site = expn_data.def_site;
+        self.records.push(ExpansionRecord {
+            id: expn_id.as_u32(),
+            parent: expn_data.parent.as_u32(),
+            depth,
+            kind: kind.to_string(),
+            name: name.to_string(),
+            call_site: SpanLocation::new(source_map, expn_data.call_site),
+            def_site: (!def_site.is_dummy()).then(|| SpanLocation::new(source_map, def_site)),
+            arm: self.matched_arms.remove(&expn_id),
+            output: fragment_to_string(fragment),
+        });
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "{}", json::as_json(&self.records))?;
+        file.flush()
+    }
+}
+
+fn fragment_to_string(fragment: &AstFragment) -> Option<String> {
+    let parts: Vec<String> = match fragment {
+        AstFragment::OptExpr(expr) => expr.iter().map(|e| pprust::expr_to_string(e)).collect(),
+        AstFragment::Expr(expr) => vec![pprust::expr_to_string(expr)],
+        AstFragment::Pat(pat) => vec![pprust::pat_to_string(pat)],
+        AstFragment::Ty(ty) => vec![pprust::ty_to_string(ty)],
+        AstFragment::Stmts(stmts) => stmts.iter().map(pprust::stmt_to_string).collect(),
+        AstFragment::Items(items) => items.iter().map(|i| pprust::item_to_string(i)).collect(),
+        AstFragment::Params(params) => params.iter().map(pprust::param_to_string).collect(),
+        AstFragment::GenericParams(params) => vec![pprust::generic_params_to_string(params)],
+        // The remaining fragments can only be produced by attribute macros on the corresponding
+        // nodes, which can't be pretty-printed on their own.
+        _ => return None,
+    };
+    Some(parts.join("\n"))
+}
@@ -6,6 +6,7 @@
 #![feature(proc_macro_diagnostic)]
 #![feature(proc_macro_internals)]
 #![feature(proc_macro_span)]
+#![feature(proc_macro_value)]
 #![feature(try_blocks)]
 
 #[macro_use]
@@ -23,6 +24,7 @@
 #[macro_use]
 pub mod config;
 pub mod expand;
+pub mod explorer;
 pub mod module;
 pub mod proc_macro;
 
@@ -104,11 +104,13 @@
 //! Kleene operators under which a meta-variable is repeating is the concatenation of the stacks
 //! stored when entering a macro definition starting from the state in which the meta-variable is
 //! bound.
+use crate::mbe::metavar_expr::MetaVarExpr;
 use crate::mbe::{KleeneToken, TokenTree};
 
 use rustc_ast::token::{DelimToken, Token, TokenKind};
 use rustc_ast::{NodeId, DUMMY_NODE_ID};
 use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::pluralize;
 use rustc_session::lint::builtin::META_VARIABLE_MISUSE;
 use rustc_session::parse::ParseSess;
 use rustc_span::symbol::kw;
@@ -233,7 +235,7 @@ fn check_binders(
     valid: &mut bool,
 ) {
     match *lhs {
-        TokenTree::Token(..) => {}
+        TokenTree::Token(..) | TokenTree::MetaVarExpr(..) => {}
         // This can only happen when checking a nested macro because this LHS is then in the RHS of
         // the outer macro. See ui/macros/macro-of-higher-order.rs where $y:$fragment in the
         // LHS of the nested macro (and RHS of the outer macro) is parsed as MetaVar(y) Colon
@@ -333,6 +335,9 @@ fn check_occurrences(
             let name = MacroRulesNormalizedIdent::new(name);
             check_ops_is_prefix(sess, node_id, macros, binders, ops, span, name);
         }
+        TokenTree::MetaVarExpr(span, ref expr) => {
+            check_metavar_expr(sess, node_id, span.entire(), expr, macros, binders, ops, valid);
+        }
         TokenTree::Delimited(_, ref del) => {
             check_nested_occurrences(sess, node_id, &del.tts, macros, binders, ops, valid);
         }
@@ -535,6 +540,84 @@ fn check_nested_macro(
     i
 }
 
+/// Checks that a meta-variable expression in the RHS of a macro definition can be evaluated, and
+/// sets `valid` to false otherwise. Unlike invalid meta-variable occurrences, which are linted,
+/// these are errors as the expression has no meaningful expansion.
+///
+/// The expressions of nested macro definitions are only checked for unknown meta-variables, as
+/// the repetitions they are used in are only known once the outer macro is expanded.
+///
+/// Arguments:
+/// - `sess` is used to emit diagnostics and lints
+/// - `node_id` is used to emit lints
+/// - `span` is the span of the whole expression, e.g. `${count(x)}`
+/// - `expr` is the meta-variable expression to check
+/// - `macros` is the stack of possible outer macros
+/// - `binders` contains the binders of the associated LHS
+/// - `ops` is the stack of Kleene operators from the RHS
+/// - `valid` is set in case of errors
+fn check_metavar_expr(
+    sess: &ParseSess,
+    node_id: NodeId,
+    span: Span,
+    expr: &MetaVarExpr,
+    macros: &Stack<'_, MacroState<'_>>,
+    binders: &Binders,
+    ops: &Stack<'_, KleeneToken>,
+    valid: &mut bool,
+) {
+    let depth = SmallVec::<[KleeneToken; 1]>::from(ops).len();
+    let mut error = |msg: &str| {
+        sess.span_diagnostic.span_err(span, msg);
+        *valid = false;
+    };
+    match *expr {
+        MetaVarExpr::Ignore(ident) => {
+            let name = MacroRulesNormalizedIdent::new(ident);
+            check_ops_is_prefix(sess, node_id, macros, binders, ops, ident.span, name);
+        }
+        MetaVarExpr::Count(ident, count_depth) => {
+            let name = MacroRulesNormalizedIdent::new(ident);
+            let binder = match get_binder_info(macros, binders, name) {
+                Some(binder) => binder,
+                None => return error(&format!("unknown macro variable `{}`", name)),
+            };
+            if !macros.is_empty() {
+                return;
+            }
+            // The number of repetitions of the meta-variable that are left to count here.
+            let nested = binder.ops.len().saturating_sub(depth);
+            if nested == 0 {
+                error(&format!(
+                    "`count` can only be used on meta-variables that repeat more deeply than \
+                     where it is used, and `{}` does not",
+                    name
+                ));
+            } else if let Some(count_depth) = count_depth.filter(|&d| d >= nested) {
+                error(&format!(
+                    "`count` depth `{}` is out of range, `{}` only repeats {} more time{} here",
+                    count_depth,
+                    name,
+                    nested,
+                    pluralize!(nested)
+                ));
+            }
+        }
+        MetaVarExpr::Index(index_depth) | MetaVarExpr::Length(index_depth) => {
+            if macros.is_empty() && index_depth >= depth {
+                let expr_name = if let MetaVarExpr::Index(..) = expr { "index" } else { "length" };
+                error(&format!(
+                    "`{}` with depth `{}` must be used inside at least {} repetition{}",
+                    expr_name,
+                    index_depth,
+                    index_depth + 1,
+                    pluralize!(index_depth + 1)
+                ));
+            }
+        }
+    }
+}
+
 /// Checks that a meta-variable occurrence is valid.
 ///
 /// Arguments:
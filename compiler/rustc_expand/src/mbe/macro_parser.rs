@@ -77,6 +77,7 @@
 use crate::mbe::{self, TokenTree};
 
 use rustc_ast::token::{self, DocComment, Nonterminal, Token};
+use rustc_ast_pretty::pprust;
 use rustc_parse::parser::Parser;
 use rustc_session::parse::ParseSess;
 use rustc_span::symbol::MacroRulesNormalizedIdent;
@@ -281,7 +282,7 @@ pub(super) fn count_names(ms: &[TokenTree]) -> usize {
             + match *elt {
                 TokenTree::Sequence(_, ref seq) => seq.num_captures,
                 TokenTree::Delimited(_, ref delim) => count_names(&delim.tts),
-                TokenTree::MetaVar(..) => 0,
+                TokenTree::MetaVar(..) | TokenTree::MetaVarExpr(..) => 0,
                 TokenTree::MetaVarDecl(..) => 1,
                 TokenTree::Token(..) => 0,
             }
@@ -391,7 +392,7 @@ fn n_rec<I: Iterator<Item = NamedMatch>>(
                 }
                 Occupied(..) => return Err((sp, format!("duplicated bind name: {}", bind_name))),
             },
-            TokenTree::MetaVar(..) | TokenTree::Token(..) => (),
+            TokenTree::MetaVar(..) | TokenTree::MetaVarExpr(..) | TokenTree::Token(..) => (),
         }
 
         Ok(())
@@ -434,6 +435,8 @@ fn token_name_eq(t1: &Token, t2: &Token) -> bool {
 /// - `eof_items`: the set of items that would be valid if this was the EOF.
 /// - `bb_items`: the set of items that are waiting for the black-box parser.
 /// - `token`: the current token of the parser.
+/// - `expected`: if given, collects a description of every matcher element that could not match
+///   `token`, which is used to explain why a macro arm failed to match.
 ///
 /// # Returns
 ///
@@ -445,7 +448,16 @@ fn inner_parse_loop<'root, 'tt>(
     eof_items: &mut SmallVec<[MatcherPosHandle<'root, 'tt>; 1]>,
     bb_items: &mut SmallVec<[MatcherPosHandle<'root, 'tt>; 1]>,
     token: &Token,
+    mut expected: Option<&mut Vec<String>>,
 ) -> ParseResult<()> {
+    let mut expect = |what: String| {
+        if let Some(expected) = &mut expected {
+            if !expected.contains(&what) {
+                expected.push(what);
+            }
+        }
+    };
+
     // Pop items from `cur_items` until it is empty.
     while let Some(mut item) = cur_items.pop() {
         // When unzipped trees end, remove them. This corresponds to backtracking out of a
@@ -503,6 +515,8 @@ fn inner_parse_loop<'root, 'tt>(
                     if item.sep.as_ref().map_or(false, |sep| token_name_eq(token, sep)) {
                         item.idx += 1;
                         next_items.push(item);
+                    } else if let Some(sep) = &item.sep {
+                        expect(format!("`{}`", pprust::token_to_string(sep)));
                     }
                 }
                 // We don't need a separator. Move the "dot" back to the beginning of the matcher
@@ -566,7 +580,7 @@ fn inner_parse_loop<'root, 'tt>(
 
                 // We need to match a metavar with a valid ident... call out to the black-box
                 // parser by adding an item to `bb_items`.
-                TokenTree::MetaVarDecl(_, _, Some(kind)) => {
+                TokenTree::MetaVarDecl(_, name, Some(kind)) => {
                     // Built-in nonterminals never start with these tokens, so we can eliminate
                     // them from consideration.
                     //
@@ -574,6 +588,8 @@ fn inner_parse_loop<'root, 'tt>(
                     // edition-specific matching behavior for non-terminals.
                     if Parser::nonterminal_may_begin_with(kind, token) {
                         bb_items.push(item);
+                    } else {
+                        expect(format!("`${}:{}`", name, kind));
                     }
                 }
 
@@ -604,7 +620,8 @@ fn inner_parse_loop<'root, 'tt>(
                 // rules. NOTE that this is not necessarily an error unless _all_ items in
                 // `cur_items` end up doing this. There may still be some other matchers that do
                 // end up working out.
-                TokenTree::Token(..) | TokenTree::MetaVar(..) => {}
+                TokenTree::Token(t) => expect(format!("`{}`", pprust::token_to_string(&t))),
+                TokenTree::MetaVar(..) | TokenTree::MetaVarExpr(..) => {}
             }
         }
     }
@@ -616,6 +633,26 @@ fn inner_parse_loop<'root, 'tt>(
 /// Use the given sequence of token trees (`ms`) as a matcher. Match the token
 /// stream from the given `parser` against it and return the match.
 pub(super) fn parse_tt(parser: &mut Cow<'_, Parser<'_>>, ms: &[TokenTree]) -> NamedParseResult {
+    parse_tt_inner(parser, ms, None)
+}
+
+/// Like `parse_tt`, but on `Failure` also returns what the matcher expected instead of the token
+/// it failed at (e.g. `` `,` `` or `` `$e:expr` ``). This is slower than `parse_tt`, so it is only
+/// used to explain why none of the arms of a macro matched.
+pub(super) fn parse_tt_explained(
+    parser: &mut Cow<'_, Parser<'_>>,
+    ms: &[TokenTree],
+) -> (NamedParseResult, Vec<String>) {
+    let mut expected = Vec::new();
+    let result = parse_tt_inner(parser, ms, Some(&mut expected));
+    (result, expected)
+}
+
+fn parse_tt_inner(
+    parser: &mut Cow<'_, Parser<'_>>,
+    ms: &[TokenTree],
+    mut expected: Option<&mut Vec<String>>,
+) -> NamedParseResult {
     // A queue of possible matcher positions. We initialize it with the matcher position in which
     // the "dot" is before the first token of the first token tree in `ms`. `inner_parse_loop` then
     // processes all of these possible matcher positions and produces possible next positions into
@@ -636,6 +673,11 @@ pub(super) fn parse_tt(parser: &mut Cow<'_, Parser<'_>>, ms: &[TokenTree]) -> Na
         let mut eof_items = SmallVec::new();
         assert!(next_items.is_empty());
 
+        // Only the expectations of the last position are interesting.
+        if let Some(expected) = &mut expected {
+            expected.clear();
+        }
+
         // Process `cur_items` until either we have finished the input or we need to get some
         // parsing from the black-box parser done. The result is that `next_items` will contain a
         // bunch of possible next matcher positions in `next_items`.
@@ -646,6 +688,7 @@ pub(super) fn parse_tt(parser: &mut Cow<'_, Parser<'_>>, ms: &[TokenTree]) -> Na
             &mut eof_items,
             &mut bb_items,
             &parser.token,
+            expected.as_deref_mut(),
         ) {
             Success(_) => {}
             Failure(token, msg) => return Failure(token, msg),
@@ -3,7 +3,7 @@
 use crate::expand::{ensure_complete_parse, parse_ast_fragment, AstFragment, AstFragmentKind};
 use crate::mbe;
 use crate::mbe::macro_check;
-use crate::mbe::macro_parser::parse_tt;
+use crate::mbe::macro_parser::{parse_tt, parse_tt_explained};
 use crate::mbe::macro_parser::{Error, ErrorReported, Failure, Success};
 use crate::mbe::macro_parser::{MatchedNonterminal, MatchedSeq};
 use crate::mbe::transcribe::transcribe;
@@ -23,9 +23,9 @@
 use rustc_session::parse::ParseSess;
 use rustc_session::Session;
 use rustc_span::edition::Edition;
-use rustc_span::hygiene::Transparency;
+use rustc_span::hygiene::{ExpnData, ExpnId, ExpnKind, MacroKind, Transparency};
 use rustc_span::symbol::{kw, sym, Ident, MacroRulesNormalizedIdent};
-use rustc_span::Span;
+use rustc_span::{MultiSpan, Span, DUMMY_SP};
 
 use std::borrow::Cow;
 use std::collections::hash_map::Entry;
@@ -174,6 +174,45 @@ fn expand<'cx>(
             &self.rhses,
         )
     }
+
+    fn expand_tokens(&self, sess: &ParseSess, input: TokenStream) -> Option<TokenStream> {
+        if !self.valid {
+            return None;
+        }
+        let parser = parser_from_cx(sess, input);
+        for (lhs, rhs) in self.lhses.iter().zip(&self.rhses) {
+            let (lhs, rhs) = match (lhs, rhs) {
+                (mbe::TokenTree::Delimited(_, lhs), mbe::TokenTree::Delimited(_, rhs)) => {
+                    (lhs, rhs)
+                }
+                _ => return None,
+            };
+            match parse_tt(&mut Cow::Borrowed(&parser), &lhs.tts) {
+                Success(named_matches) => {
+                    // The tokens are marked with a fresh expansion, as they would be when
+                    // expanding a crate, called from outside of any other expansion.
+                    let expn_data = ExpnData::default(
+                        ExpnKind::Macro(MacroKind::Bang, self.name.name),
+                        DUMMY_SP,
+                        self.span.edition(),
+                        None,
+                    );
+                    let expn_id = ExpnId::fresh(Some(expn_data));
+                    let tts = rhs.tts.clone();
+                    return transcribe(sess, expn_id, &named_matches, tts, self.transparency)
+                        .map_err(|mut err| err.emit())
+                        .ok();
+                }
+                Failure(..) => {}
+                Error(sp, msg) => {
+                    sess.span_diagnostic.span_err(sp, &msg);
+                    return None;
+                }
+                ErrorReported => return None,
+            }
+        }
+        None
+    }
 }
 
 fn macro_rules_dummy_expander<'cx>(
@@ -259,7 +298,13 @@ fn generic_extension<'cx>(
 
                 let rhs_spans = rhs.iter().map(|t| t.span()).collect::<Vec<_>>();
                 // rhs has holes ( `$id` and `$(...)` that need filled)
-                let mut tts = match transcribe(cx, &named_matches, rhs, transparency) {
+                let mut tts = match transcribe(
+                    sess,
+                    cx.current_expansion.id,
+                    &named_matches,
+                    rhs,
+                    transparency,
+                ) {
                     Ok(tts) => tts,
                     Err(mut err) => {
                         err.emit();
@@ -279,6 +324,10 @@ fn generic_extension<'cx>(
                     });
                 }
 
+                if let Some(explorer) = &mut cx.expansion_explorer {
+                    explorer.record_arm(cx.current_expansion.id, i);
+                }
+
                 if cx.trace_macros() {
                     let msg = format!("to `{}`", pprust::tts_to_string(&tts));
                     trace_macros_note(&mut cx.expansions, sp, msg);
@@ -352,11 +401,55 @@ fn generic_extension<'cx>(
             }
         }
     }
+
+    // Explain how far each arm got, like `-Z macro-backtrace` does for the expansion itself.
+    if cx.sess.opts.debugging_opts.macro_backtrace {
+        note_arm_failures(cx, sp, &arg, lhses, &mut err);
+    }
     err.emit();
     cx.trace_macros_diag();
     DummyResult::any(sp)
 }
 
+/// Adds a note for every arm of a macro that failed to match, pointing at the token where its
+/// matcher gave up and listing what it expected there instead.
+fn note_arm_failures(
+    cx: &ExtCtxt<'_>,
+    sp: Span,
+    arg: &TokenStream,
+    lhses: &[mbe::TokenTree],
+    err: &mut DiagnosticBuilder<'_>,
+) {
+    let sess = &cx.sess.parse_sess;
+    for (i, lhs) in lhses.iter().enumerate() {
+        let lhs_tt = match *lhs {
+            mbe::TokenTree::Delimited(_, ref delim) => &delim.tts[..],
+            _ => continue,
+        };
+        let parser = parser_from_cx(sess, arg.clone());
+        let (token, expected) = match parse_tt_explained(&mut Cow::Borrowed(&parser), lhs_tt) {
+            (Failure(token, _), expected) => (token, expected),
+            _ => continue,
+        };
+        let reached = match token.kind {
+            token::Eof => "the end of the macro invocation".to_string(),
+            _ => format!("`{}`", pprust::token_to_string(&token)),
+        };
+        let mut msg = format!("rule #{} did not match at {}", i + 1, reached);
+        match &expected[..] {
+            [] => {}
+            [expected] => msg.push_str(&format!(", expected {}", expected)),
+            expected => msg.push_str(&format!(", expected one of {}", expected.join(", "))),
+        }
+        let mut span = MultiSpan::from_span(lhs.span());
+        span.push_span_label(
+            token.span.substitute_dummy(sp),
+            format!("rule #{} matched up to here", i + 1),
+        );
+        err.span_note(span, &msg);
+    }
+}
+
 // Note that macro-by-example's input is also matched against a token tree:
 //                   $( $lhs:tt => $rhs:tt );+
 //
@@ -559,7 +652,10 @@ fn check_lhs_no_empty_seq(sess: &ParseSess, tts: &[mbe::TokenTree]) -> bool {
     use mbe::TokenTree;
     for tt in tts {
         match *tt {
-            TokenTree::Token(..) | TokenTree::MetaVar(..) | TokenTree::MetaVarDecl(..) => (),
+            TokenTree::Token(..)
+            | TokenTree::MetaVar(..)
+            | TokenTree::MetaVarDecl(..)
+            | TokenTree::MetaVarExpr(..) => (),
             TokenTree::Delimited(_, ref del) => {
                 if !check_lhs_no_empty_seq(sess, &del.tts) {
                     return false;
@@ -648,7 +744,10 @@ fn build_recur(sets: &mut FirstSets, tts: &[TokenTree]) -> TokenSet {
             let mut first = TokenSet::empty();
             for tt in tts.iter().rev() {
                 match *tt {
-                    TokenTree::Token(..) | TokenTree::MetaVar(..) | TokenTree::MetaVarDecl(..) => {
+                    TokenTree::Token(..)
+                    | TokenTree::MetaVar(..)
+                    | TokenTree::MetaVarDecl(..)
+                    | TokenTree::MetaVarExpr(..) => {
                         first.replace_with(tt.clone());
                     }
                     TokenTree::Delimited(span, ref delimited) => {
@@ -710,7 +809,10 @@ fn first(&self, tts: &[mbe::TokenTree]) -> TokenSet {
         for tt in tts.iter() {
             assert!(first.maybe_empty);
             match *tt {
-                TokenTree::Token(..) | TokenTree::MetaVar(..) | TokenTree::MetaVarDecl(..) => {
+                TokenTree::Token(..)
+                | TokenTree::MetaVar(..)
+                | TokenTree::MetaVarDecl(..)
+                | TokenTree::MetaVarExpr(..) => {
                     first.add_one(tt.clone());
                     return first;
                 }
@@ -886,7 +988,10 @@ fn check_matcher_core(
         // First, update `last` so that it corresponds to the set
         // of NT tokens that might end the sequence `... token`.
         match *token {
-            TokenTree::Token(..) | TokenTree::MetaVar(..) | TokenTree::MetaVarDecl(..) => {
+            TokenTree::Token(..)
+            | TokenTree::MetaVar(..)
+            | TokenTree::MetaVarDecl(..)
+            | TokenTree::MetaVarExpr(..) => {
                 if token_can_be_followed_by_any(token) {
                     // don't need to track tokens that work with any,
                     last.replace_with_irrelevant();
@@ -0,0 +1,149 @@
+use rustc_ast::token::{self, Token};
+use rustc_ast::tokenstream::{Cursor, TokenStream, TokenTree};
+use rustc_ast_pretty::pprust;
+use rustc_errors::PResult;
+use rustc_session::parse::ParseSess;
+use rustc_span::symbol::{sym, Ident};
+use rustc_span::Span;
+
+/// A meta-variable expression, e.g. `${count(x)}`, which is evaluated while transcribing the
+/// right-hand side of a macro rule.
+#[derive(Debug, Clone, PartialEq, Encodable, Decodable)]
+crate enum MetaVarExpr {
+    /// The number of times the fragments bound to a meta-variable repeat. With a depth, only the
+    /// repetitions that many levels below the current one are counted, otherwise the fragments
+    /// themselves are.
+    Count(Ident, Option<usize>),
+
+    /// Expands to nothing, but still lets the meta-variable drive the repetition it appears in.
+    Ignore(Ident),
+
+    /// The index of the current iteration of the repetition at the given depth, with `0` being
+    /// the innermost repetition.
+    Index(usize),
+
+    /// The number of iterations of the repetition at the given depth, with `0` being the
+    /// innermost repetition.
+    Length(usize),
+}
+
+impl MetaVarExpr {
+    /// Parses the contents of `${...}`, e.g. `count(x, 1)`.
+    crate fn parse<'sess>(
+        input: &TokenStream,
+        outer_span: Span,
+        sess: &'sess ParseSess,
+    ) -> PResult<'sess, MetaVarExpr> {
+        let mut tts = input.trees();
+        let ident = parse_ident(&mut tts, sess, outer_span)?;
+        let args = match tts.next() {
+            Some(TokenTree::Delimited(_, token::Paren, args)) => args,
+            _ => {
+                let msg = "meta-variable expression parameters must be wrapped in parentheses";
+                return Err(sess.span_diagnostic.struct_span_err(ident.span, msg));
+            }
+        };
+        check_trailing_token(&mut tts, sess)?;
+
+        let mut args = args.trees();
+        let expr = match ident.name {
+            sym::count => {
+                let ident = parse_ident(&mut args, sess, ident.span)?;
+                let depth = if try_eat_comma(&mut args) {
+                    Some(parse_depth(&mut args, sess, ident.span)?)
+                } else {
+                    None
+                };
+                MetaVarExpr::Count(ident, depth)
+            }
+            sym::ignore => MetaVarExpr::Ignore(parse_ident(&mut args, sess, ident.span)?),
+            sym::index => MetaVarExpr::Index(parse_optional_depth(&mut args, sess, ident.span)?),
+            sym::length => MetaVarExpr::Length(parse_optional_depth(&mut args, sess, ident.span)?),
+            _ => {
+                let msg = format!("unrecognized meta-variable expression `{}`", ident);
+                return Err(sess
+                    .span_diagnostic
+                    .struct_span_err(ident.span, &msg)
+                    .help("supported expressions are `count`, `ignore`, `index` and `length`"));
+            }
+        };
+        check_trailing_token(&mut args, sess)?;
+        Ok(expr)
+    }
+
+    /// The meta-variable the expression refers to, if any.
+    crate fn ident(&self) -> Option<Ident> {
+        match *self {
+            MetaVarExpr::Count(ident, _) | MetaVarExpr::Ignore(ident) => Some(ident),
+            MetaVarExpr::Index(..) | MetaVarExpr::Length(..) => None,
+        }
+    }
+}
+
+fn check_trailing_token<'sess>(iter: &mut Cursor, sess: &'sess ParseSess) -> PResult<'sess, ()> {
+    if let Some(tt) = iter.next() {
+        let msg = format!("unexpected token: {}", pprust::tt_to_string(&tt));
+        Err(sess.span_diagnostic.struct_span_err(tt.span(), &msg))
+    } else {
+        Ok(())
+    }
+}
+
+/// Parses the name of a meta-variable, without the leading `$`.
+fn parse_ident<'sess>(
+    iter: &mut Cursor,
+    sess: &'sess ParseSess,
+    fallback_span: Span,
+) -> PResult<'sess, Ident> {
+    match iter.next() {
+        Some(TokenTree::Token(token)) => match token.ident() {
+            Some((ident, false)) => Ok(ident),
+            _ => {
+                let token_str = pprust::token_to_string(&token);
+                let msg = format!("expected identifier, found `{}`", token_str);
+                Err(sess.span_diagnostic.struct_span_err(token.span, &msg))
+            }
+        },
+        Some(tt) => Err(sess.span_diagnostic.struct_span_err(tt.span(), "expected identifier")),
+        None => Err(sess.span_diagnostic.struct_span_err(fallback_span, "expected identifier")),
+    }
+}
+
+/// Parses a depth, which is an unsuffixed integer literal.
+fn parse_depth<'sess>(
+    iter: &mut Cursor,
+    sess: &'sess ParseSess,
+    fallback_span: Span,
+) -> PResult<'sess, usize> {
+    let span = match iter.next() {
+        Some(TokenTree::Token(Token {
+            kind: token::Literal(token::Lit { kind: token::Integer, symbol, suffix: None }),
+            span,
+        })) => match symbol.as_str().parse() {
+            Ok(depth) => return Ok(depth),
+            Err(_) => span,
+        },
+        Some(tt) => tt.span(),
+        None => fallback_span,
+    };
+    let msg = "meta-variable expression depth must be a literal integer without suffix";
+    Err(sess.span_diagnostic.struct_span_err(span, msg))
+}
+
+/// Parses the depth of `index` and `length`, which defaults to the innermost repetition.
+fn parse_optional_depth<'sess>(
+    iter: &mut Cursor,
+    sess: &'sess ParseSess,
+    fallback_span: Span,
+) -> PResult<'sess, usize> {
+    if iter.look_ahead(0).is_none() { Ok(0) } else { parse_depth(iter, sess, fallback_span) }
+}
+
+fn try_eat_comma(iter: &mut Cursor) -> bool {
+    if let Some(TokenTree::Token(Token { kind: token::Comma, .. })) = iter.look_ahead(0) {
+        iter.next();
+        true
+    } else {
+        false
+    }
+}
@@ -1,4 +1,5 @@
 use crate::mbe::macro_parser;
+use crate::mbe::metavar_expr::MetaVarExpr;
 use crate::mbe::{Delimited, KleeneOp, KleeneToken, SequenceRepetition, TokenTree};
 
 use rustc_ast::token::{self, Token};
@@ -156,6 +157,29 @@ fn parse_tree(
             }
 
             match next {
+                // `tree` is followed by a brace-delimited set of token trees on the right hand side
+                // of a macro. This is a meta-variable expression (e.g. `${count(x)}`).
+                Some(tokenstream::TokenTree::Delimited(span, token::Brace, tts))
+                    if !expect_matchers =>
+                {
+                    if !features.macro_metavar_expr {
+                        feature_err(
+                            sess,
+                            sym::macro_metavar_expr,
+                            span.entire(),
+                            "meta-variable expressions are unstable",
+                        )
+                        .emit();
+                    }
+                    match MetaVarExpr::parse(&tts, span.entire(), sess) {
+                        Ok(expr) => TokenTree::MetaVarExpr(span, expr),
+                        Err(mut err) => {
+                            err.emit();
+                            TokenTree::token(token::Dollar, span.entire())
+                        }
+                    }
+                }
+
                 // `tree` is followed by a delimited set of token trees. This indicates the beginning
                 // of a repetition sequence in the macro (e.g. `$(pat)*`).
                 Some(tokenstream::TokenTree::Delimited(span, delim, tts)) => {
@@ -1,6 +1,6 @@
-use crate::base::ExtCtxt;
 use crate::mbe;
 use crate::mbe::macro_parser::{MatchedNonterminal, MatchedSeq, NamedMatch};
+use crate::mbe::metavar_expr::MetaVarExpr;
 
 use rustc_ast::mut_visit::{self, MutVisitor};
 use rustc_ast::token::{self, NtTT, Token};
@@ -8,8 +8,9 @@
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::sync::Lrc;
 use rustc_errors::{pluralize, PResult};
+use rustc_session::parse::ParseSess;
 use rustc_span::hygiene::{ExpnId, Transparency};
-use rustc_span::symbol::MacroRulesNormalizedIdent;
+use rustc_span::symbol::{Ident, MacroRulesNormalizedIdent, Symbol};
 use rustc_span::Span;
 
 use smallvec::{smallvec, SmallVec};
@@ -63,6 +64,7 @@ fn next(&mut self) -> Option<mbe::TokenTree> {
 /// - `interp` is a map of meta-variables to the tokens (non-terminals) they matched in the
 ///   invocation. We are assuming we already know there is a match.
 /// - `src` is the RHS of the MBE, that is, the "example" we are filling in.
+/// - `expn_id` is the expansion whose mark is applied to the spans of the produced tokens.
 ///
 /// For example,
 ///
@@ -80,7 +82,8 @@ fn next(&mut self) -> Option<mbe::TokenTree> {
 ///
 /// Along the way, we do some additional error checking.
 pub(super) fn transcribe<'a>(
-    cx: &ExtCtxt<'a>,
+    sess: &'a ParseSess,
+    expn_id: ExpnId,
     interp: &FxHashMap<MacroRulesNormalizedIdent, NamedMatch>,
     src: Vec<mbe::TokenTree>,
     transparency: Transparency,
@@ -112,7 +115,7 @@ pub(super) fn transcribe<'a>(
     // again, and we are done transcribing.
     let mut result: Vec<TreeAndSpacing> = Vec::new();
     let mut result_stack = Vec::new();
-    let mut marker = Marker(cx.current_expansion.id, transparency);
+    let mut marker = Marker(expn_id, transparency);
 
     loop {
         // Look at the last frame on the stack.
@@ -172,7 +175,7 @@ pub(super) fn transcribe<'a>(
             seq @ mbe::TokenTree::Sequence(..) => {
                 match lockstep_iter_size(&seq, interp, &repeats) {
                     LockstepIterSize::Unconstrained => {
-                        return Err(cx.struct_span_err(
+                        return Err(sess.span_diagnostic.struct_span_err(
                             seq.span(), /* blame macro writer */
                             "attempted to repeat an expression containing no syntax variables \
                              matched as repeating at this depth",
@@ -184,7 +187,7 @@ pub(super) fn transcribe<'a>(
                         // happens when two meta-variables are used in the same repetition in a
                         // sequence, but they come from different sequence matchers and repeat
                         // different amounts.
-                        return Err(cx.struct_span_err(seq.span(), &msg[..]));
+                        return Err(sess.span_diagnostic.struct_span_err(seq.span(), &msg[..]));
                     }
 
                     LockstepIterSize::Constraint(len, _) => {
@@ -202,7 +205,7 @@ pub(super) fn transcribe<'a>(
                                 // FIXME: this really ought to be caught at macro definition
                                 // time... It happens when the Kleene operator in the matcher and
                                 // the body for the same meta-variable do not match.
-                                return Err(cx.struct_span_err(
+                                return Err(sess.span_diagnostic.struct_span_err(
                                     sp.entire(),
                                     "this must repeat at least once",
                                 ));
@@ -246,7 +249,7 @@ pub(super) fn transcribe<'a>(
                         result.push(token.into());
                     } else {
                         // We were unable to descend far enough. This is an error.
-                        return Err(cx.struct_span_err(
+                        return Err(sess.span_diagnostic.struct_span_err(
                             sp, /* blame the macro writer */
                             &format!("variable '{}' is still repeating at this depth", ident),
                         ));
@@ -261,6 +264,17 @@ pub(super) fn transcribe<'a>(
                 }
             }
 
+            // Replace the meta-variable expression with its value, if any.
+            mbe::TokenTree::MetaVarExpr(sp, expr) => {
+                let mut sp = sp.entire();
+                if let Some(value) = eval_metavar_expr(sess, interp, &repeats, &expr, sp)? {
+                    marker.visit_span(&mut sp);
+                    let symbol = Symbol::intern(&value.to_string());
+                    let lit = token::Lit::new(token::Integer, symbol, None);
+                    result.push(TokenTree::token(token::Literal(lit), sp).into());
+                }
+            }
+
             // If we are entering a new delimiter, we push its contents to the `stack` to be
             // processed, and we push all of the currently produced results to the `result_stack`.
             // We will produce all of the results of the inside of the `Delimited` and then we will
@@ -286,6 +300,70 @@ pub(super) fn transcribe<'a>(
     }
 }
 
+/// Evaluates a meta-variable expression, returning the integer it expands to or `None` for
+/// expressions that expand to nothing.
+fn eval_metavar_expr<'a>(
+    sess: &'a ParseSess,
+    interp: &FxHashMap<MacroRulesNormalizedIdent, NamedMatch>,
+    repeats: &[(usize, usize)],
+    expr: &MetaVarExpr,
+    sp: Span,
+) -> PResult<'a, Option<usize>> {
+    match *expr {
+        MetaVarExpr::Count(ident, depth) => {
+            let name = MacroRulesNormalizedIdent::new(ident);
+            match lookup_cur_matched(name, interp, repeats) {
+                Some(MatchedSeq(named_matches)) => {
+                    count_repetitions(sess, depth, named_matches, sp).map(Some)
+                }
+                Some(MatchedNonterminal(_)) => Err(sess.span_diagnostic.struct_span_err(
+                    sp,
+                    &format!("`{}` does not repeat more deeply than where `count` is used", name),
+                )),
+                None => Err(sess
+                    .span_diagnostic
+                    .struct_span_err(sp, &format!("unknown macro variable `{}`", name))),
+            }
+        }
+        MetaVarExpr::Ignore(..) => Ok(None),
+        MetaVarExpr::Index(depth) | MetaVarExpr::Length(depth) => {
+            match repeats.iter().nth_back(depth) {
+                Some(&(index, length)) => {
+                    Ok(Some(if let MetaVarExpr::Index(..) = expr { index } else { length }))
+                }
+                None => Err(sess
+                    .span_diagnostic
+                    .struct_span_err(sp, &format!("repetition depth `{}` is out of range", depth))),
+            }
+        }
+    }
+}
+
+/// Counts the fragments of a repeating meta-variable, or with a `depth`, its repetitions that many
+/// levels down.
+fn count_repetitions<'a>(
+    sess: &'a ParseSess,
+    depth: Option<usize>,
+    named_matches: &[NamedMatch],
+    sp: Span,
+) -> PResult<'a, usize> {
+    match depth {
+        Some(0) => Ok(named_matches.len()),
+        _ => named_matches
+            .iter()
+            .map(|matched| match matched {
+                MatchedSeq(named_matches) => {
+                    count_repetitions(sess, depth.map(|depth| depth - 1), named_matches, sp)
+                }
+                MatchedNonterminal(_) if depth.is_none() => Ok(1),
+                MatchedNonterminal(_) => {
+                    Err(sess.span_diagnostic.struct_span_err(sp, "`count` depth is out of range"))
+                }
+            })
+            .sum(),
+    }
+}
+
 /// Lookup the meta-var named `ident` and return the matched token tree from the invocation using
 /// the set of matches `interpolations`.
 ///
@@ -382,15 +460,28 @@ fn lockstep_iter_size(
             })
         }
         TokenTree::MetaVar(_, name) | TokenTree::MetaVarDecl(_, name, _) => {
-            let name = MacroRulesNormalizedIdent::new(name);
-            match lookup_cur_matched(name, interpolations, repeats) {
-                Some(matched) => match matched {
-                    MatchedNonterminal(_) => LockstepIterSize::Unconstrained,
-                    MatchedSeq(ref ads) => LockstepIterSize::Constraint(ads.len(), name),
-                },
-                _ => LockstepIterSize::Unconstrained,
-            }
+            meta_var_iter_size(name, interpolations, repeats)
+        }
+        TokenTree::MetaVarExpr(_, ref expr) => {
+            expr.ident().map_or(LockstepIterSize::Unconstrained, |name| {
+                meta_var_iter_size(name, interpolations, repeats)
+            })
         }
         TokenTree::Token(..) => LockstepIterSize::Unconstrained,
     }
 }
+
+fn meta_var_iter_size(
+    name: Ident,
+    interpolations: &FxHashMap<MacroRulesNormalizedIdent, NamedMatch>,
+    repeats: &[(usize, usize)],
+) -> LockstepIterSize {
+    let name = MacroRulesNormalizedIdent::new(name);
+    match lookup_cur_matched(name, interpolations, repeats) {
+        Some(matched) => match matched {
+            MatchedNonterminal(_) => LockstepIterSize::Unconstrained,
+            MatchedSeq(ref ads) => LockstepIterSize::Constraint(ads.len(), name),
+        },
+        _ => LockstepIterSize::Unconstrained,
+    }
+}
@@ -1,20 +1,24 @@
 use crate::base::ExtCtxt;
 
 use rustc_ast as ast;
+use rustc_ast::attr;
 use rustc_ast::token;
 use rustc_ast::tokenstream::{self, CanSynthesizeMissingTokens};
 use rustc_ast::tokenstream::{DelimSpan, Spacing::*, TokenStream, TreeAndSpacing};
+use rustc_ast::util::literal::LitError;
 use rustc_ast_pretty::pprust;
 use rustc_data_structures::sync::Lrc;
-use rustc_errors::Diagnostic;
+use rustc_errors::{Diagnostic, PResult};
+use rustc_lexer::is_ident;
+use rustc_lint_defs::{BuiltinLintDiagnostics, ProcMacroLintParts};
 use rustc_parse::lexer::nfc_normalize;
 use rustc_parse::{nt_to_tokenstream, parse_stream_from_source_str};
 use rustc_session::parse::ParseSess;
 use rustc_span::symbol::{self, kw, sym, Symbol};
-use rustc_span::{BytePos, FileName, MultiSpan, Pos, SourceFile, Span};
+use rustc_span::{BytePos, FileName, MultiSpan, Pos, SourceFile, Span, DUMMY_SP};
 
 use pm::bridge::{server, TokenTree};
-use pm::{Delimiter, Level, LineColumn, Spacing};
+use pm::{Applicability, ConversionErrorKind, Delimiter, Level, LineColumn, Spacing};
 use std::ops::Bound;
 use std::{ascii, panic};
 
@@ -266,6 +270,18 @@ fn to_internal(self) -> TokenStream {
     }
 }
 
+impl ToInternal<rustc_errors::Applicability> for Applicability {
+    fn to_internal(self) -> rustc_errors::Applicability {
+        match self {
+            Applicability::MachineApplicable => rustc_errors::Applicability::MachineApplicable,
+            Applicability::MaybeIncorrect => rustc_errors::Applicability::MaybeIncorrect,
+            Applicability::HasPlaceholders => rustc_errors::Applicability::HasPlaceholders,
+            Applicability::Unspecified => rustc_errors::Applicability::Unspecified,
+            _ => unreachable!("unknown proc_macro::Applicability variant: {:?}", self),
+        }
+    }
+}
+
 impl ToInternal<rustc_errors::Level> for Level {
     fn to_internal(self) -> rustc_errors::Level {
         match self {
@@ -351,32 +367,36 @@ pub struct Literal {
     span: Span,
 }
 
-pub(crate) struct Rustc<'a> {
-    sess: &'a ParseSess,
+pub(crate) struct Rustc<'a, 'b> {
+    ecx: &'a mut ExtCtxt<'b>,
     def_site: Span,
     call_site: Span,
     mixed_site: Span,
     span_debug: bool,
 }
 
-impl<'a> Rustc<'a> {
-    pub fn new(cx: &'a ExtCtxt<'_>) -> Self {
-        let expn_data = cx.current_expansion.id.expn_data();
+impl<'a, 'b> Rustc<'a, 'b> {
+    pub fn new(ecx: &'a mut ExtCtxt<'b>) -> Self {
+        let expn_data = ecx.current_expansion.id.expn_data();
         Rustc {
-            sess: &cx.sess.parse_sess,
-            def_site: cx.with_def_site_ctxt(expn_data.def_site),
-            call_site: cx.with_call_site_ctxt(expn_data.call_site),
-            mixed_site: cx.with_mixed_site_ctxt(expn_data.call_site),
-            span_debug: cx.ecfg.span_debug,
+            def_site: ecx.with_def_site_ctxt(expn_data.def_site),
+            call_site: ecx.with_call_site_ctxt(expn_data.call_site),
+            mixed_site: ecx.with_mixed_site_ctxt(expn_data.call_site),
+            span_debug: ecx.ecfg.span_debug,
+            ecx,
         }
     }
 
+    fn sess(&self) -> &ParseSess {
+        self.ecx.parse_sess()
+    }
+
     fn lit(&mut self, kind: token::LitKind, symbol: Symbol, suffix: Option<Symbol>) -> Literal {
         Literal { lit: token::Lit::new(kind, symbol, suffix), span: server::Span::call_site(self) }
     }
 }
 
-impl server::Types for Rustc<'_> {
+impl server::Types for Rustc<'_, '_> {
     type FreeFunctions = FreeFunctions;
     type TokenStream = TokenStream;
     type TokenStreamBuilder = tokenstream::TokenStreamBuilder;
@@ -391,13 +411,19 @@ impl server::Types for Rustc<'_> {
     type Span = Span;
 }
 
-impl server::FreeFunctions for Rustc<'_> {
+impl server::FreeFunctions for Rustc<'_, '_> {
     fn track_env_var(&mut self, var: &str, value: Option<&str>) {
-        self.sess.env_depinfo.borrow_mut().insert((Symbol::intern(var), value.map(Symbol::intern)));
+        self.sess()
+            .env_depinfo
+            .borrow_mut()
+            .insert((Symbol::intern(var), value.map(Symbol::intern)));
+    }
+    fn track_path(&mut self, path: &str) {
+        self.sess().file_depinfo.borrow_mut().insert(Symbol::intern(path));
     }
 }
 
-impl server::TokenStream for Rustc<'_> {
+impl server::TokenStream for Rustc<'_, '_> {
     fn new(&mut self) -> Self::TokenStream {
         TokenStream::default()
     }
@@ -408,13 +434,54 @@ fn from_str(&mut self, src: &str) -> Self::TokenStream {
         parse_stream_from_source_str(
             FileName::proc_macro_source_code(src),
             src.to_string(),
-            self.sess,
+            self.sess(),
             Some(self.call_site),
         )
     }
     fn to_string(&mut self, stream: &Self::TokenStream) -> String {
         pprust::tts_to_string(stream)
     }
+    fn expand_expr(&mut self, stream: &Self::TokenStream) -> Result<Self::TokenStream, ()> {
+        // Parse the expression from our tokenstream.
+        let expr: PResult<'_, _> = try {
+            let mut p = rustc_parse::stream_to_parser(
+                self.sess(),
+                stream.clone(),
+                Some("proc_macro expand expr"),
+            );
+            let expr = p.parse_expr()?;
+            if p.token != token::Eof {
+                p.unexpected()?;
+            }
+            expr
+        };
+        let expr = expr.map_err(|mut err| err.emit())?;
+
+        // Perform eager expansion on the expression.
+        let expr = self
+            .ecx
+            .expander()
+            .fully_expand_fragment(crate::expand::AstFragment::Expr(expr))
+            .make_expr();
+
+        // NOTE: For now, limit `expand_expr` to exclusively expand to literals, as the tokens of
+        // an arbitrary expanded expression cannot be recovered in the general case.
+        match &expr.kind {
+            ast::ExprKind::Lit(l) => Ok(tokenstream::TokenTree::Token(l.to_token()).into()),
+            ast::ExprKind::Unary(ast::UnOp::Neg, e) => match &e.kind {
+                ast::ExprKind::Lit(l) if matches!(l.token.kind, token::Integer | token::Float) => {
+                    Ok(vec![
+                        tokenstream::TokenTree::token(token::BinOp(token::Minus), expr.span),
+                        tokenstream::TokenTree::Token(l.to_token()),
+                    ]
+                    .into_iter()
+                    .collect())
+                }
+                _ => Err(()),
+            },
+            _ => Err(()),
+        }
+    }
     fn from_token_tree(
         &mut self,
         tree: TokenTree<Self::Group, Self::Punct, Self::Ident, Self::Literal>,
@@ -426,7 +493,7 @@ fn into_iter(&mut self, stream: Self::TokenStream) -> Self::TokenStreamIter {
     }
 }
 
-impl server::TokenStreamBuilder for Rustc<'_> {
+impl server::TokenStreamBuilder for Rustc<'_, '_> {
     fn new(&mut self) -> Self::TokenStreamBuilder {
         tokenstream::TokenStreamBuilder::new()
     }
@@ -438,7 +505,7 @@ fn build(&mut self, builder: Self::TokenStreamBuilder) -> Self::TokenStream {
     }
 }
 
-impl server::TokenStreamIter for Rustc<'_> {
+impl server::TokenStreamIter for Rustc<'_, '_> {
     fn next(
         &mut self,
         iter: &mut Self::TokenStreamIter,
@@ -446,7 +513,7 @@ fn next(
         loop {
             let tree = iter.stack.pop().or_else(|| {
                 let next = iter.cursor.next_with_spacing()?;
-                Some(TokenTree::from_internal((next, self.sess, &mut iter.stack)))
+                Some(TokenTree::from_internal((next, self.sess(), &mut iter.stack)))
             })?;
             // A hack used to pass AST fragments to attribute and derive macros
             // as a single nonterminal token instead of a token stream.
@@ -463,7 +530,7 @@ fn next(
     }
 }
 
-impl server::Group for Rustc<'_> {
+impl server::Group for Rustc<'_, '_> {
     fn new(&mut self, delimiter: Delimiter, stream: Self::TokenStream) -> Self::Group {
         Group {
             delimiter,
@@ -492,7 +559,7 @@ fn set_span(&mut self, group: &mut Self::Group, span: Self::Span) {
     }
 }
 
-impl server::Punct for Rustc<'_> {
+impl server::Punct for Rustc<'_, '_> {
     fn new(&mut self, ch: char, spacing: Spacing) -> Self::Punct {
         Punct::new(ch, spacing == Spacing::Joint, server::Span::call_site(self))
     }
@@ -510,9 +577,9 @@ fn with_span(&mut self, punct: Self::Punct, span: Self::Span) -> Self::Punct {
     }
 }
 
-impl server::Ident for Rustc<'_> {
+impl server::Ident for Rustc<'_, '_> {
     fn new(&mut self, string: &str, span: Self::Span, is_raw: bool) -> Self::Ident {
-        Ident::new(self.sess, Symbol::intern(string), is_raw, span)
+        Ident::new(self.sess(), Symbol::intern(string), is_raw, span)
     }
     fn span(&mut self, ident: Self::Ident) -> Self::Span {
         ident.span
@@ -522,7 +589,7 @@ fn with_span(&mut self, ident: Self::Ident, span: Self::Span) -> Self::Ident {
     }
 }
 
-impl server::Literal for Rustc<'_> {
+impl server::Literal for Rustc<'_, '_> {
     fn debug_kind(&mut self, literal: &Self::Literal) -> String {
         format!("{:?}", literal.lit.kind)
     }
@@ -532,6 +599,45 @@ fn symbol(&mut self, literal: &Self::Literal) -> String {
     fn suffix(&mut self, literal: &Self::Literal) -> Option<String> {
         literal.lit.suffix.as_ref().map(Symbol::to_string)
     }
+    fn str_value(&mut self, literal: &Self::Literal) -> Result<String, ConversionErrorKind> {
+        match lit_value(literal.lit)? {
+            ast::LitKind::Str(symbol, _) => Ok(symbol.to_string()),
+            _ => Err(ConversionErrorKind::InvalidLiteralKind),
+        }
+    }
+    fn byte_str_value(&mut self, literal: &Self::Literal) -> Result<Vec<u8>, ConversionErrorKind> {
+        match lit_value(literal.lit)? {
+            ast::LitKind::ByteStr(bytes) => Ok(bytes.to_vec()),
+            _ => Err(ConversionErrorKind::InvalidLiteralKind),
+        }
+    }
+    fn char_value(&mut self, literal: &Self::Literal) -> Result<char, ConversionErrorKind> {
+        match lit_value(literal.lit)? {
+            ast::LitKind::Char(ch) => Ok(ch),
+            _ => Err(ConversionErrorKind::InvalidLiteralKind),
+        }
+    }
+    fn int_value(
+        &mut self,
+        literal: &Self::Literal,
+    ) -> Result<(u128, Option<String>), ConversionErrorKind> {
+        match lit_value(literal.lit)? {
+            ast::LitKind::Int(n, _) => Ok((n, literal.lit.suffix.as_ref().map(Symbol::to_string))),
+            _ => Err(ConversionErrorKind::InvalidLiteralKind),
+        }
+    }
+    fn float_value(
+        &mut self,
+        literal: &Self::Literal,
+    ) -> Result<(f64, Option<String>), ConversionErrorKind> {
+        match lit_value(literal.lit)? {
+            ast::LitKind::Float(symbol, _) => {
+                let n = symbol.as_str().parse().map_err(|_| ConversionErrorKind::Malformed)?;
+                Ok((n, literal.lit.suffix.as_ref().map(Symbol::to_string)))
+            }
+            _ => Err(ConversionErrorKind::InvalidLiteralKind),
+        }
+    }
     fn integer(&mut self, n: &str) -> Self::Literal {
         self.lit(token::Integer, Symbol::intern(n), None)
     }
@@ -612,7 +718,31 @@ fn subspan(
     }
 }
 
-impl server::SourceFile for Rustc<'_> {
+/// Decodes a literal token the same way the parser does, unescaping strings and characters and
+/// parsing numbers, for the value accessors of `proc_macro::Literal`.
+fn lit_value(lit: token::Lit) -> Result<ast::LitKind, ConversionErrorKind> {
+    // Negative numbers are never lexed as a single token, they can only come from the
+    // `proc_macro::Literal` constructors.
+    if lit.symbol.as_str().starts_with('-') {
+        return Err(ConversionErrorKind::InvalidLiteralKind);
+    }
+    match ast::Lit::from_lit_token(lit, DUMMY_SP) {
+        Ok(ast::Lit { kind: ast::LitKind::Err(_), .. }) => {
+            Err(ConversionErrorKind::InvalidLiteralKind)
+        }
+        Ok(lit) => Ok(lit.kind),
+        Err(LitError::NotLiteral) => Err(ConversionErrorKind::InvalidLiteralKind),
+        Err(LitError::LexerError | LitError::NonDecimalFloat(_)) => {
+            Err(ConversionErrorKind::Malformed)
+        }
+        Err(
+            LitError::InvalidSuffix | LitError::InvalidIntSuffix | LitError::InvalidFloatSuffix,
+        ) => Err(ConversionErrorKind::InvalidSuffix),
+        Err(LitError::IntTooLarge) => Err(ConversionErrorKind::OutOfRange),
+    }
+}
+
+impl server::SourceFile for Rustc<'_, '_> {
     fn eq(&mut self, file1: &Self::SourceFile, file2: &Self::SourceFile) -> bool {
         Lrc::ptr_eq(file1, file2)
     }
@@ -631,7 +761,7 @@ fn is_real(&mut self, file: &Self::SourceFile) -> bool {
     }
 }
 
-impl server::MultiSpan for Rustc<'_> {
+impl server::MultiSpan for Rustc<'_, '_> {
     fn new(&mut self) -> Self::MultiSpan {
         vec![]
     }
@@ -640,7 +770,7 @@ fn push(&mut self, spans: &mut Self::MultiSpan, span: Self::Span) {
     }
 }
 
-impl server::Diagnostic for Rustc<'_> {
+impl server::Diagnostic for Rustc<'_, '_> {
     fn new(&mut self, level: Level, msg: &str, spans: Self::MultiSpan) -> Self::Diagnostic {
         let mut diag = Diagnostic::new(level.to_internal(), msg);
         diag.set_span(MultiSpan::from_spans(spans));
@@ -655,12 +785,85 @@ fn sub(
     ) {
         diag.sub(level.to_internal(), msg, MultiSpan::from_spans(spans), None);
     }
+    fn span_label(&mut self, diag: &mut Self::Diagnostic, span: Self::Span, label: &str) {
+        diag.span_label(span, label);
+    }
+    fn suggestion(
+        &mut self,
+        diag: &mut Self::Diagnostic,
+        msg: &str,
+        parts: Vec<(Self::Span, String)>,
+        applicability: Applicability,
+    ) {
+        diag.multipart_suggestion(msg, parts, applicability.to_internal());
+    }
     fn emit(&mut self, diag: Self::Diagnostic) {
-        self.sess.span_diagnostic.emit_diagnostic(&diag);
+        self.sess().span_diagnostic.emit_diagnostic(&diag);
+    }
+    fn emit_lint(&mut self, diag: Self::Diagnostic, lint: &str) {
+        // Only tool-scoped names are accepted, so that macros can't emit (or collide with) the
+        // lints of the compiler.
+        let mut segments = lint.split("::");
+        let tool = match (segments.next(), segments.next(), segments.next()) {
+            (Some(tool), Some(name), None) if is_ident(tool) && is_ident(name) => tool,
+            _ => {
+                let msg = format!("invalid lint name `{}` emitted by a procedural macro", lint);
+                self.sess()
+                    .span_diagnostic
+                    .struct_span_err(diag.span.clone(), &msg)
+                    .note(
+                        "procedural macros can only emit lints scoped by a tool, like `tool::lint`",
+                    )
+                    .emit();
+                return;
+            }
+        };
+        let tool = symbol::Ident::from_str(tool);
+        if !attr::is_known_lint_tool(tool) && !self.ecx.resolver.is_registered_tool(tool.name) {
+            let msg = format!("unknown tool name `{}` in lint emitted by a procedural macro", tool);
+            self.sess()
+                .span_diagnostic
+                .struct_span_err(diag.span.clone(), &msg)
+                .help("add `#![register_tool(...)]` to the crate root to register the tool")
+                .emit();
+            return;
+        }
+
+        let children = diag
+            .children
+            .iter()
+            .map(|child| {
+                let is_help = child.level == rustc_errors::Level::Help;
+                (is_help, child.message(), child.span.clone())
+            })
+            .collect();
+        let suggestions = diag
+            .suggestions
+            .iter()
+            .filter_map(|suggestion| {
+                let substitution = suggestion.substitutions.first()?;
+                let parts = substitution
+                    .parts
+                    .iter()
+                    .map(|part| (part.span, part.snippet.clone()))
+                    .collect();
+                Some((suggestion.msg.clone(), parts, suggestion.applicability))
+            })
+            .collect();
+
+        let lint = self.sess().proc_macro_lint(Symbol::intern(lint));
+        let node_id = self.ecx.resolver.lint_node_id(self.ecx.current_expansion.id);
+        self.sess().buffer_lint_with_diagnostic(
+            lint,
+            diag.span.clone(),
+            node_id,
+            &diag.message(),
+            BuiltinLintDiagnostics::ProcMacroLint(ProcMacroLintParts { children, suggestions }),
+        );
     }
 }
 
-impl server::Span for Rustc<'_> {
+impl server::Span for Rustc<'_, '_> {
     fn debug(&mut self, span: Self::Span) -> String {
         if self.span_debug {
             format!("{:?}", span)
@@ -678,7 +881,7 @@ fn mixed_site(&mut self) -> Self::Span {
         self.mixed_site
     }
     fn source_file(&mut self, span: Self::Span) -> Self::SourceFile {
-        self.sess.source_map().lookup_char_pos(span.lo()).file
+        self.sess().source_map().lookup_char_pos(span.lo()).file
     }
     fn parent(&mut self, span: Self::Span) -> Option<Self::Span> {
         span.parent()
@@ -687,16 +890,16 @@ fn source(&mut self, span: Self::Span) -> Self::Span {
         span.source_callsite()
     }
     fn start(&mut self, span: Self::Span) -> LineColumn {
-        let loc = self.sess.source_map().lookup_char_pos(span.lo());
+        let loc = self.sess().source_map().lookup_char_pos(span.lo());
         LineColumn { line: loc.line, column: loc.col.to_usize() }
     }
     fn end(&mut self, span: Self::Span) -> LineColumn {
-        let loc = self.sess.source_map().lookup_char_pos(span.hi());
+        let loc = self.sess().source_map().lookup_char_pos(span.hi());
         LineColumn { line: loc.line, column: loc.col.to_usize() }
     }
     fn join(&mut self, first: Self::Span, second: Self::Span) -> Option<Self::Span> {
-        let self_loc = self.sess.source_map().lookup_char_pos(first.lo());
-        let other_loc = self.sess.source_map().lookup_char_pos(second.lo());
+        let self_loc = self.sess().source_map().lookup_char_pos(first.lo());
+        let other_loc = self.sess().source_map().lookup_char_pos(second.lo());
 
         if self_loc.file.name != other_loc.file.name {
             return None;
@@ -708,6 +911,6 @@ fn resolved_at(&mut self, span: Self::Span, at: Self::Span) -> Self::Span {
         span.with_ctxt(at.ctxt())
     }
     fn source_text(&mut self, span: Self::Span) -> Option<String> {
-        self.sess.source_map().span_to_snippet(span).ok()
+        self.sess().source_map().span_to_snippet(span).ok()
     }
 }
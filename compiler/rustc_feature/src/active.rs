@@ -644,6 +644,26 @@ pub fn set(&self, features: &mut Features, span: Span) {
     /// Allows `extern "C-unwind" fn` to enable unwinding across ABI boundaries.
     (active, c_unwind, "1.52.0", Some(74990), None),
 
+    /// Allows `#[derive(Default)]` and `#[default]` on enums.
+    (active, derive_default_enum, "1.52.0", None, None),
+
+    /// Allows `${count()}`, `${index()}`, `${length()}` and `${ignore()}` in `macro_rules!`.
+    (active, macro_metavar_expr, "1.52.0", None, None),
+
+    /// Allows `#[cmp(skip)]` and `#[cmp(order = N)]` on the fields of types deriving the
+    /// comparison traits, `Hash` or `Debug`.
+    (active, derive_cmp_attrs, "1.52.0", None, None),
+
+    /// Allows `#![doc(auto_cfg)]`, which makes rustdoc infer `#[doc(cfg(...))]` from the
+    /// `#[cfg(...)]` attributes of items.
+    (active, doc_auto_cfg, "1.52.0", None, None),
+
+    /// Allows `#![doc(cfg_hide(...))]`.
+    (active, doc_cfg_hide, "1.52.0", None, None),
+
+    /// Allows `#[doc(hidden_arms = "...")]` on `macro_rules!` definitions.
+    (active, doc_hidden_arms, "1.52.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
@@ -50,6 +50,15 @@
 //! any more and will delete those. It will also delete any finalized session
 //! directories for a given crate except for the most recent one.
 //!
+//! On top of that, the total size and age of the incremental compilation
+//! directory can be bounded with `-Z incremental-gc-max-size` and
+//! `-Z incremental-gc-max-age`. When a session is finalized, the finalized
+//! session directories of *all* crates in the incremental directory are
+//! considered, and whole sessions are deleted, least recently created first,
+//! until both limits are met. Deleting such a session follows the same locking
+//! protocol as regular garbage collection, so sessions that are still being
+//! read from are left alone.
+//!
 //! ## Synchronization
 //!
 //! There is some synchronization needed in order for the compiler to be able to
@@ -384,6 +393,10 @@ pub fn finalize_session_directory(sess: &Session, svh: Svh) {
     }
 
     let _ = garbage_collect_session_directories(sess);
+
+    if let Err(err) = enforce_cache_limits(sess) {
+        debug!("finalize_session_directory() - error enforcing cache limits: {}", err);
+    }
 }
 
 pub fn delete_all_session_dir_contents(sess: &Session) -> io::Result<()> {
@@ -885,6 +898,175 @@ pub fn garbage_collect_session_directories(sess: &Session) -> io::Result<()> {
     Ok(())
 }
 
+/// A finalized session directory that may be deleted in order to enforce the
+/// limits given by `-Z incremental-gc-max-age` and `-Z incremental-gc-max-size`.
+struct CollectableSession {
+    timestamp: SystemTime,
+    path: PathBuf,
+    size: u64,
+}
+
+/// Deletes finalized session directories of all crates in the incremental
+/// directory, least recently created first, until none of them is older than
+/// `-Z incremental-gc-max-age` and the directory is no larger than
+/// `-Z incremental-gc-max-size`.
+pub fn enforce_cache_limits(sess: &Session) -> io::Result<()> {
+    let opts = &sess.opts.debugging_opts;
+    // Saturate rather than overflow, a huge limit is as good as no limit.
+    let max_age = opts
+        .incremental_gc_max_age
+        .map(|hours| Duration::from_secs((hours as u64).saturating_mul(3600)));
+    let max_size = opts
+        .incremental_gc_max_size
+        .map(|megabytes| (megabytes as u64).saturating_mul(1024 * 1024));
+    if max_age.is_none() && max_size.is_none() {
+        return Ok(());
+    }
+
+    let incr_dir = sess.opts.incremental.as_ref().unwrap();
+    let current_session_dir = sess.incr_comp_session_dir().clone();
+
+    let mut sessions = vec![];
+    for crate_dir in incr_dir.read_dir()? {
+        let crate_dir = match crate_dir {
+            Ok(crate_dir) => crate_dir.path(),
+            // Ignore any errors
+            Err(_) => continue,
+        };
+        let entries = match crate_dir.read_dir() {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for dir_entry in entries {
+            let dir_entry = match dir_entry {
+                Ok(dir_entry) => dir_entry,
+                Err(_) => continue,
+            };
+            let entry_name = dir_entry.file_name();
+            let entry_name = entry_name.to_string_lossy();
+            if !is_session_directory(&entry_name) || !is_finalized(&entry_name) {
+                continue;
+            }
+
+            let path = dir_entry.path();
+            if path == current_session_dir {
+                continue;
+            }
+            if let Ok(timestamp) = extract_timestamp_from_session_dir(&entry_name) {
+                sessions.push(CollectableSession { timestamp, size: dir_size(&path), path });
+            }
+        }
+    }
+
+    let current_size = dir_size(&current_session_dir);
+    let collected = collect_sessions(
+        sessions,
+        current_size,
+        max_age,
+        max_size,
+        SystemTime::now(),
+        |path| {
+            // Get an exclusive lock. If that fails, some other process is
+            // still reading from the session directory.
+            let lock = match flock::Lock::new(&lock_file_path(path), false, false, true) {
+                Ok(lock) => lock,
+                Err(_) => {
+                    debug!("enforce_cache_limits() - not collecting, still in use");
+                    return false;
+                }
+            };
+
+            debug!("enforce_cache_limits() - deleting `{}`", path.display());
+            if let Err(err) = safe_remove_dir_all(path) {
+                sess.warn(&format!(
+                    "Failed to garbage collect incremental compilation \
+                     session directory `{}`: {}",
+                    path.display(),
+                    err
+                ));
+                return false;
+            }
+            delete_session_dir_lock_file(sess, &lock_file_path(path));
+
+            // Let's make it explicit that the file lock is released at this point,
+            // or rather, that we held on to it until here
+            mem::drop(lock);
+            true
+        },
+    );
+
+    if opts.incremental_info {
+        for (session, reason) in collected {
+            eprintln!(
+                "[incremental] garbage collected session directory `{}` ({} bytes, {})",
+                session.path.display(),
+                session.size,
+                reason,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Decides which of the given sessions to delete, oldest first, calling
+/// `delete` for each of them. Returns the sessions that were actually deleted,
+/// along with the reason.
+fn collect_sessions(
+    mut sessions: Vec<CollectableSession>,
+    current_size: u64,
+    max_age: Option<Duration>,
+    max_size: Option<u64>,
+    now: SystemTime,
+    mut delete: impl FnMut(&Path) -> bool,
+) -> Vec<(CollectableSession, &'static str)> {
+    sessions.sort_by_key(|session| session.timestamp);
+
+    let mut total_size = current_size + sessions.iter().map(|session| session.size).sum::<u64>();
+    let mut collected = vec![];
+
+    for session in sessions {
+        let is_too_old = max_age.map_or(false, |max_age| {
+            // Sessions with a timestamp in the future are never too old.
+            now.duration_since(session.timestamp).map_or(false, |age| age > max_age)
+        });
+        let reason = if is_too_old {
+            "older than the maximum age"
+        } else if max_size.map_or(false, |max_size| total_size > max_size) {
+            "cache larger than the maximum size"
+        } else {
+            // All remaining sessions are younger, and the cache is small enough.
+            break;
+        };
+
+        if delete(&session.path) {
+            total_size -= session.size;
+            collected.push((session, reason));
+        }
+    }
+
+    collected
+}
+
+/// Computes the size of all files in a directory, recursively. Note that files
+/// hard-linked between session directories are counted once per directory.
+fn dir_size(path: &Path) -> u64 {
+    let entries = match path.read_dir() {
+        Ok(entries) => entries,
+        Err(_) => return 0,
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
 fn delete_old(sess: &Session, path: &Path) {
     debug!("garbage_collect_session_directories() - deleting `{}`", path.display());
 
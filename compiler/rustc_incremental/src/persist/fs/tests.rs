@@ -82,3 +82,66 @@ fn test_find_source_directory_in_iter() {
         None
     );
 }
+
+#[test]
+fn test_collect_sessions() {
+    let session = |secs, name: &str, size| CollectableSession {
+        timestamp: UNIX_EPOCH + Duration::new(secs, 0),
+        path: PathBuf::from(name),
+        size,
+    };
+    let sessions = || {
+        vec![
+            session(30, "c", 300),
+            session(10, "a", 100),
+            session(20, "b", 200),
+            session(40, "d", 400),
+        ]
+    };
+    let now = UNIX_EPOCH + Duration::new(50, 0);
+    let collected_names = |collected: Vec<(CollectableSession, &str)>| {
+        collected.into_iter().map(|(session, _)| session.path).collect::<Vec<_>>()
+    };
+
+    // Without limits, nothing is collected.
+    assert_eq!(collect_sessions(sessions(), 0, None, None, now, |_| true).len(), 0);
+
+    // Sessions older than 25 seconds are collected.
+    assert_eq!(
+        collected_names(collect_sessions(
+            sessions(),
+            0,
+            Some(Duration::new(25, 0)),
+            None,
+            now,
+            |_| true
+        )),
+        vec![PathBuf::from("a"), PathBuf::from("b")]
+    );
+
+    // The oldest sessions are collected until the total size, including the
+    // current session, is at most 800 bytes.
+    assert_eq!(
+        collected_names(collect_sessions(sessions(), 100, None, Some(800), now, |_| true)),
+        vec![PathBuf::from("a"), PathBuf::from("b")]
+    );
+
+    // Sessions that are still in use are skipped.
+    assert_eq!(
+        collected_names(collect_sessions(sessions(), 100, None, Some(800), now, |path| {
+            path != Path::new("a")
+        })),
+        vec![PathBuf::from("b"), PathBuf::from("c")]
+    );
+
+    // A maximum age that would overflow the timestamp doesn't panic.
+    let max_age = Duration::from_secs(u64::MAX);
+    assert_eq!(collect_sessions(sessions(), 0, Some(max_age), None, now, |_| true).len(), 0);
+
+    // Sessions from the future are never too old.
+    let now = UNIX_EPOCH + Duration::new(5, 0);
+    assert_eq!(
+        collect_sessions(sessions(), 0, Some(Duration::new(1, 0)), None, now, |_| true).len(),
+        0
+    );
+}
@@ -2,12 +2,13 @@
 use rustc_data_structures::sync::join;
 use rustc_middle::dep_graph::{DepGraph, WorkProduct, WorkProductId};
 use rustc_middle::ty::TyCtxt;
+use rustc_serialize::json;
 use rustc_serialize::opaque::{FileEncodeResult, FileEncoder};
 use rustc_serialize::Encodable as RustcEncodable;
 use rustc_session::Session;
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::data::*;
 use super::dirty_clean;
@@ -19,6 +20,14 @@ pub fn save_dep_graph(tcx: TyCtxt<'_>) {
     debug!("save_dep_graph()");
     tcx.dep_graph.with_ignore(|| {
         let sess = tcx.sess;
+        if let Some(path) = &sess.opts.debugging_opts.dep_graph_snapshot {
+            if !sess.has_errors_or_delayed_span_bugs() {
+                sess.time("incr_comp_dump_dep_graph_snapshot", || {
+                    dump_dep_graph_snapshot(tcx, path)
+                });
+            }
+        }
+
         if sess.opts.incremental.is_none() {
             return;
         }
@@ -159,6 +168,17 @@ fn encode_dep_graph(tcx: TyCtxt<'_>, encoder: &mut FileEncoder) -> FileEncodeRes
     tcx.sess.time("incr_comp_encode_serialized_dep_graph", || tcx.dep_graph.encode(encoder))
 }
 
+fn dump_dep_graph_snapshot(tcx: TyCtxt<'_>, path: &Path) {
+    let snapshot = tcx.dep_graph.snapshot();
+    if let Err(err) = fs::write(path, json::as_json(&snapshot).to_string()) {
+        tcx.sess.err(&format!(
+            "failed to write dependency graph snapshot to `{}`: {}",
+            path.display(),
+            err
+        ));
+    }
+}
+
 fn encode_work_product_index(
     work_products: &FxHashMap<WorkProductId, WorkProduct>,
     encoder: &mut FileEncoder,
@@ -318,6 +318,18 @@ fn configure_and_expand_inner<'a>(
             ecx.check_unused_macros();
         });
 
+        if let (Some(path), Some(explorer)) =
+            (&sess.opts.debugging_opts.expansion_explorer, &ecx.expansion_explorer)
+        {
+            if let Err(err) = explorer.write(path) {
+                sess.err(&format!(
+                    "failed to write expansion explorer output to `{}`: {}",
+                    path.display(),
+                    err
+                ));
+            }
+        }
+
         let mut missing_fragment_specifiers: Vec<_> = ecx
             .sess
             .parse_sess
@@ -578,6 +590,21 @@ fn write_out_deps(
             .map(|fmap| escape_dep_filename(&fmap.unmapped_path.as_ref().unwrap_or(&fmap.name)))
             .collect();
 
+        // Account for explicitly marked-to-track files
+        // (e.g. accessed in proc macros).
+        let file_depinfo = sess.parse_sess.file_depinfo.borrow();
+        let mut extra_tracked_files: Vec<_> = file_depinfo
+            .iter()
+            .map(|path| {
+                let path = PathBuf::from(&*path.as_str());
+                escape_dep_filename(&FileName::Real(RealFileName::Named(path)))
+            })
+            .filter(|file| !files.contains(file))
+            .collect();
+        extra_tracked_files.sort_unstable();
+        extra_tracked_files.dedup();
+        files.extend(extra_tracked_files);
+
         if let Some(ref backend) = sess.opts.debugging_opts.codegen_backend {
             files.push(backend.to_string());
         }
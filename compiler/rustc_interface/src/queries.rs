@@ -437,6 +437,22 @@ pub fn enter<F, T>(&self, f: F) -> T
             }
         }
 
+        let sess = self.session();
+        if let Some(n) = sess.opts.debugging_opts.self_profile_summary {
+            sess.prof.with_profiler(|profiler| profiler.print_summary(n));
+        }
+        if let Some(path) = &sess.opts.debugging_opts.self_profile_chrome_trace {
+            sess.prof.with_profiler(|profiler| {
+                if let Err(err) = profiler.write_chrome_trace(path) {
+                    sess.err(&format!(
+                        "failed to write Chrome trace to `{}`: {}",
+                        path.display(),
+                        err
+                    ));
+                }
+            });
+        }
+
         _timer = Some(self.session().timer("free_global_ctxt"));
 
         ret
@@ -7,7 +7,8 @@
 use rustc_session::config::{rustc_optgroups, ErrorOutputType, ExternLocation, Options, Passes};
 use rustc_session::config::{CFGuard, ExternEntry, LinkerPluginLto, LtoCli, SwitchWithOptPath};
 use rustc_session::config::{
-    Externs, OutputType, OutputTypes, SanitizerSet, SymbolManglingVersion, WasiExecModel,
+    CoverageOptions, Externs, MonoStatsFormat, OutputType, OutputTypes, SanitizerSet,
+    SymbolManglingVersion, WasiExecModel,
 };
 use rustc_session::lint::Level;
 use rustc_session::search_paths::SearchPath;
@@ -472,6 +473,7 @@ macro_rules! untracked {
     untracked!(borrowck, String::from("other"));
     untracked!(borrowck_stats, true);
     untracked!(deduplicate_diagnostics, true);
+    untracked!(dep_graph_snapshot, Some(PathBuf::from("dep-graph.json")));
     untracked!(dep_tasks, true);
     untracked!(dont_buffer_diagnostics, true);
     untracked!(dump_dep_graph, true);
@@ -480,10 +482,15 @@ macro_rules! untracked {
     untracked!(dump_mir_dir, String::from("abc"));
     untracked!(dump_mir_exclude_pass_number, true);
     untracked!(dump_mir_graphviz, true);
+    untracked!(dump_mono_stats, SwitchWithOptPath::Enabled(Some("mono-stats".into())));
+    untracked!(dump_mono_stats_format, MonoStatsFormat::Csv);
     untracked!(emit_future_incompat_report, true);
     untracked!(emit_stack_sizes, true);
+    untracked!(expansion_explorer, Some(PathBuf::from("expansions.json")));
     untracked!(hir_stats, true);
     untracked!(identify_regions, true);
+    untracked!(incremental_gc_max_age, Some(24));
+    untracked!(incremental_gc_max_size, Some(1024));
     untracked!(incremental_ignore_spans, true);
     untracked!(incremental_info, true);
     untracked!(incremental_verify_ich, true);
@@ -512,7 +519,9 @@ macro_rules! untracked {
     untracked!(query_stats, true);
     untracked!(save_analysis, true);
     untracked!(self_profile, SwitchWithOptPath::Enabled(None));
+    untracked!(self_profile_chrome_trace, Some(PathBuf::from("trace.json")));
     untracked!(self_profile_events, Some(vec![String::new()]));
+    untracked!(self_profile_summary, Some(20));
     untracked!(span_debug, true);
     untracked!(span_free_formats, true);
     untracked!(strip, Strip::None);
@@ -546,6 +555,7 @@ macro_rules! tracked {
     tracked!(binary_dep_depinfo, true);
     tracked!(chalk, true);
     tracked!(codegen_backend, Some("abc".to_string()));
+    tracked!(coverage_options, CoverageOptions { branch: true });
     tracked!(crate_attr, vec!["abc".to_string()]);
     tracked!(debug_macros, true);
     tracked!(dep_info_omit_d_target, true);
@@ -35,7 +35,7 @@
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{self, print::Printer, subst::GenericArg, Ty, TyCtxt};
 use rustc_serialize::json::Json;
-use rustc_session::lint::{BuiltinLintDiagnostics, ExternDepSpec};
+use rustc_session::lint::{BuiltinLintDiagnostics, ExternDepSpec, ProcMacroLintParts};
 use rustc_session::lint::{FutureIncompatibleInfo, Level, Lint, LintBuffer, LintId};
 use rustc_session::Session;
 use rustc_session::SessionLintStore;
@@ -670,6 +670,22 @@ fn lookup_with_diagnostics(
                         json
                     );
                 }
+                BuiltinLintDiagnostics::ProcMacroLint(ProcMacroLintParts {
+                    children,
+                    suggestions,
+                }) => {
+                    for (is_help, msg, span) in children {
+                        match (is_help, span.primary_spans().is_empty()) {
+                            (true, true) => db.help(&msg),
+                            (true, false) => db.span_help(span, &msg),
+                            (false, true) => db.note(&msg),
+                            (false, false) => db.span_note(span, &msg),
+                        };
+                    }
+                    for (msg, parts, applicability) in suggestions {
+                        db.multipart_suggestion(&msg, parts, applicability);
+                    }
+                }
             }
             // Rewrap `db`, and pass control to the user.
             decorate(LintDiagnosticBuilder::new(db));
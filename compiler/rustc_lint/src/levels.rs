@@ -4,7 +4,7 @@
 use rustc_ast::attr;
 use rustc_ast::unwrap_or;
 use rustc_ast_pretty::pprust;
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
@@ -56,6 +56,9 @@ pub struct LintLevelsBuilder<'s> {
     cur: u32,
     warn_about_weird_lints: bool,
     store: &'s LintStore,
+    /// Tools declared with `#![register_tool]` at the crate root, whose scoped lints may be
+    /// emitted by procedural macros.
+    registered_tools: FxHashSet<Symbol>,
 }
 
 pub struct BuilderPush {
@@ -72,6 +75,7 @@ pub fn new(sess: &'s Session, warn_about_weird_lints: bool, store: &'s LintStore
             id_to_set: Default::default(),
             warn_about_weird_lints,
             store,
+            registered_tools: Default::default(),
         };
         builder.process_command_line(sess, store);
         assert_eq!(builder.sets.list.len(), 1);
@@ -223,6 +227,15 @@ pub(crate) fn push(
         let mut specs = FxHashMap::default();
         let sess = self.sess;
         let bad_attr = |span| struct_span_err!(sess, span, E0452, "malformed lint attribute input");
+        if is_crate_node {
+            for attr in sess.filter_by_name(attrs, sym::register_tool) {
+                for nested in attr.meta_item_list().unwrap_or_default() {
+                    if let Some(ident) = nested.ident() {
+                        self.registered_tools.insert(ident.name);
+                    }
+                }
+            }
+        }
         for attr in attrs {
             let level = match Level::from_symbol(attr.name_or_empty()) {
                 None => continue,
@@ -304,7 +317,9 @@ pub(crate) fn push(
                 };
                 let tool_name = if meta_item.path.segments.len() > 1 {
                     let tool_ident = meta_item.path.segments[0].ident;
-                    if !attr::is_known_lint_tool(tool_ident) {
+                    if !attr::is_known_lint_tool(tool_ident)
+                        && !self.registered_tools.contains(&tool_ident.name)
+                    {
                         struct_span_err!(
                             sess,
                             tool_ident.span,
@@ -384,7 +399,22 @@ pub(crate) fn push(
                                 // If Tool(Err(None, _)) is returned, then either the lint does not
                                 // exist in the tool or the code was not compiled with the tool and
                                 // therefore the lint was never added to the `LintStore`. To detect
-                                // this is the responsibility of the lint tool.
+                                // this is the responsibility of the lint tool. Lints emitted by
+                                // procedural macros are not in the `LintStore` either, but are
+                                // known to the session once the macro has emitted them.
+                                let complete_name =
+                                    Symbol::intern(&format!("{}::{}", tool_name.unwrap(), name));
+                                let proc_macro_lint = sess
+                                    .parse_sess
+                                    .proc_macro_lints
+                                    .lock()
+                                    .get(&complete_name)
+                                    .copied();
+                                if let Some(lint) = proc_macro_lint {
+                                    let src =
+                                        LintLevelSource::Node(complete_name, li.span(), reason);
+                                    self.insert_spec(&mut specs, LintId::of(lint), (level, src));
+                                }
                             }
                         }
                     }
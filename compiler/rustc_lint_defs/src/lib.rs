@@ -247,6 +247,17 @@ pub enum ExternDepSpec {
     Raw(String),
 }
 
+/// The child messages and suggestions of a diagnostic that a procedural macro emitted as a
+/// lint, added to the lint's diagnostic once its level is known. Span labels are part of the
+/// `span` of the `BufferedEarlyLint`.
+#[derive(PartialEq)]
+pub struct ProcMacroLintParts {
+    /// Child messages as `(is_help, message, span)`, the others are emitted as notes.
+    pub children: Vec<(bool, String, MultiSpan)>,
+    /// Suggestions as `(message, parts, applicability)`.
+    pub suggestions: Vec<(String, Vec<(Span, String)>, Applicability)>,
+}
+
 // This could be a closure, but then implementing derive trait
 // becomes hacky (and it gets allocated).
 #[derive(PartialEq)]
@@ -266,6 +277,7 @@ pub enum BuiltinLintDiagnostics {
     PatternsInFnsWithoutBody(Span, Ident),
     LegacyDeriveHelpers(Span),
     ExternDepSpec(String, ExternDepSpec),
+    ProcMacroLint(ProcMacroLintParts),
 }
 
 /// Lints that are buffered up early on in the `Session` before the
@@ -10,6 +10,7 @@ using namespace llvm;
 
 struct LLVMRustCounterMappingRegion {
   coverage::Counter Count;
+  coverage::Counter FalseCount;
   uint32_t FileID;
   uint32_t ExpandedFileID;
   uint32_t LineStart;
@@ -45,10 +46,17 @@ extern "C" void LLVMRustCoverageWriteMappingToBuffer(
   SmallVector<coverage::CounterMappingRegion, 0> MappingRegions;
   MappingRegions.reserve(NumMappingRegions);
   for (const auto &Region : makeArrayRef(RustMappingRegions, NumMappingRegions)) {
+#if LLVM_VERSION_GE(12, 0)
+    MappingRegions.emplace_back(
+        Region.Count, Region.FalseCount, Region.FileID, Region.ExpandedFileID,
+        Region.LineStart, Region.ColumnStart, Region.LineEnd, Region.ColumnEnd,
+        Region.Kind);
+#else
     MappingRegions.emplace_back(
         Region.Count, Region.FileID, Region.ExpandedFileID,
         Region.LineStart, Region.ColumnStart, Region.LineEnd, Region.ColumnEnd,
         Region.Kind);
+#endif
   }
   auto CoverageMappingWriter = coverage::CoverageMappingWriter(
       makeArrayRef(VirtualFileMappingIDs, NumVirtualFileMappingIDs),
@@ -104,7 +112,9 @@ extern "C" void LLVMRustCoverageWriteMappingVarNameToString(RustStringRef Str) {
 }
 
 extern "C" uint32_t LLVMRustCoverageMappingVersion() {
-#if LLVM_VERSION_GE(11, 0)
+#if LLVM_VERSION_GE(12, 0)
+  return coverage::CovMapVersion::Version5;
+#elif LLVM_VERSION_GE(11, 0)
   return coverage::CovMapVersion::Version4;
 #else
   return coverage::CovMapVersion::Version3;
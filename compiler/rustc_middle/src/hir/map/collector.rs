@@ -18,6 +18,7 @@
 use rustc_span::source_map::SourceMap;
 use rustc_span::{Span, Symbol, DUMMY_SP};
 
+use std::hash::Hasher;
 use std::iter::repeat;
 
 /// A visitor that walks over the HIR and collects `Node`s into a HIR map.
@@ -87,6 +88,28 @@ fn upstream_crates(cstore: &dyn CrateStore) -> Vec<(Symbol, Fingerprint, Svh)> {
     upstream_crates
 }
 
+/// The files explicitly tracked by proc macros through `tracked_path::path`,
+/// along with a fingerprint of their current contents, or `None` if they can
+/// no longer be read.
+fn tracked_files(sess: &Session) -> Vec<(Symbol, Option<Fingerprint>)> {
+    let mut tracked_files: Vec<_> = sess
+        .parse_sess
+        .file_depinfo
+        .borrow()
+        .iter()
+        .map(|&path| {
+            let fingerprint = std::fs::read(&*path.as_str()).ok().map(|contents| {
+                let mut hasher = StableHasher::new();
+                hasher.write(&contents);
+                hasher.finish()
+            });
+            (path, fingerprint)
+        })
+        .collect();
+    tracked_files.sort_unstable_by_key(|&(path, _)| path.as_str());
+    tracked_files
+}
+
 impl<'a, 'hir> NodeCollector<'a, 'hir> {
     pub(super) fn root(
         sess: &'a Session,
@@ -146,6 +169,7 @@ pub(super) fn root(
 
     pub(super) fn finalize_and_compute_crate_hash(
         mut self,
+        sess: &Session,
         crate_disambiguator: CrateDisambiguator,
         cstore: &dyn CrateStore,
         commandline_args_hash: u64,
@@ -182,8 +206,13 @@ pub(super) fn finalize_and_compute_crate_hash(
 
         source_file_names.sort_unstable();
 
+        // Files read by proc macros are inputs of the crate just like its
+        // source files. Hashing their contents makes `crate_hash`, which is
+        // re-evaluated in every incremental session, change along with them.
+        let tracked_files = tracked_files(sess);
+
         let crate_hash_input = (
-            ((node_hashes, upstream_crates), source_file_names),
+            (((node_hashes, upstream_crates), source_file_names), tracked_files),
             (commandline_args_hash, crate_disambiguator.to_fingerprint()),
         );
 
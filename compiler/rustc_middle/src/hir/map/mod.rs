@@ -978,7 +978,12 @@ pub(super) fn index_hir<'tcx>(tcx: TyCtxt<'tcx>, cnum: CrateNum) -> &'tcx Indexe
 
         let crate_disambiguator = tcx.sess.local_crate_disambiguator();
         let cmdline_args = tcx.sess.opts.dep_tracking_hash();
-        collector.finalize_and_compute_crate_hash(crate_disambiguator, &*tcx.cstore, cmdline_args)
+        collector.finalize_and_compute_crate_hash(
+            tcx.sess,
+            crate_disambiguator,
+            &*tcx.cstore,
+            cmdline_args,
+        )
     };
 
     tcx.arena.alloc(IndexedHir { crate_hash, map })
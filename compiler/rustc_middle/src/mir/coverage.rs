@@ -105,6 +105,12 @@ pub enum CoverageKind {
         rhs: ExpressionOperandId,
     },
     Unreachable,
+    /// A branch region, counting how often a condition (or a `match` arm) was taken and how often
+    /// it was not. Both counts refer to counters or expressions injected elsewhere in the function.
+    Branch {
+        true_count: ExpressionOperandId,
+        false_count: ExpressionOperandId,
+    },
 }
 
 impl CoverageKind {
@@ -114,6 +120,7 @@ pub fn as_operand_id(&self) -> ExpressionOperandId {
             Counter { id, .. } => ExpressionOperandId::from(id),
             Expression { id, .. } => ExpressionOperandId::from(id),
             Unreachable => bug!("Unreachable coverage cannot be part of an expression"),
+            Branch { .. } => bug!("Branch coverage cannot be part of an expression"),
         }
     }
 
@@ -128,6 +135,10 @@ pub fn is_expression(&self) -> bool {
     pub fn is_unreachable(&self) -> bool {
         *self == Self::Unreachable
     }
+
+    pub fn is_branch(&self) -> bool {
+        matches!(self, Self::Branch { .. })
+    }
 }
 
 impl Debug for CoverageKind {
@@ -144,6 +155,9 @@ fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
                 rhs.index(),
             ),
             Unreachable => write!(fmt, "Unreachable"),
+            Branch { true_count, false_count } => {
+                write!(fmt, "Branch(true: {}, false: {})", true_count.index(), false_count.index())
+            }
         }
     }
 }
@@ -6,7 +6,7 @@
 
 use debug::{DebugCounters, NESTED_INDENT};
 use graph::{BasicCoverageBlock, BcbBranch, CoverageGraph, TraverseCoverageGraphWithLoops};
-use spans::CoverageSpan;
+use spans::{BranchSpan, CoverageSpan};
 
 use rustc_data_structures::graph::WithNumNodes;
 use rustc_index::bit_set::BitSet;
@@ -44,9 +44,72 @@ pub fn make_bcb_counters(
         &mut self,
         basic_coverage_blocks: &mut CoverageGraph,
         coverage_spans: &Vec<CoverageSpan>,
+        branch_spans: &[BranchSpan],
     ) -> Result<Vec<CoverageKind>, Error> {
         let mut bcb_counters = BcbCounters::new(self, basic_coverage_blocks);
-        bcb_counters.make_bcb_counters(coverage_spans)
+        bcb_counters.make_bcb_counters(coverage_spans, branch_spans)
+    }
+
+    /// Makes a `CoverageKind::Branch` for each `BranchSpan`, from the counters and expressions
+    /// already assigned (by `make_bcb_counters()`) to the branching BCB and its branches. The
+    /// "true" edges into the arms of a `match` are added up, and arms (without a "false" BCB) are
+    /// counted as not taken by subtracting that sum from the first test of the `match`; these
+    /// `Expression`s are added to `collect_intermediate_expressions`.
+    pub fn make_branch_region_counters(
+        &mut self,
+        basic_coverage_blocks: &CoverageGraph,
+        branch_spans: &[BranchSpan],
+        collect_intermediate_expressions: &mut Vec<CoverageKind>,
+    ) -> Result<Vec<CoverageKind>, Error> {
+        let branch_operand = |from_bcb, to_bcb| {
+            let branch = BcbBranch::from_to(from_bcb, to_bcb, basic_coverage_blocks);
+            match branch.counter(basic_coverage_blocks) {
+                Some(counter_kind) => Ok(counter_kind.as_operand_id()),
+                None => Error::from_string(format!("{:?} has no counter", branch)),
+            }
+        };
+
+        let mut branches = Vec::with_capacity(branch_spans.len());
+        for branch_span in branch_spans {
+            let mut true_count = None;
+            for &(from_bcb, to_bcb) in &branch_span.true_edges {
+                let edge_count = branch_operand(from_bcb, to_bcb)?;
+                true_count = Some(match true_count {
+                    Some(sum) => {
+                        let expression = self.make_expression(sum, Op::Add, edge_count, || None);
+                        let sum = expression.as_operand_id();
+                        collect_intermediate_expressions.push(expression);
+                        sum
+                    }
+                    None => edge_count,
+                });
+            }
+            let true_count = match true_count {
+                Some(true_count) => true_count,
+                None => return Error::from_string(format!("{:?} has no edges", branch_span)),
+            };
+            let false_count = match branch_span.false_bcb {
+                Some(false_bcb) => branch_operand(branch_span.from_bcb, false_bcb)?,
+                None => {
+                    let from_count = match basic_coverage_blocks[branch_span.from_bcb].counter() {
+                        Some(counter_kind) => counter_kind.as_operand_id(),
+                        None => {
+                            return Error::from_string(format!(
+                                "{:?} has no counter",
+                                branch_span.from_bcb
+                            ));
+                        }
+                    };
+                    let expression =
+                        self.make_expression(from_count, Op::Subtract, true_count, || None);
+                    let false_count = expression.as_operand_id();
+                    collect_intermediate_expressions.push(expression);
+                    false_count
+                }
+            };
+            branches.push(CoverageKind::Branch { true_count, false_count });
+        }
+        Ok(branches)
     }
 
     fn make_counter<F>(&mut self, debug_block_label_fn: F) -> CoverageKind
@@ -141,6 +204,7 @@ fn new(
     fn make_bcb_counters(
         &mut self,
         coverage_spans: &[CoverageSpan],
+        branch_spans: &[BranchSpan],
     ) -> Result<Vec<CoverageKind>, Error> {
         debug!("make_bcb_counters(): adding a counter or expression to each BasicCoverageBlock");
         let num_bcbs = self.basic_coverage_blocks.num_nodes();
@@ -150,6 +214,14 @@ fn make_bcb_counters(
         for covspan in coverage_spans {
             bcbs_with_coverage.insert(covspan.bcb);
         }
+        // The counts of a branch region are derived from the counters of the branching BCB and
+        // its branches, so treat branching BCBs as if they had a `CoverageSpan`.
+        for branch_span in branch_spans {
+            bcbs_with_coverage.insert(branch_span.from_bcb);
+            for &(from_bcb, _) in &branch_span.true_edges {
+                bcbs_with_coverage.insert(from_bcb);
+            }
+        }
 
         // Walk the `CoverageGraph`. For each `BasicCoverageBlock` node with an associated
         // `CoverageSpan`, add a counter. If the `BasicCoverageBlock` branches, add a counter or
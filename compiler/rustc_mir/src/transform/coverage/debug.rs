@@ -309,6 +309,11 @@ pub fn format_counter(&self, counter_kind: &CoverageKind) -> String {
                 format!("Expression({})", self.format_counter_kind(counter_kind))
             }
             CoverageKind::Unreachable { .. } => "Unreachable".to_owned(),
+            CoverageKind::Branch { true_count, false_count } => format!(
+                "Branch(true: {}, false: {})",
+                self.format_operand(true_count),
+                self.format_operand(false_count),
+            ),
         }
     }
 
@@ -533,6 +538,17 @@ pub fn add_expression_operands(&mut self, expression: &CoverageKind) {
         }
     }
 
+    /// Branch regions are not expressions themselves, but their counts keep the counters and
+    /// expressions they refer to in use.
+    pub fn add_branch_operands(&mut self, branch: &CoverageKind) {
+        if let Some(used_expression_operands) = self.some_used_expression_operands.as_mut() {
+            if let CoverageKind::Branch { true_count, false_count } = *branch {
+                used_expression_operands.entry(true_count).or_insert_with(Vec::new);
+                used_expression_operands.entry(false_count).or_insert_with(Vec::new);
+            }
+        }
+    }
+
     pub fn expression_is_used(&self, expression: &CoverageKind) -> bool {
         if let Some(used_expression_operands) = self.some_used_expression_operands.as_ref() {
             used_expression_operands.contains_key(&expression.as_operand_id())
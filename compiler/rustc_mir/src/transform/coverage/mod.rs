@@ -10,7 +10,7 @@
 
 use counters::CoverageCounters;
 use graph::{BasicCoverageBlock, BasicCoverageBlockData, CoverageGraph};
-use spans::{CoverageSpan, CoverageSpans};
+use spans::{BranchSpan, CoverageSpan, CoverageSpans};
 
 use crate::transform::MirPass;
 use crate::util::pretty;
@@ -98,6 +98,7 @@ struct Instrumentor<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     mir_body: &'a mut mir::Body<'tcx>,
     source_file: Lrc<SourceFile>,
+    hir_body: &'tcx rustc_hir::Body<'tcx>,
     fn_sig_span: Span,
     body_span: Span,
     basic_coverage_blocks: CoverageGraph,
@@ -123,6 +124,7 @@ fn new(pass_name: &'a str, tcx: TyCtxt<'tcx>, mir_body: &'a mut mir::Body<'tcx>)
             tcx,
             mir_body,
             source_file,
+            hir_body,
             fn_sig_span,
             body_span,
             basic_coverage_blocks,
@@ -180,6 +182,20 @@ fn inject_counters(&'a mut self) {
             );
         }
 
+        ////////////////////////////////////////////////////
+        // If requested, compute `BranchSpan`s for the branching `BasicCoverageBlock`s.
+        let branch_spans = if tcx.sess.opts.debugging_opts.coverage_options.branch {
+            spans::generate_branch_spans(
+                &self.mir_body,
+                self.hir_body,
+                body_span,
+                &self.basic_coverage_blocks,
+                &coverage_spans,
+            )
+        } else {
+            Vec::new()
+        };
+
         ////////////////////////////////////////////////////
         // Create an optimized mix of `Counter`s and `Expression`s for the `CoverageGraph`. Ensure
         // every `CoverageSpan` has a `Counter` or `Expression` assigned to its `BasicCoverageBlock`
@@ -190,16 +206,33 @@ fn inject_counters(&'a mut self) {
         // direct associate to any `BasicCoverageBlock`, are returned in the method `Result`.
         let intermediate_expressions_or_error = self
             .coverage_counters
-            .make_bcb_counters(&mut self.basic_coverage_blocks, &coverage_spans);
+            .make_bcb_counters(&mut self.basic_coverage_blocks, &coverage_spans, &branch_spans);
 
         let (result, intermediate_expressions) = match intermediate_expressions_or_error {
-            Ok(intermediate_expressions) => {
+            Ok(mut intermediate_expressions) => {
+                ////////////////////////////////////////////////////
+                // Look up the counts of each `BranchSpan` before the counters are removed from
+                // the `CoverageGraph` below. Branch regions are injected with the intermediate
+                // expressions, as the final step.
+                let branches_or_error = self.coverage_counters.make_branch_region_counters(
+                    &self.basic_coverage_blocks,
+                    &branch_spans,
+                    &mut intermediate_expressions,
+                );
+                let branches = match branches_or_error {
+                    Ok(branches) => branches,
+                    Err(e) => bug!("Error processing: {:?}: {:?}", def_id, e),
+                };
+
                 // If debugging, add any intermediate expressions (which are not associated with any
                 // BCB) to the `debug_used_expressions` map.
                 if debug_used_expressions.is_enabled() {
                     for intermediate_expression in &intermediate_expressions {
                         debug_used_expressions.add_expression_operands(intermediate_expression);
                     }
+                    for branch in &branches {
+                        debug_used_expressions.add_branch_operands(branch);
+                    }
                 }
 
                 ////////////////////////////////////////////////////
@@ -230,6 +263,8 @@ fn inject_counters(&'a mut self) {
                 // debug output, if any.
                 ////////////////////////////////////////////////////
 
+                self.inject_branch_regions(branch_spans, branches);
+
                 (Ok(()), intermediate_expressions)
             }
             Err(e) => (Err(e), Vec::new()),
@@ -317,6 +352,43 @@ fn inject_coverage_span_counters(
         }
     }
 
+    /// Inject a `Branch` coverage statement, with its code region, for each `BranchSpan`. Branch
+    /// regions have no runtime overhead: their counts are computed from the counters and
+    /// expressions injected for the `BasicCoverageBlock`s, so they are injected into the
+    /// `START_BLOCK`, like intermediate expressions.
+    fn inject_branch_regions(
+        &mut self,
+        branch_spans: Vec<BranchSpan>,
+        branches: Vec<CoverageKind>,
+    ) {
+        let source_map = self.tcx.sess.source_map();
+        let body_span = self.body_span;
+        let file_name = Symbol::intern(&self.source_file.name.to_string());
+
+        for (branch_span, branch) in branch_spans.into_iter().zip(branches) {
+            let code_region = make_code_region(
+                source_map,
+                file_name,
+                &self.source_file,
+                branch_span.span,
+                body_span,
+            );
+            debug!(
+                "  injecting branch {:?} for {:?} at code region: {:?}",
+                branch, branch_span, code_region
+            );
+            let data = &mut self.mir_body[mir::START_BLOCK];
+            let source_info = data.terminator().source_info;
+            data.statements.push(Statement {
+                source_info,
+                kind: StatementKind::Coverage(box Coverage {
+                    kind: branch,
+                    code_region: Some(code_region),
+                }),
+            });
+        }
+    }
+
     /// `inject_coverage_span_counters()` looped through the `CoverageSpan`s and injected the
     /// counter from the `CoverageSpan`s `BasicCoverageBlock`, removing it from the BCB in the
     /// process (via `take_counter()`).
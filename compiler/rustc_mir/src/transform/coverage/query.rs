@@ -95,6 +95,10 @@ fn visit_coverage(&mut self, coverage: &Coverage, _location: Location) {
                     self.update_from_expression_operand(u32::from(lhs));
                     self.update_from_expression_operand(u32::from(rhs));
                 }
+                CoverageKind::Branch { true_count, false_count } => {
+                    self.update_from_expression_operand(u32::from(true_count));
+                    self.update_from_expression_operand(u32::from(false_count));
+                }
                 _ => {}
             }
         } else {
@@ -156,7 +160,9 @@ fn covered_code_regions<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId) -> Vec<&'tcx Cod
         .iter()
         .map(|data| {
             data.statements.iter().filter_map(|statement| match statement.kind {
-                StatementKind::Coverage(box ref coverage) => {
+                // Branch regions only make sense together with the counters of the function, so
+                // they are not kept for functions that are never codegenned.
+                StatementKind::Coverage(box ref coverage) if !coverage.kind.is_branch() => {
                     coverage.code_region.as_ref() // may be None
                 }
                 _ => None,
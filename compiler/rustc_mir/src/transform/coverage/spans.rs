@@ -3,10 +3,13 @@
 
 use crate::util::spanview::source_range_no_file;
 
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
 use rustc_data_structures::graph::WithNumNodes;
+use rustc_hir as hir;
+use rustc_hir::intravisit;
 use rustc_middle::mir::{
-    self, AggregateKind, BasicBlock, FakeReadCause, Rvalue, Statement, StatementKind, Terminator,
-    TerminatorKind,
+    self, AggregateKind, BasicBlock, FakeReadCause, Local, LocalKind, Operand, Rvalue, Statement,
+    StatementKind, Terminator, TerminatorKind,
 };
 use rustc_middle::ty::TyCtxt;
 
@@ -160,6 +163,269 @@ pub fn format_coverage_statements(
     }
 }
 
+/// A branch region, counting how often control flow went from `from_bcb` along one of the
+/// `true_edges`, and how often it did not.
+#[derive(Debug, Clone)]
+pub(super) struct BranchSpan {
+    pub span: Span,
+    /// The BCB counting every evaluation of the branch: the `SwitchInt` on a condition, or the
+    /// first `SwitchInt` testing the patterns of a `match`.
+    pub from_bcb: BasicCoverageBlock,
+    /// The edges taken when the branch is "true": the edge from a condition to the BCB taken when
+    /// it is `true`, or every edge from the tests of a `match` into one of its arms.
+    pub true_edges: Vec<(BasicCoverageBlock, BasicCoverageBlock)>,
+    /// The BCB taken when a boolean condition is `false`. This is `None` for `match` arms, whose
+    /// "false" count is the number of times any other arm was taken.
+    pub false_bcb: Option<BasicCoverageBlock>,
+}
+
+/// The `&&` and `||` expressions and the `match` expressions of a function body, used to find the
+/// conditions and the arms their `SwitchInt`s branch on.
+struct HirBranches<'tcx> {
+    /// Maps the span of each `&&` or `||` expression to the spans of its two operands.
+    logical_ops: FxHashMap<Span, (Span, Span)>,
+    /// The arms of each `match` expression written as such (not desugared from `if let`, `for`,
+    /// `?`, etc.).
+    matches: Vec<&'tcx [hir::Arm<'tcx>]>,
+}
+
+impl<'tcx> intravisit::Visitor<'tcx> for HirBranches<'tcx> {
+    type Map = intravisit::ErasedMap<'tcx>;
+
+    fn nested_visit_map(&mut self) -> intravisit::NestedVisitorMap<Self::Map> {
+        // Closures have their own MIR, and their own branches.
+        intravisit::NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        match expr.kind {
+            hir::ExprKind::Binary(op, lhs, rhs)
+                if matches!(op.node, hir::BinOpKind::And | hir::BinOpKind::Or) =>
+            {
+                self.logical_ops.insert(expr.span, (lhs.span, rhs.span));
+            }
+            hir::ExprKind::Match(_, arms, hir::MatchSource::Normal) => self.matches.push(arms),
+            _ => {}
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Finds the branches of the `CoverageGraph` to report, for `-Z coverage-options=branch`.
+///
+/// Every condition a `SwitchInt` on a `bool` branches on produces a `BranchSpan`, covering the
+/// condition. Each operand of `&&` and `||` is a condition of its own, so `if a && b` produces one
+/// `BranchSpan` for `a` and one for `b`, but none for `a && b`, whose outcome follows from its
+/// operands. The `SwitchInt`s testing the patterns of a `match` produce one `BranchSpan` per arm,
+/// covering its pattern, and counting how often that arm was taken out of all executions of the
+/// `match`.
+pub(super) fn generate_branch_spans(
+    mir_body: &mir::Body<'tcx>,
+    hir_body: &'tcx hir::Body<'tcx>,
+    body_span: Span,
+    basic_coverage_blocks: &CoverageGraph,
+    coverage_spans: &[CoverageSpan],
+) -> Vec<BranchSpan> {
+    let mut hir_branches = HirBranches { logical_ops: FxHashMap::default(), matches: Vec::new() };
+    intravisit::Visitor::visit_body(&mut hir_branches, hir_body);
+
+    // These maps are computed once, rather than searched for every branch, so that large functions
+    // with many branches don't take quadratic time.
+    let assignment_spans = temp_assignment_spans(mir_body);
+    let mut first_bcb_spans: FxHashMap<BasicCoverageBlock, Span> = FxHashMap::default();
+    for covspan in coverage_spans.iter().filter(|covspan| covspan.span != body_span) {
+        first_bcb_spans
+            .entry(covspan.bcb)
+            .and_modify(|span| {
+                if covspan.span.lo() < span.lo() {
+                    *span = covspan.span;
+                }
+            })
+            .or_insert(covspan.span);
+    }
+
+    let mut branch_spans = Vec::new();
+    // Both operands of `&&` and `||` are branched on by a `SwitchInt` with the span of the entire
+    // expression, so they are told apart once all of them are known.
+    let mut operand_branches: FxIndexMap<Span, Vec<BranchSpan>> = FxIndexMap::default();
+    let mut match_tests = vec![Vec::new(); hir_branches.matches.len()];
+    for (from_bcb, bcb_data) in basic_coverage_blocks.iter_enumerated() {
+        let terminator = bcb_data.terminator(mir_body);
+        let (discr, switch_ty, targets) = match terminator.kind {
+            TerminatorKind::SwitchInt { ref discr, switch_ty, ref targets } => {
+                (discr, switch_ty, targets)
+            }
+            _ => continue,
+        };
+
+        // The tests of a `match` have the span of the (sub-)pattern they test.
+        let terminator_span = terminator.source_info.span;
+        if let Some(match_index) = hir_branches
+            .matches
+            .iter()
+            .position(|arms| arms.iter().any(|arm| arm.pat.span.contains(terminator_span)))
+        {
+            match_tests[match_index].push(from_bcb);
+            continue;
+        }
+        if !switch_ty.is_bool() {
+            continue;
+        }
+
+        // Targets leading to `Unreachable` blocks are not part of the `CoverageGraph`.
+        let successors = &basic_coverage_blocks.successors[from_bcb];
+        let target_bcb = |bb| {
+            basic_coverage_blocks
+                .bcb_from_bb(bb)
+                .filter(|target_bcb| successors.contains(target_bcb))
+        };
+        let mut values = targets.iter();
+        let false_bb = match (values.next(), values.next()) {
+            (Some((0, false_bb)), None) => false_bb,
+            _ => continue,
+        };
+        let (true_bcb, false_bcb) = match (target_bcb(targets.otherwise()), target_bcb(false_bb)) {
+            (Some(true_bcb), Some(false_bcb)) if true_bcb != false_bcb => (true_bcb, false_bcb),
+            _ => continue,
+        };
+        let branch_span = |span| BranchSpan {
+            span: function_source_span(span, body_span),
+            from_bcb,
+            true_edges: vec![(from_bcb, true_bcb)],
+            false_bcb: Some(false_bcb),
+        };
+
+        if hir_branches.logical_ops.contains_key(&terminator_span) {
+            operand_branches.entry(terminator_span).or_default().push(branch_span(terminator_span));
+        } else if let Some(span) = condition_span(mir_body, &assignment_spans, discr) {
+            // The result of `&&` or `||` (for instance, as an `if` condition) is not a condition of
+            // its own.
+            if !hir_branches.logical_ops.contains_key(&span) {
+                branch_spans.push(branch_span(span));
+            }
+        }
+    }
+
+    for (op_span, branches) in operand_branches.iter() {
+        let (lhs_span, rhs_span) = hir_branches.logical_ops[op_span];
+        for branch_span in branches {
+            // The right-hand operand is only evaluated after branching on the left-hand operand.
+            let from_bcb = branch_span.from_bcb;
+            let is_rhs = branches.iter().any(|other| {
+                other.from_bcb != from_bcb
+                    && basic_coverage_blocks.is_dominated_by(from_bcb, other.from_bcb)
+            });
+            let operand_span = if is_rhs { rhs_span } else { lhs_span };
+            // An operand that is itself `&&` or `||` is covered by the branches on its operands.
+            if !hir_branches.logical_ops.contains_key(&operand_span) {
+                branch_spans.push(BranchSpan {
+                    span: function_source_span(operand_span, body_span),
+                    ..branch_span.clone()
+                });
+            }
+        }
+    }
+
+    'matches: for (arms, tests) in hir_branches.matches.iter().zip(match_tests) {
+        // Every execution of the `match` starts with the test that dominates all other tests.
+        let root_bcb = match tests.iter().find(|&&root_bcb| {
+            tests.iter().all(|&test_bcb| basic_coverage_blocks.is_dominated_by(test_bcb, root_bcb))
+        }) {
+            Some(&root_bcb) => root_bcb,
+            None => continue,
+        };
+
+        let mut arm_edges = vec![Vec::new(); arms.len()];
+        for &test_bcb in &tests {
+            for &target_bcb in &basic_coverage_blocks.successors[test_bcb] {
+                // Blocks without a `CoverageSpan` (such as the blocks shared by the alternatives of
+                // an or-pattern, or the blocks continuing with the next test) are followed to the
+                // arm or test they lead to.
+                let mut arm_bcb = target_bcb;
+                for _ in 0..basic_coverage_blocks.num_nodes() {
+                    match basic_coverage_blocks.successors[arm_bcb][..] {
+                        [successor]
+                            if !tests.contains(&arm_bcb)
+                                && !first_bcb_spans.contains_key(&arm_bcb) =>
+                        {
+                            arm_bcb = successor
+                        }
+                        _ => break,
+                    }
+                }
+                if tests.contains(&arm_bcb) {
+                    continue;
+                }
+                let arm_index = first_bcb_spans.get(&arm_bcb).and_then(|&span| {
+                    arms.iter()
+                        .position(|arm| function_source_span(arm.span, body_span).contains(span))
+                });
+                match arm_index {
+                    Some(arm_index) => arm_edges[arm_index].push((test_bcb, target_bcb)),
+                    // The counts of the arms would not add up, so don't report any of them.
+                    None => continue 'matches,
+                }
+            }
+        }
+
+        if arm_edges.iter().filter(|true_edges| !true_edges.is_empty()).count() < 2 {
+            continue;
+        }
+        for (arm, true_edges) in arms.iter().zip(arm_edges) {
+            if !true_edges.is_empty() {
+                branch_spans.push(BranchSpan {
+                    span: function_source_span(arm.pat.span, body_span),
+                    from_bcb: root_bcb,
+                    true_edges,
+                    false_bcb: None,
+                });
+            }
+        }
+    }
+    branch_spans
+}
+
+/// Maps every temporary to the span of its first assignment, either by a statement or as the
+/// destination of a call.
+fn temp_assignment_spans(mir_body: &mir::Body<'tcx>) -> FxHashMap<Local, Span> {
+    let mut assignment_spans = FxHashMap::default();
+    let mut record = |place: mir::Place<'tcx>, span| {
+        if let Some(local) = place.as_local() {
+            if mir_body.local_kind(local) == LocalKind::Temp {
+                assignment_spans.entry(local).or_insert(span);
+            }
+        }
+    };
+    for data in mir_body.basic_blocks().iter() {
+        for statement in data.statements.iter() {
+            if let StatementKind::Assign(box (place, _)) = statement.kind {
+                record(place, statement.source_info.span);
+            }
+        }
+        if let TerminatorKind::Call { destination: Some((place, _)), .. } = data.terminator().kind {
+            record(place, data.terminator().source_info.span);
+        }
+    }
+    assignment_spans
+}
+
+/// Returns the span of the condition evaluated into the discriminant of a boolean `SwitchInt`.
+///
+/// Conditions (other than the operands of `&&` and `||`) are evaluated into a temporary, so its
+/// assignment has the span of the condition. This includes an `if` on the result of `&&` or `||`,
+/// whose temporary is assigned the span of the entire `&&` or `||` expression.
+fn condition_span(
+    mir_body: &mir::Body<'tcx>,
+    assignment_spans: &FxHashMap<Local, Span>,
+    discr: &Operand<'tcx>,
+) -> Option<Span> {
+    let local = discr.place()?.as_local()?;
+    if mir_body.local_kind(local) != LocalKind::Temp {
+        return None;
+    }
+    assignment_spans.get(&local).copied()
+}
+
 /// Converts the initial set of `CoverageSpan`s (one per MIR `Statement` or `Terminator`) into a
 /// minimal set of `CoverageSpan`s, using the BCB CFG to determine where it is safe and useful to:
 ///
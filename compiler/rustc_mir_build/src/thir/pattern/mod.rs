@@ -851,6 +851,43 @@ fn lower_path(&mut self, qpath: &hir::QPath<'_>, id: hir::HirId, span: Span) ->
         }
     }
 
+    /// Evaluates the inline constant of a `const { ... }` pattern and feeds it to
+    /// `const_to_pat`. Like named constants, inline constants have to be evaluated here, as the
+    /// exhaustiveness checker needs to know their value.
+    fn lower_inline_const(
+        &mut self,
+        anon_const: &'tcx hir::AnonConst,
+        id: hir::HirId,
+        span: Span,
+    ) -> Pat<'tcx> {
+        let anon_const_def_id = self.tcx.hir().local_def_id(anon_const.hir_id);
+        let value = ty::Const::from_anon_const(self.tcx, anon_const_def_id);
+        let mir_structural_match_violation =
+            self.tcx.mir_const_qualif(anon_const_def_id.to_def_id()).custom_eq;
+
+        // Use `Reveal::All` here because patterns are always monomorphic even if their function
+        // isn't.
+        let param_env_reveal_all = self.param_env.with_reveal_all_normalized(self.tcx);
+        let value = value.eval(self.tcx, param_env_reveal_all);
+        match value.val {
+            ty::ConstKind::Value(_) => {
+                self.const_to_pat(value, id, span, mir_structural_match_violation)
+            }
+            ty::ConstKind::Error(_) => Pat { span, ty: value.ty, kind: Box::new(PatKind::Wild) },
+            _ => {
+                // The pattern is lowered both by match checking and by MIR building, only report
+                // the error in the former.
+                let msg = "constant pattern depends on a generic parameter";
+                if self.include_lint_checks {
+                    self.tcx.sess.span_err(span, msg);
+                } else {
+                    self.tcx.sess.delay_span_bug(span, msg);
+                }
+                Pat { span, ty: value.ty, kind: Box::new(PatKind::Wild) }
+            }
+        }
+    }
+
     /// Converts literals, paths and negation of literals to patterns.
     /// The special case for negation exists to allow things like `-128_i8`
     /// which would overflow if we tried to evaluate `128_i8` and then negate
@@ -861,9 +898,7 @@ fn lower_lit(&mut self, expr: &'tcx hir::Expr<'tcx>) -> PatKind<'tcx> {
         } else {
             let (lit, neg) = match expr.kind {
                 hir::ExprKind::ConstBlock(ref anon_const) => {
-                    let anon_const_def_id = self.tcx.hir().local_def_id(anon_const.hir_id);
-                    let value = ty::Const::from_anon_const(self.tcx, anon_const_def_id);
-                    return *self.const_to_pat(value, expr.hir_id, expr.span, false).kind;
+                    return *self.lower_inline_const(anon_const, expr.hir_id, expr.span).kind;
                 }
                 hir::ExprKind::Lit(ref lit) => (lit, false),
                 hir::ExprKind::Unary(hir::UnOp::Neg, ref expr) => {
@@ -95,7 +95,8 @@ pub enum Position {
     ArgumentImplicitlyIs(usize),
     /// The argument is located at a specific index given in the format
     ArgumentIs(usize),
-    /// The argument has a name.
+    /// The argument has a name. The name may be a field access path like `self.id`, which can
+    /// only be captured from the surrounding scope.
     ArgumentNamed(Symbol),
 }
 
@@ -147,7 +148,8 @@ pub enum Flag {
 pub enum Count {
     /// The count is specified explicitly.
     CountIs(usize),
-    /// The count is specified by the argument with the given name.
+    /// The count is specified by the argument with the given name, which may be a field access
+    /// path like `self.width`.
     CountIsName(Symbol),
     /// The count is specified by the argument at the given index.
     CountIsParam(usize),
@@ -494,7 +496,7 @@ fn position(&mut self) -> Option<Position> {
         } else {
             match self.cur.peek() {
                 Some(&(_, c)) if rustc_lexer::is_id_start(c) => {
-                    Some(ArgumentNamed(Symbol::intern(self.word())))
+                    Some(ArgumentNamed(Symbol::intern(self.field_path())))
                 }
 
                 // This is an `ArgumentNext`.
@@ -657,7 +659,7 @@ fn count(&mut self, start: usize) -> (Count, Option<InnerSpan>) {
             }
         } else {
             let tmp = self.cur.clone();
-            let word = self.word();
+            let word = self.field_path();
             if word.is_empty() {
                 self.cur = tmp;
                 (CountImplied, None)
@@ -704,6 +706,67 @@ fn word(&mut self) -> &'a str {
         word
     }
 
+    /// Parses a word followed by any number of field accesses, like `self.config.timeout` or
+    /// `pair.0`, at the current position. Method calls and indexing are rejected, as evaluating
+    /// an argument must not have side effects.
+    fn field_path(&mut self) -> &'a str {
+        let start = match self.cur.peek() {
+            Some(&(pos, _)) => pos,
+            None => return "",
+        };
+        if self.word().is_empty() {
+            return "";
+        }
+        loop {
+            let mut lookahead = self.cur.clone();
+            match (lookahead.next(), lookahead.next()) {
+                (Some((_, '.')), Some((_, c))) if rustc_lexer::is_id_start(c) => {
+                    self.cur.next();
+                    self.word();
+                }
+                (Some((_, '.')), Some((_, c))) if c.is_ascii_digit() => {
+                    self.cur.next();
+                    self.integer();
+                }
+                _ => break,
+            }
+        }
+        let end = self.cur.peek().map_or(self.input.len(), |&(pos, _)| pos);
+        let (description, label, open, close) = match self.cur.peek() {
+            Some((_, '(')) => {
+                ("method calls are not supported in format arguments", "method call", '(', ')')
+            }
+            Some((_, '[')) => {
+                ("indexing is not supported in format arguments", "indexing", '[', ']')
+            }
+            _ => return &self.input[start..end],
+        };
+        // Point at the whole path, up to the matching closing delimiter if there is one.
+        let mut depth = 0;
+        let mut span_end = end + 1;
+        for (i, c) in self.input[end..].char_indices() {
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    span_end = end + i + 1;
+                    break;
+                }
+            } else if c == '}' {
+                break;
+            }
+        }
+        self.err_with_note(
+            description,
+            label,
+            "only variables and their fields can be used as format arguments, consider passing \
+             the expression as an argument instead",
+            self.to_span_index(start).to(self.to_span_index(span_end)),
+        );
+        &self.input[start..end]
+    }
+
     /// Optionally parses an integer at the current position. This doesn't deal
     /// with overflow at all, it's just accumulating digits.
     fn integer(&mut self) -> Option<usize> {
@@ -294,3 +294,51 @@ fn format_mixture() {
         ],
     );
 }
+#[test]
+fn format_field_access() {
+    use rustc_span::{edition, SessionGlobals, SESSION_GLOBALS};
+    SESSION_GLOBALS.set(&SessionGlobals::new(edition::DEFAULT_EDITION), || {
+        same(
+            "{self.id}",
+            &[NextArgument(Argument {
+                position: ArgumentNamed(Symbol::intern("self.id")),
+                format: fmtdflt(),
+            })],
+        );
+        same(
+            "{cfg.timeouts.0:?}",
+            &[NextArgument(Argument {
+                position: ArgumentNamed(Symbol::intern("cfg.timeouts.0")),
+                format: FormatSpec { ty: "?", ..fmtdflt() },
+            })],
+        );
+        same(
+            "{x:cfg.width$.cfg.precision$}",
+            &[NextArgument(Argument {
+                position: ArgumentNamed(Symbol::intern("x")),
+                format: FormatSpec {
+                    width: CountIsName(Symbol::intern("cfg.width")),
+                    precision: CountIsName(Symbol::intern("cfg.precision")),
+                    ..fmtdflt()
+                },
+            })],
+        );
+        musterr("{self.id()}");
+        musterr("{ids[0]}");
+        musterr("{self.}");
+    });
+}
+#[test]
+fn format_field_access_error_span() {
+    use rustc_span::{edition, SessionGlobals, SESSION_GLOBALS};
+    SESSION_GLOBALS.set(&SessionGlobals::new(edition::DEFAULT_EDITION), || {
+        let span_of = |s: &str| {
+            let mut p = Parser::new(s, None, None, false, ParseMode::Format);
+            p.next();
+            p.errors[0].span
+        };
+        assert_eq!(span_of("{self.id()}"), InnerSpan::new(1, 10));
+        assert_eq!(span_of("{ids[v[0]]}"), InnerSpan::new(1, 10));
+        assert_eq!(span_of("{self.id(}"), InnerSpan::new(1, 9));
+    });
+}
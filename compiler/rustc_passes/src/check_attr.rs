@@ -561,6 +561,70 @@ fn check_doc_attrs(&self, attr: &Attribute, hir_id: HirId, target: Target) -> bo
                             );
                             return false;
                         }
+                    } else if meta.has_name(sym::auto_cfg) {
+                        if CRATE_HIR_ID != hir_id {
+                            self.tcx.struct_span_lint_hir(
+                                INVALID_DOC_ATTRIBUTES,
+                                hir_id,
+                                meta.span(),
+                                |lint| {
+                                    lint.build(
+                                        "`#![doc(auto_cfg)]` is only allowed as a crate level \
+                                         attribute",
+                                    )
+                                    .emit();
+                                },
+                            );
+                            return false;
+                        }
+                    } else if meta.has_name(sym::cfg_hide) {
+                        if CRATE_HIR_ID != hir_id {
+                            self.tcx.struct_span_lint_hir(
+                                INVALID_DOC_ATTRIBUTES,
+                                hir_id,
+                                meta.span(),
+                                |lint| {
+                                    lint.build(
+                                        "`#![doc(cfg_hide(...))]` is only allowed as a crate level \
+                                         attribute",
+                                    )
+                                    .emit();
+                                },
+                            );
+                            return false;
+                        }
+                    } else if meta.has_name(sym::hidden_arms) {
+                        if target != Target::MacroDef {
+                            self.tcx.struct_span_lint_hir(
+                                INVALID_DOC_ATTRIBUTES,
+                                hir_id,
+                                meta.span(),
+                                |lint| {
+                                    lint.build(
+                                        "`#[doc(hidden_arms)]` is only allowed on `macro_rules!` \
+                                         definitions",
+                                    )
+                                    .emit();
+                                },
+                            );
+                            return false;
+                        }
+                        if meta.value_str().is_none() {
+                            self.tcx.struct_span_lint_hir(
+                                INVALID_DOC_ATTRIBUTES,
+                                hir_id,
+                                meta.span(),
+                                |lint| {
+                                    lint.build(
+                                        "`#[doc(hidden_arms)]` expects the tokens starting the \
+                                         hidden arms as a string",
+                                    )
+                                    .help("for example, `#[doc(hidden_arms = \"@\")]`")
+                                    .emit();
+                                },
+                            );
+                            return false;
+                        }
                     } else if let Some(i_meta) = meta.meta_item() {
                         if ![
                             sym::cfg,
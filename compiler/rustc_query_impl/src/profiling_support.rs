@@ -236,7 +236,17 @@ fn alloc_self_profile_query_strings_for_query_cache<'tcx, C>(
     C::Key: Debug + Clone,
 {
     tcx.prof.with_profiler(|profiler| {
-        let event_id_builder = profiler.event_id_builder();
+        if profiler.event_recording_enabled() {
+            query_cache.iter_results(|results| {
+                profiler.record_query_names(results.map(|v| v.2.into()), query_name);
+            });
+        }
+
+        // The events are only kept in memory, there are no strings to allocate.
+        let event_id_builder = match profiler.event_id_builder() {
+            Some(event_id_builder) => event_id_builder,
+            None => return,
+        };
 
         // Walk the entire query cache and allocate the appropriate
         // string representations. Each cache entry is uniquely
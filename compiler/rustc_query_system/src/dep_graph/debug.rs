@@ -56,3 +56,36 @@ pub fn test<K: DepKind>(&self, source: &DepNode<K>, target: &DepNode<K>) -> bool
         self.source.test(source) && self.target.test(target)
     }
 }
+
+/// A snapshot of the dependency graph of a compilation session, as written by
+/// `-Z dep-graph-snapshot`.
+#[derive(Encodable)]
+pub struct DepGraphSnapshot {
+    pub nodes: Vec<SnapshotNode>,
+}
+
+#[derive(Encodable)]
+pub struct SnapshotNode {
+    /// The `Debug` representation of the dep-node.
+    pub node: String,
+    pub fingerprint: String,
+    pub status: SnapshotNodeStatus,
+    /// The indices of the nodes this node depends on.
+    pub edges: Vec<usize>,
+    /// For nodes that were re-executed, the dependency that could not be
+    /// marked green, if known. Following these links leads back to the input
+    /// that changed.
+    pub cause: Option<String>,
+}
+
+#[derive(Encodable)]
+pub enum SnapshotNodeStatus {
+    /// The node did not exist in the previous session.
+    New,
+    /// The node was re-executed and its result changed.
+    Red,
+    /// The node was re-executed but its result did not change.
+    LightGreen,
+    /// The node was marked green without being re-executed.
+    DarkGreen,
+}
@@ -19,7 +19,7 @@
 use std::ops::Range;
 use std::sync::atomic::Ordering::Relaxed;
 
-use super::debug::EdgeFilter;
+use super::debug::{DepGraphSnapshot, EdgeFilter, SnapshotNode, SnapshotNodeStatus};
 use super::prev::PreviousDepGraph;
 use super::query::DepGraphQuery;
 use super::serialized::SerializedDepNodeIndex;
@@ -93,6 +93,11 @@ struct DepGraphData<K: DepKind> {
     previous_work_products: FxHashMap<WorkProductId, WorkProduct>,
 
     dep_node_debug: Lock<FxHashMap<DepNode<K>, String>>,
+
+    /// For every node of the previous graph that could not be marked green,
+    /// the dependency that caused it to be re-executed. This is only filled in
+    /// when `-Z dep-graph-snapshot` is enabled.
+    red_causes: Lock<FxHashMap<SerializedDepNodeIndex, SerializedDepNodeIndex>>,
 }
 
 pub fn hash_result<HashCtxt, R>(hcx: &mut HashCtxt, result: &R) -> Option<Fingerprint>
@@ -116,6 +121,7 @@ pub fn new(
             data: Some(Lrc::new(DepGraphData {
                 previous_work_products: prev_work_products,
                 dep_node_debug: Default::default(),
+                red_causes: Default::default(),
                 current: CurrentDepGraph::new(prev_graph_node_count),
                 emitting_diagnostics: Default::default(),
                 emitting_diagnostics_cond_var: Condvar::new(),
@@ -675,6 +681,7 @@ fn try_mark_previous_green<Ctxt: QueryContext<DepKind = K>>(
                         dep_node,
                         data.previous.index_to_node(dep_dep_node_index)
                     );
+                    self.record_red_cause(tcx, data, prev_dep_node_index, dep_dep_node_index);
                     return None;
                 }
                 None => {
@@ -728,6 +735,12 @@ fn try_mark_previous_green<Ctxt: QueryContext<DepKind = K>>(
                                         dependency {:?} was red after forcing",
                                     dep_node, dep_dep_node
                                 );
+                                self.record_red_cause(
+                                    tcx,
+                                    data,
+                                    prev_dep_node_index,
+                                    dep_dep_node_index,
+                                );
                                 return None;
                             }
                             None => {
@@ -763,6 +776,7 @@ fn try_mark_previous_green<Ctxt: QueryContext<DepKind = K>>(
                                 could not be forced",
                             dep_node, dep_dep_node
                         );
+                        self.record_red_cause(tcx, data, prev_dep_node_index, dep_dep_node_index);
                         return None;
                     }
                 }
@@ -807,6 +821,21 @@ fn try_mark_previous_green<Ctxt: QueryContext<DepKind = K>>(
         Some(dep_node_index)
     }
 
+    /// Remembers that the previous node `prev_dep_node_index` had to be
+    /// re-executed because of its dependency `cause`.
+    #[inline]
+    fn record_red_cause<Ctxt: QueryContext<DepKind = K>>(
+        &self,
+        tcx: Ctxt,
+        data: &DepGraphData<K>,
+        prev_dep_node_index: SerializedDepNodeIndex,
+        cause: SerializedDepNodeIndex,
+    ) {
+        if tcx.dep_context().sess().opts.debugging_opts.dep_graph_snapshot.is_some() {
+            data.red_causes.lock().insert(prev_dep_node_index, cause);
+        }
+    }
+
     /// Atomically emits some loaded diagnostics.
     /// This may be called concurrently on multiple threads for the same dep node.
     #[cold]
@@ -910,6 +939,74 @@ pub fn register_reused_dep_nodes<Ctxt: DepContext<DepKind = K>>(&self, tcx: Ctxt
         }
     }
 
+    /// Collects the dependency graph of the current session, along with the
+    /// color of every node and the dependency that caused it to be
+    /// re-executed, for `-Z dep-graph-snapshot`.
+    pub fn snapshot(&self) -> DepGraphSnapshot {
+        let data = self.data.as_ref().unwrap();
+        let previous = &data.previous;
+        let red_causes = data.red_causes.lock();
+
+        // Note locking order: `prev_index_to_index`, then `data`.
+        let prev_index_to_index = data.current.prev_index_to_index.lock();
+        let data = data.current.data.lock();
+
+        let cause_of = |prev_index: SerializedDepNodeIndex| {
+            red_causes.get(&prev_index).map(|&cause| format!("{:?}", previous.index_to_node(cause)))
+        };
+        let edges_of = |edges: &Range<EdgeIndex>| {
+            let edges = &data.unshared_edges.raw[edges.start.index()..edges.end.index()];
+            edges.iter().map(|i| i.index()).collect()
+        };
+
+        let nodes = data
+            .hybrid_indices
+            .iter()
+            .map(|&hybrid_index| match hybrid_index.into() {
+                HybridIndex::New(new_index) => SnapshotNode {
+                    node: format!("{:?}", data.new.nodes[new_index]),
+                    fingerprint: data.new.fingerprints[new_index].to_hex(),
+                    status: SnapshotNodeStatus::New,
+                    edges: edges_of(&data.new.edges[new_index]),
+                    cause: None,
+                },
+                HybridIndex::Red(red_index) => {
+                    let prev_index = data.red.node_indices[red_index];
+                    SnapshotNode {
+                        node: format!("{:?}", previous.index_to_node(prev_index)),
+                        fingerprint: data.red.fingerprints[red_index].to_hex(),
+                        status: SnapshotNodeStatus::Red,
+                        edges: edges_of(&data.red.edges[red_index]),
+                        cause: cause_of(prev_index),
+                    }
+                }
+                HybridIndex::LightGreen(lg_index) => {
+                    let prev_index = data.light_green.node_indices[lg_index];
+                    SnapshotNode {
+                        node: format!("{:?}", previous.index_to_node(prev_index)),
+                        fingerprint: previous.fingerprint_by_index(prev_index).to_hex(),
+                        status: SnapshotNodeStatus::LightGreen,
+                        edges: edges_of(&data.light_green.edges[lg_index]),
+                        cause: cause_of(prev_index),
+                    }
+                }
+                HybridIndex::DarkGreen(prev_index) => SnapshotNode {
+                    node: format!("{:?}", previous.index_to_node(prev_index)),
+                    fingerprint: previous.fingerprint_by_index(prev_index).to_hex(),
+                    status: SnapshotNodeStatus::DarkGreen,
+                    edges: previous
+                        .edge_targets_from(prev_index)
+                        .iter()
+                        .map(|&dst| prev_index_to_index[dst].unwrap().index())
+                        .collect(),
+                    cause: None,
+                },
+            })
+            .collect();
+
+        DepGraphSnapshot { nodes }
+    }
+
     pub fn print_incremental_info(&self) {
         #[derive(Clone)]
         struct Stat<Kind: DepKind> {
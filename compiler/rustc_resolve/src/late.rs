@@ -2385,6 +2385,13 @@ fn resolve_expr(&mut self, expr: &'ast Expr, parent: Option<&'ast Expr>) {
                 self.visit_expr(elem);
                 self.resolve_anon_const(ct, IsRepeatExpr::Yes);
             }
+            ExprKind::ConstBlock(ref ct) => {
+                // Inline constants are type-checked together with their enclosing body, so they
+                // may use its generic parameters, but not its local variables.
+                self.with_constant_rib(IsRepeatExpr::No, true, None, |this| {
+                    visit::walk_anon_const(this, ct);
+                });
+            }
             _ => {
                 visit::walk_expr(self, expr);
             }
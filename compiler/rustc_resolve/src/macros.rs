@@ -425,6 +425,10 @@ fn cfg_accessible(&mut self, expn_id: ExpnId, path: &ast::Path) -> Result<bool,
             .emit();
         Ok(false)
     }
+
+    fn is_registered_tool(&self, tool: Symbol) -> bool {
+        self.registered_tools.contains(&Ident::with_dummy_span(tool))
+    }
 }
 
 impl<'a> Resolver<'a> {
@@ -184,6 +184,34 @@ pub enum MirSpanview {
     Block,
 }
 
+/// The kinds of coverage instrumentation enabled by `-Z coverage-options`, in addition to the
+/// code regions always instrumented by `-Z instrument-coverage`.
+#[derive(Clone, Copy, Default, PartialEq, Hash, Debug)]
+pub struct CoverageOptions {
+    /// `-Z coverage-options=branch`: emit a branch region, counting how often each outcome was
+    /// taken, for every `if` and `while` condition, each operand of `&&` and `||`, and every
+    /// `match` arm. (This does not include MC/DC coverage.)
+    pub branch: bool,
+}
+
+/// The different formats the report written by `-Z dump-mono-stats` can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum MonoStatsFormat {
+    /// `-Z dump-mono-stats-format=json`
+    Json,
+    /// `-Z dump-mono-stats-format=csv`
+    Csv,
+}
+
+impl MonoStatsFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            MonoStatsFormat::Json => "json",
+            MonoStatsFormat::Csv => "csv",
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Hash)]
 pub enum LinkerPluginLto {
     LinkerPlugin(PathBuf),
@@ -744,6 +772,7 @@ impl Options {
     pub fn build_dep_graph(&self) -> bool {
         self.incremental.is_some()
             || self.debugging_opts.dump_dep_graph
+            || self.debugging_opts.dep_graph_snapshot.is_some()
             || self.debugging_opts.query_dep_graph
     }
 
@@ -2317,8 +2346,8 @@ pub fn needs_analysis(&self) -> bool {
 /// how the hash should be calculated when adding a new command-line argument.
 crate mod dep_tracking {
     use super::{
-        CFGuard, CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LtoCli, OptLevel,
-        OutputTypes, Passes, SanitizerSet, SourceFileHashAlgorithm, SwitchWithOptPath,
+        CFGuard, CoverageOptions, CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LtoCli,
+        OptLevel, OutputTypes, Passes, SanitizerSet, SourceFileHashAlgorithm, SwitchWithOptPath,
         SymbolManglingVersion, TrimmedDefPaths,
     };
     use crate::lint;
@@ -2398,6 +2427,7 @@ fn hash(&self, hasher: &mut DefaultHasher, error_format: ErrorOutputType) {
     impl_dep_tracking_hash_via_hash!(NativeLibKind);
     impl_dep_tracking_hash_via_hash!(SanitizerSet);
     impl_dep_tracking_hash_via_hash!(CFGuard);
+    impl_dep_tracking_hash_via_hash!(CoverageOptions);
     impl_dep_tracking_hash_via_hash!(TargetTriple);
     impl_dep_tracking_hash_via_hash!(Edition);
     impl_dep_tracking_hash_via_hash!(LinkerPluginLto);
@@ -262,6 +262,8 @@ mod $mod_desc {
         pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavor::one_of();
         pub const parse_optimization_fuel: &str = "crate=integer";
         pub const parse_mir_spanview: &str = "`statement` (default), `terminator`, or `block`";
+        pub const parse_coverage_options: &str = "comma separated list of coverage kinds: `branch`";
+        pub const parse_mono_stats_format: &str = "either `json` or `csv`";
         pub const parse_unpretty: &str = "`string` or `string=string`";
         pub const parse_treat_err_as_bug: &str = "either no value or a number bigger than 0";
         pub const parse_lto: &str =
@@ -592,6 +594,29 @@ fn parse_mir_spanview(slot: &mut Option<MirSpanview>, v: Option<&str>) -> bool {
             true
         }
 
+        fn parse_coverage_options(slot: &mut CoverageOptions, v: Option<&str>) -> bool {
+            if let Some(v) = v {
+                for s in v.split(',') {
+                    match s {
+                        "branch" => slot.branch = true,
+                        _ => return false,
+                    }
+                }
+                true
+            } else {
+                false
+            }
+        }
+
+        fn parse_mono_stats_format(slot: &mut MonoStatsFormat, v: Option<&str>) -> bool {
+            *slot = match v {
+                Some("json") => MonoStatsFormat::Json,
+                Some("csv") => MonoStatsFormat::Csv,
+                _ => return false,
+            };
+            true
+        }
+
         fn parse_treat_err_as_bug(slot: &mut Option<NonZeroUsize>, v: Option<&str>) -> bool {
             match v {
                 Some(s) => { *slot = s.parse().ok(); slot.is_some() }
@@ -880,6 +905,10 @@ fn parse_split_debuginfo(slot: &mut Option<SplitDebuginfo>, v: Option<&str>) ->
         "the backend to use"),
     combine_cgu: bool = (false, parse_bool, [TRACKED],
         "combine CGUs into a single one"),
+    coverage_options: CoverageOptions = (CoverageOptions::default(), parse_coverage_options,
+        [TRACKED],
+        "additional kinds of coverage instrumentation for `-Z instrument-coverage`: \
+        `branch` (default: none)"),
     crate_attr: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
         "inject the given attribute in the crate"),
     debug_macros: bool = (false, parse_bool, [TRACKED],
@@ -889,6 +918,10 @@ fn parse_split_debuginfo(slot: &mut Option<SplitDebuginfo>, v: Option<&str>) ->
     dep_info_omit_d_target: bool = (false, parse_bool, [TRACKED],
         "in dep-info output, omit targets for tracking dependencies of the dep-info files \
         themselves (default: no)"),
+    dep_graph_snapshot: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a JSON snapshot of the dependency graph to the given file, including the \
+        fingerprint and red/green status of every node and the dependency that caused \
+        re-executed nodes to be re-executed"),
     dep_tasks: bool = (false, parse_bool, [UNTRACKED],
         "print tasks that execute and the color their dep node gets (requires debug build) \
         (default: no)"),
@@ -924,10 +957,20 @@ fn parse_split_debuginfo(slot: &mut Option<SplitDebuginfo>, v: Option<&str>) ->
         computed `block` spans (one span encompassing a block's terminator and \
         all statements). If `-Z instrument-coverage` is also enabled, create \
         an additional `.html` file showing the computed coverage spans."),
+    dump_mono_stats: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "output statistics about monomorphized generic definitions (instantiation \
+        counts, MIR sizes, codegen units and LLVM IR instruction counts) into a \
+        file in the given directory (default: no)"),
+    dump_mono_stats_format: MonoStatsFormat = (MonoStatsFormat::Json, parse_mono_stats_format,
+        [UNTRACKED], "the format of the `-Z dump-mono-stats` report: `json` (default) or `csv`"),
     emit_future_incompat_report: bool = (false, parse_bool, [UNTRACKED],
         "emits a future-incompatibility report for lints (RFC 2834)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
+    expansion_explorer: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write every macro expansion (call site, definition, matched `macro_rules!` arm, output \
+        and nesting depth) as JSON to the given file"),
     fewer_names: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "reduce memory use by retaining fewer names within compilation artifacts (LLVM-IR) \
         (default: no)"),
@@ -950,6 +993,12 @@ fn parse_split_debuginfo(slot: &mut Option<SplitDebuginfo>, v: Option<&str>) ->
         "generate human-readable, predictable names for codegen units (default: no)"),
     identify_regions: bool = (false, parse_bool, [UNTRACKED],
         "display unnamed regions as `'<id>`, using a non-ident unique id (default: no)"),
+    incremental_gc_max_age: Option<usize> = (None, parse_opt_uint, [UNTRACKED],
+        "delete finalized incremental compilation sessions of any crate that are older \
+        than the given number of hours"),
+    incremental_gc_max_size: Option<usize> = (None, parse_opt_uint, [UNTRACKED],
+        "delete the least recently created incremental compilation sessions until the \
+        incremental directory takes up at most the given number of megabytes"),
     incremental_ignore_spans: bool = (false, parse_bool, [UNTRACKED],
         "ignore spans during ICH computation -- used for testing (default: no)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
@@ -986,7 +1035,8 @@ fn parse_split_debuginfo(slot: &mut Option<SplitDebuginfo>, v: Option<&str>) ->
     ls: bool = (false, parse_bool, [UNTRACKED],
         "list the symbols defined by a library crate (default: no)"),
     macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
-        "show macro backtraces (default: no)"),
+        "show macro backtraces, and how far each `macro_rules!` rule got when none of them \
+        matched (default: no)"),
     merge_functions: Option<MergeFunctions> = (None, parse_merge_functions, [TRACKED],
         "control the operation of the MergeFunctions LLVM pass, taking \
         the same values as the target option of the same name"),
@@ -1089,12 +1139,17 @@ fn parse_split_debuginfo(slot: &mut Option<SplitDebuginfo>, v: Option<&str>) ->
     self_profile: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
         parse_switch_with_opt_path, [UNTRACKED],
         "run the self profiler and output the raw event data"),
+    self_profile_chrome_trace: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "run the self profiler and write its events as a Chrome trace to the given file"),
     // keep this in sync with the event filter names in librustc_data_structures/profiling.rs
     self_profile_events: Option<Vec<String>> = (None, parse_opt_comma_list, [UNTRACKED],
         "specify the events recorded by the self profiler;
         for example: `-Z self-profile-events=default,query-keys`
         all options: none, all, default, generic-activity, query-provider, query-cache-hit
                      query-blocked, incr-cache-load, query-keys, function-args, args, llvm"),
+    self_profile_summary: Option<usize> = (None, parse_opt_uint, [UNTRACKED],
+        "run the self profiler and print the given number of queries and activities with the \
+        highest self time, invocation count and cache hits at the end of compilation"),
     share_generics: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "make the current crate share its generic instantiations"),
     show_span: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -1,7 +1,7 @@
 //! Contains `ParseSess` which holds state living beyond what one `Parser` might.
 //! It also serves as an input to the parser itself.
 
-use crate::lint::{BufferedEarlyLint, BuiltinLintDiagnostics, Lint, LintId};
+use crate::lint::{BufferedEarlyLint, BuiltinLintDiagnostics, Level, Lint, LintId};
 use rustc_ast::node_id::NodeId;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::{Lock, Lrc};
@@ -133,6 +133,10 @@ pub struct ParseSess {
     pub reached_eof: Lock<bool>,
     /// Environment variables accessed during the build and their values when they exist.
     pub env_depinfo: Lock<FxHashSet<(Symbol, Option<Symbol>)>>,
+    /// File paths accessed during the build.
+    pub file_depinfo: Lock<FxHashSet<Symbol>>,
+    /// Tool lints emitted by procedural macros, keyed by their full `tool::name` path.
+    pub proc_macro_lints: Lock<FxHashMap<Symbol, &'static Lint>>,
     /// All the type ascriptions expressions that have had a suggestion for likely path typo.
     pub type_ascription_path_suggestions: Lock<FxHashSet<Span>>,
     /// Whether cfg(version) should treat the current release as incomplete
@@ -161,6 +165,8 @@ pub fn with_span_handler(handler: Handler, source_map: Lrc<SourceMap>) -> Self {
             symbol_gallery: SymbolGallery::default(),
             reached_eof: Lock::new(false),
             env_depinfo: Default::default(),
+            file_depinfo: Default::default(),
+            proc_macro_lints: Default::default(),
             type_ascription_path_suggestions: Default::default(),
             assume_incomplete_release: false,
         }
@@ -218,6 +224,28 @@ pub fn buffer_lint_with_diagnostic(
         });
     }
 
+    /// Returns the lint used for diagnostics that procedural macros emit under `name`. Lints are
+    /// interned by name in `proc_macro_lints`: the `Lint` of a name is only allocated (and
+    /// leaked, since lints are `'static`) the first time a macro emits it in this session.
+    /// These lints are not registered with the lint store, so they are only known to the lint
+    /// level machinery through that map.
+    pub fn proc_macro_lint(&self, name: Symbol) -> &'static Lint {
+        let mut proc_macro_lints = self.proc_macro_lints.lock();
+        if let Some(&lint) = proc_macro_lints.get(&name) {
+            return lint;
+        }
+        let lint: &'static Lint = Box::leak(Box::new(Lint {
+            name: Box::leak(name.to_string().into_boxed_str()),
+            default_level: Level::Warn,
+            desc: "lint emitted by a procedural macro",
+            report_in_external_macro: true,
+            is_plugin: true,
+            ..Lint::default_fields_for_macro()
+        }));
+        proc_macro_lints.insert(name, lint);
+        lint
+    }
+
     /// Extend an error with a suggestion to wrap an expression with parentheses to allow the
     /// parser to continue parsing the following operation as part of the same expression.
     pub fn expr_parentheses_needed(
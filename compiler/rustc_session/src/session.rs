@@ -36,7 +36,7 @@
 use std::io::Write;
 use std::num::NonZeroU32;
 use std::ops::{Div, Mul};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -1333,15 +1333,21 @@ pub fn build_session(
         sopts.debugging_opts.diagnostic_handler_flags(can_emit_warnings),
     );
 
-    let self_profiler = if let SwitchWithOptPath::Enabled(ref d) = sopts.debugging_opts.self_profile
-    {
-        let directory =
-            if let Some(ref directory) = d { directory } else { std::path::Path::new(".") };
+    // `-Z self-profile-summary` and `-Z self-profile-chrome-trace` run the self
+    // profiler, but only write the raw event data with `-Z self-profile`.
+    let record_events = sopts.debugging_opts.self_profile_summary.is_some()
+        || sopts.debugging_opts.self_profile_chrome_trace.is_some();
+    let output_directory = match sopts.debugging_opts.self_profile {
+        SwitchWithOptPath::Enabled(ref d) => Some(d.as_deref().unwrap_or(Path::new("."))),
+        SwitchWithOptPath::Disabled => None,
+    };
 
+    let self_profiler = if output_directory.is_some() || record_events {
         let profiler = SelfProfiler::new(
-            directory,
+            output_directory,
             sopts.crate_name.as_deref(),
             &sopts.debugging_opts.self_profile_events,
+            record_events,
         );
         match profiler {
             Ok(profiler) => Some(Arc::new(profiler)),
@@ -300,6 +300,7 @@
         attr_literals,
         attributes,
         augmented_assignments,
+        auto_cfg,
         auto_traits,
         automatically_derived,
         avx512_target_feature,
@@ -346,6 +347,7 @@
         cfg_attr_multi,
         cfg_doctest,
         cfg_eval,
+        cfg_hide,
         cfg_panic,
         cfg_sanitize,
         cfg_target_feature,
@@ -420,6 +422,7 @@
         core_panic_macro,
         cosf32,
         cosf64,
+        count,
         crate_id,
         crate_in_paths,
         crate_local,
@@ -462,6 +465,8 @@
         deref_mut,
         deref_target,
         derive,
+        derive_cmp_attrs,
+        derive_default_enum,
         destructuring_assignment,
         diagnostic,
         direct,
@@ -473,7 +478,10 @@
         div_assign,
         doc,
         doc_alias,
+        doc_auto_cfg,
         doc_cfg,
+        doc_cfg_hide,
+        doc_hidden_arms,
         doc_keyword,
         doc_masked,
         doc_spotlight,
@@ -605,6 +613,7 @@
         hashset_type,
         hexagon_target_feature,
         hidden,
+        hidden_arms,
         homogeneous_aggregate,
         html_favicon_url,
         html_logo_url,
@@ -669,6 +678,7 @@
         lateout,
         lazy_normalization_consts,
         le,
+        length,
         let_chains,
         lhs,
         lib,
@@ -704,6 +714,7 @@
         macro_export,
         macro_lifetime_matcher,
         macro_literal_matcher,
+        macro_metavar_expr,
         macro_reexport,
         macro_use,
         macro_vis_matcher,
@@ -819,6 +830,7 @@
         options,
         or,
         or_patterns,
+        order,
         other,
         out,
         overlapping_marker_traits,
@@ -1111,6 +1123,7 @@
         size_of,
         size_of_val,
         sized,
+        skip,
         slice,
         slice_alloc,
         slice_patterns,
@@ -6,6 +6,7 @@
 use crate::check::cast;
 use crate::check::coercion::CoerceMany;
 use crate::check::fatally_break_rust;
+use crate::check::gather_locals::GatherLocalsVisitor;
 use crate::check::method::{probe, MethodError, SelfSource};
 use crate::check::report_unexpected_variant_res;
 use crate::check::BreakableCtxt;
@@ -30,6 +31,7 @@
 use rustc_hir as hir;
 use rustc_hir::def::{CtorKind, DefKind, Res};
 use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::Visitor;
 use rustc_hir::lang_items::LangItem;
 use rustc_hir::{ExprKind, QPath};
 use rustc_infer::infer;
@@ -288,7 +290,9 @@ fn check_expr_kind(
             }
             ExprKind::DropTemps(e) => self.check_expr_with_expectation(e, expected),
             ExprKind::Array(args) => self.check_expr_array(args, expected, expr),
-            ExprKind::ConstBlock(ref anon_const) => self.to_const(anon_const).ty,
+            ExprKind::ConstBlock(ref anon_const) => {
+                self.check_expr_const_block(anon_const, expected)
+            }
             ExprKind::Repeat(element, ref count) => {
                 self.check_expr_repeat(element, count, expected, expr)
             }
@@ -1082,6 +1086,31 @@ fn check_expr_array(
         self.tcx.mk_array(element_ty, args.len() as u64)
     }
 
+    /// Type-checks the body of an inline `const { ... }` block as part of the enclosing body, so
+    /// that its type can be inferred from the context, e.g. in `[const { Vec::new() }; N]`.
+    fn check_expr_const_block(
+        &self,
+        anon_const: &'tcx hir::AnonConst,
+        expected: Expectation<'tcx>,
+    ) -> Ty<'tcx> {
+        let hir = self.tcx.hir();
+        let body = hir.body(anon_const.body);
+        let def_id = hir.local_def_id(anon_const.hir_id).to_def_id();
+        let outer_hir_id = hir.local_def_id_to_hir_id(
+            crate::check::typeck_root_def_id(self.tcx, def_id).expect_local(),
+        );
+
+        // The block is a separate body, so it gets its own function context (e.g. `return` is
+        // not allowed in it), but shares the inference context with the enclosing body.
+        let fcx = FnCtxt::new(self, self.param_env, body.value.hir_id);
+        GatherLocalsVisitor::new(&fcx, outer_hir_id).visit_body(body);
+
+        let ty = fcx.check_expr_with_expectation(&body.value, expected);
+        fcx.require_type_is_sized(ty, body.value.span, traits::ConstSized);
+        fcx.write_ty(anon_const.hir_id, ty);
+        ty
+    }
+
     fn check_expr_repeat(
         &self,
         element: &'tcx hir::Expr<'tcx>,
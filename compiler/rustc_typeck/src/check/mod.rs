@@ -104,14 +104,13 @@
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{pluralize, struct_span_err, Applicability};
 use rustc_hir as hir;
-use rustc_hir::def::Res;
+use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{CrateNum, DefId, LocalDefId, LOCAL_CRATE};
 use rustc_hir::intravisit::Visitor;
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
 use rustc_hir::{HirIdMap, ImplicitSelfKind, Node};
 use rustc_index::bit_set::BitSet;
 use rustc_index::vec::Idx;
-use rustc_infer::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use rustc_middle::ty::fold::{TypeFoldable, TypeFolder};
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::subst::GenericArgKind;
@@ -311,10 +310,40 @@ fn primary_body_of(
     }
 }
 
+/// Returns `true` if `hir_id` is the anonymous constant of an inline `const { ... }` block.
+crate fn is_inline_const(tcx: TyCtxt<'_>, hir_id: hir::HirId) -> bool {
+    matches!(tcx.hir().find(hir_id), Some(Node::AnonConst(_)))
+        && matches!(
+            tcx.hir().get(tcx.hir().get_parent_node(hir_id)),
+            Node::Expr(&hir::Expr { kind: hir::ExprKind::ConstBlock(ref anon_const), .. })
+                if anon_const.hir_id == hir_id
+        )
+}
+
+/// Returns the definition whose typeck results contain the results of `def_id`.
+///
+/// Closures and inline constants are type-checked together with the body they appear in, as
+/// they are part of the same "inference environment".
+crate fn typeck_root_def_id(tcx: TyCtxt<'_>, def_id: DefId) -> DefId {
+    let mut def_id = tcx.closure_base_def_id(def_id);
+    while let Some(local_def_id) = def_id.as_local() {
+        if tcx.def_kind(def_id) != DefKind::AnonConst {
+            break;
+        }
+        let hir_id = tcx.hir().local_def_id_to_hir_id(local_def_id);
+        if !is_inline_const(tcx, hir_id) {
+            break;
+        }
+        let parent = tcx.hir().local_def_id(tcx.hir().enclosing_body_owner(hir_id));
+        def_id = tcx.closure_base_def_id(parent.to_def_id());
+    }
+    def_id
+}
+
 fn has_typeck_results(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
-    // Closures' typeck results come from their outermost function,
+    // Closures' and inline constants' typeck results come from their outermost function,
     // as they are part of the same "inference environment".
-    let outer_def_id = tcx.closure_base_def_id(def_id);
+    let outer_def_id = typeck_root_def_id(tcx, def_id);
     if outer_def_id != def_id {
         return tcx.has_typeck_results(outer_def_id);
     }
@@ -474,9 +503,9 @@ fn typeck_with_fallback<'tcx>(
     def_id: LocalDefId,
     fallback: impl Fn() -> Ty<'tcx> + 'tcx,
 ) -> &'tcx ty::TypeckResults<'tcx> {
-    // Closures' typeck results come from their outermost function,
+    // Closures' and inline constants' typeck results come from their outermost function,
     // as they are part of the same "inference environment".
-    let outer_def_id = tcx.closure_base_def_id(def_id.to_def_id()).expect_local();
+    let outer_def_id = typeck_root_def_id(tcx, def_id.to_def_id()).expect_local();
     if outer_def_id != def_id {
         return tcx.typeck(outer_def_id);
     }
@@ -530,19 +559,7 @@ fn typeck_with_fallback<'tcx>(
                     hir::TyKind::Infer => Some(AstConv::ast_ty_to_ty(&fcx, ty)),
                     _ => None,
                 })
-                .unwrap_or_else(|| match tcx.hir().get(id) {
-                    Node::AnonConst(_) => match tcx.hir().get(tcx.hir().get_parent_node(id)) {
-                        Node::Expr(&hir::Expr {
-                            kind: hir::ExprKind::ConstBlock(ref anon_const),
-                            ..
-                        }) if anon_const.hir_id == id => fcx.next_ty_var(TypeVariableOrigin {
-                            kind: TypeVariableOriginKind::TypeInference,
-                            span,
-                        }),
-                        _ => fallback(),
-                    },
-                    _ => fallback(),
-                });
+                .unwrap_or_else(fallback);
 
             let expected_type = fcx.normalize_associated_types_in(body.value.span, expected_type);
             fcx.require_type_is_sized(expected_type, body.value.span, traits::ConstSized);
@@ -93,6 +93,12 @@ fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
             self.fcx.analyze_closure(expr.hir_id, expr.span, body, cc);
         }
 
+        // Closures in inline constants are type-checked with the enclosing body.
+        if let hir::ExprKind::ConstBlock(ref anon_const) = expr.kind {
+            let body = self.fcx.tcx.hir().body(anon_const.body);
+            self.visit_body(body);
+        }
+
         intravisit::walk_expr(self, expr);
     }
 }
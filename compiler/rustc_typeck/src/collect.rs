@@ -1340,6 +1340,11 @@ fn generics_of(tcx: TyCtxt<'_>, def_id: DefId) -> ty::Generics {
             let parent_id = tcx.hir().get_parent_item(hir_id);
             Some(tcx.hir().local_def_id(parent_id).to_def_id())
         }
+        // Inline constants are type-checked together with the body they appear in, so they
+        // have to share its generics, just like closures.
+        Node::AnonConst(_) if crate::check::is_inline_const(tcx, hir_id) => {
+            Some(crate::check::typeck_root_def_id(tcx, def_id))
+        }
         // FIXME(#43408) always enable this once `lazy_normalization` is
         // stable enough and does not need a feature gate anymore.
         Node::AnonConst(_) => {
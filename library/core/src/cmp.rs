@@ -216,7 +216,8 @@ fn ne(&self, other: &Rhs) -> bool {
 }
 
 /// Derive macro generating an impl of the trait `PartialEq`.
-#[rustc_builtin_macro]
+#[cfg_attr(not(bootstrap), rustc_builtin_macro(PartialEq, attributes(cmp)))]
+#[cfg_attr(bootstrap, rustc_builtin_macro)]
 #[stable(feature = "builtin_macro_prelude", since = "1.38.0")]
 #[allow_internal_unstable(core_intrinsics, structural_match)]
 pub macro PartialEq($item:item) {
@@ -280,7 +281,8 @@ fn assert_receiver_is_total_eq(&self) {}
 }
 
 /// Derive macro generating an impl of the trait `Eq`.
-#[rustc_builtin_macro]
+#[cfg_attr(not(bootstrap), rustc_builtin_macro(Eq, attributes(cmp)))]
+#[cfg_attr(bootstrap, rustc_builtin_macro)]
 #[stable(feature = "builtin_macro_prelude", since = "1.38.0")]
 #[allow_internal_unstable(core_intrinsics, derive_eq, structural_match)]
 pub macro Eq($item:item) {
@@ -782,7 +784,8 @@ fn clamp(self, min: Self, max: Self) -> Self
 }
 
 /// Derive macro generating an impl of the trait `Ord`.
-#[rustc_builtin_macro]
+#[cfg_attr(not(bootstrap), rustc_builtin_macro(Ord, attributes(cmp)))]
+#[cfg_attr(bootstrap, rustc_builtin_macro)]
 #[stable(feature = "builtin_macro_prelude", since = "1.38.0")]
 #[allow_internal_unstable(core_intrinsics)]
 pub macro Ord($item:item) {
@@ -1023,7 +1026,8 @@ fn ge(&self, other: &Rhs) -> bool {
 }
 
 /// Derive macro generating an impl of the trait `PartialOrd`.
-#[rustc_builtin_macro]
+#[cfg_attr(not(bootstrap), rustc_builtin_macro(PartialOrd, attributes(cmp)))]
+#[cfg_attr(bootstrap, rustc_builtin_macro)]
 #[stable(feature = "builtin_macro_prelude", since = "1.38.0")]
 #[allow_internal_unstable(core_intrinsics)]
 pub macro PartialOrd($item:item) {
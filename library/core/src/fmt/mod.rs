@@ -584,7 +584,8 @@ pub trait Debug {
 // Separate module to reexport the macro `Debug` from prelude without the trait `Debug`.
 pub(crate) mod macros {
     /// Derive macro generating an impl of the trait `Debug`.
-    #[rustc_builtin_macro]
+    #[cfg_attr(not(bootstrap), rustc_builtin_macro(Debug, attributes(cmp)))]
+    #[cfg_attr(bootstrap, rustc_builtin_macro)]
     #[stable(feature = "builtin_macro_prelude", since = "1.38.0")]
     #[allow_internal_unstable(core_intrinsics)]
     pub macro Debug($item:item) {
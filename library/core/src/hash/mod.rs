@@ -194,7 +194,8 @@ fn hash_slice<H: Hasher>(data: &[Self], state: &mut H)
 // Separate module to reexport the macro `Hash` from prelude without the trait `Hash`.
 pub(crate) mod macros {
     /// Derive macro generating an impl of the trait `Hash`.
-    #[rustc_builtin_macro]
+    #[cfg_attr(not(bootstrap), rustc_builtin_macro(Hash, attributes(cmp)))]
+    #[cfg_attr(bootstrap, rustc_builtin_macro)]
     #[stable(feature = "builtin_macro_prelude", since = "1.38.0")]
     #[allow_internal_unstable(core_intrinsics)]
     pub macro Hash($item:item) {
@@ -8,7 +8,7 @@
 
 #![deny(unsafe_code)]
 
-use crate::{Delimiter, Level, LineColumn, Spacing};
+use crate::{Applicability, ConversionErrorKind, Delimiter, Level, LineColumn, Spacing};
 use std::fmt;
 use std::hash::Hash;
 use std::marker;
@@ -55,6 +55,7 @@ macro_rules! with_api {
             FreeFunctions {
                 fn drop($self: $S::FreeFunctions);
                 fn track_env_var(var: &str, value: Option<&str>);
+                fn track_path(path: &str);
             },
             TokenStream {
                 fn drop($self: $S::TokenStream);
@@ -63,6 +64,7 @@ macro_rules! with_api {
                 fn is_empty($self: &$S::TokenStream) -> bool;
                 fn from_str(src: &str) -> $S::TokenStream;
                 fn to_string($self: &$S::TokenStream) -> String;
+                fn expand_expr($self: &$S::TokenStream) -> Result<$S::TokenStream, ()>;
                 fn from_token_tree(
                     tree: TokenTree<$S::Group, $S::Punct, $S::Ident, $S::Literal>,
                 ) -> $S::TokenStream;
@@ -110,6 +112,15 @@ fn next(
                 fn debug_kind($self: &$S::Literal) -> String;
                 fn symbol($self: &$S::Literal) -> String;
                 fn suffix($self: &$S::Literal) -> Option<String>;
+                fn str_value($self: &$S::Literal) -> Result<String, ConversionErrorKind>;
+                fn byte_str_value($self: &$S::Literal) -> Result<Vec<u8>, ConversionErrorKind>;
+                fn char_value($self: &$S::Literal) -> Result<char, ConversionErrorKind>;
+                fn int_value(
+                    $self: &$S::Literal,
+                ) -> Result<(u128, Option<String>), ConversionErrorKind>;
+                fn float_value(
+                    $self: &$S::Literal,
+                ) -> Result<(f64, Option<String>), ConversionErrorKind>;
                 fn integer(n: &str) -> $S::Literal;
                 fn typed_integer(n: &str, kind: &str) -> $S::Literal;
                 fn float(n: &str) -> $S::Literal;
@@ -147,7 +158,15 @@ fn sub(
                     msg: &str,
                     span: $S::MultiSpan,
                 );
+                fn span_label($self: &mut $S::Diagnostic, span: $S::Span, label: &str);
+                fn suggestion(
+                    $self: &mut $S::Diagnostic,
+                    msg: &str,
+                    parts: Vec<($S::Span, String)>,
+                    applicability: Applicability,
+                );
                 fn emit($self: $S::Diagnostic);
+                fn emit_lint($self: $S::Diagnostic, lint: &str);
             },
             Span {
                 fn debug($self: $S::Span) -> String;
@@ -313,6 +332,45 @@ fn unmark(self) -> Self::Unmarked {
     }
 }
 
+impl<T: Mark, E: Mark> Mark for Result<T, E> {
+    type Unmarked = Result<T::Unmarked, E::Unmarked>;
+    fn mark(unmarked: Self::Unmarked) -> Self {
+        unmarked.map(T::mark).map_err(E::mark)
+    }
+}
+impl<T: Unmark, E: Unmark> Unmark for Result<T, E> {
+    type Unmarked = Result<T::Unmarked, E::Unmarked>;
+    fn unmark(self) -> Self::Unmarked {
+        self.map(T::unmark).map_err(E::unmark)
+    }
+}
+
+impl<A: Mark, B: Mark> Mark for Vec<(A, B)> {
+    type Unmarked = Vec<(A::Unmarked, B::Unmarked)>;
+    fn mark(unmarked: Self::Unmarked) -> Self {
+        unmarked.into_iter().map(<(A, B)>::mark).collect()
+    }
+}
+impl<A: Unmark, B: Unmark> Unmark for Vec<(A, B)> {
+    type Unmarked = Vec<(A::Unmarked, B::Unmarked)>;
+    fn unmark(self) -> Self::Unmarked {
+        self.into_iter().map(<(A, B)>::unmark).collect()
+    }
+}
+
+impl<A: Mark, B: Mark> Mark for (A, B) {
+    type Unmarked = (A::Unmarked, B::Unmarked);
+    fn mark(unmarked: Self::Unmarked) -> Self {
+        (A::mark(unmarked.0), B::mark(unmarked.1))
+    }
+}
+impl<A: Unmark, B: Unmark> Unmark for (A, B) {
+    type Unmarked = (A::Unmarked, B::Unmarked);
+    fn unmark(self) -> Self::Unmarked {
+        (self.0.unmark(), self.1.unmark())
+    }
+}
+
 macro_rules! mark_noop {
     ($($ty:ty),* $(,)?) => {
         $(
@@ -335,9 +393,14 @@ fn unmark(self) -> Self::Unmarked {
     (),
     bool,
     char,
+    u128,
+    f64,
     &'a [u8],
     &'a str,
     String,
+    Vec<u8>,
+    Applicability,
+    ConversionErrorKind,
     Delimiter,
     Level,
     LineColumn,
@@ -353,6 +416,14 @@ enum Delimiter {
         None,
     }
 );
+rpc_encode_decode!(
+    enum Applicability {
+        MachineApplicable,
+        MaybeIncorrect,
+        HasPlaceholders,
+        Unspecified,
+    }
+);
 rpc_encode_decode!(
     enum Level {
         Error,
@@ -362,6 +433,14 @@ enum Level {
     }
 );
 rpc_encode_decode!(struct LineColumn { line, column });
+rpc_encode_decode!(
+    enum ConversionErrorKind {
+        InvalidLiteralKind,
+        Malformed,
+        InvalidSuffix,
+        OutOfRange,
+    }
+);
 rpc_encode_decode!(
     enum Spacing {
         Alone,
@@ -127,6 +127,8 @@ fn decode(r: &mut Reader<'_>, _: &mut S) -> Self {
 }
 
 rpc_encode_decode!(le u32);
+rpc_encode_decode!(le u64);
+rpc_encode_decode!(le u128);
 rpc_encode_decode!(le usize);
 
 impl<S> Encode<S> for bool {
@@ -157,6 +159,18 @@ fn decode(r: &mut Reader<'_>, s: &mut S) -> Self {
     }
 }
 
+impl<S> Encode<S> for f64 {
+    fn encode(self, w: &mut Writer, s: &mut S) {
+        self.to_bits().encode(w, s);
+    }
+}
+
+impl<S> DecodeMut<'_, '_, S> for f64 {
+    fn decode(r: &mut Reader<'_>, s: &mut S) -> Self {
+        f64::from_bits(u64::decode(r, s))
+    }
+}
+
 impl<S> Encode<S> for NonZeroU32 {
     fn encode(self, w: &mut Writer, s: &mut S) {
         self.get().encode(w, s);
@@ -246,6 +260,40 @@ fn decode(r: &mut Reader<'_>, s: &mut S) -> Self {
     }
 }
 
+impl<S> Encode<S> for Vec<u8> {
+    fn encode(self, w: &mut Writer, s: &mut S) {
+        self[..].encode(w, s);
+    }
+}
+
+impl<S> DecodeMut<'_, '_, S> for Vec<u8> {
+    fn decode(r: &mut Reader<'_>, s: &mut S) -> Self {
+        <&[u8]>::decode(r, s).to_vec()
+    }
+}
+
+impl<S, A: Encode<S>, B: Encode<S>> Encode<S> for Vec<(A, B)> {
+    fn encode(self, w: &mut Writer, s: &mut S) {
+        self.len().encode(w, s);
+        for x in self {
+            x.encode(w, s);
+        }
+    }
+}
+
+impl<S, A: for<'s> DecodeMut<'a, 's, S>, B: for<'s> DecodeMut<'a, 's, S>> DecodeMut<'a, '_, S>
+    for Vec<(A, B)>
+{
+    fn decode(r: &mut Reader<'a>, s: &mut S) -> Self {
+        let len = usize::decode(r, s);
+        let mut vec = Vec::with_capacity(len);
+        for _ in 0..len {
+            vec.push(<(A, B)>::decode(r, s));
+        }
+        vec
+    }
+}
+
 /// Simplied version of panic payloads, ignoring
 /// types other than `&'static str` and `String`.
 pub enum PanicMessage {
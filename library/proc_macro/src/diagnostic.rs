@@ -15,6 +15,24 @@ pub enum Level {
     Help,
 }
 
+/// Indicates the confidence in the correctness of a suggestion, which tools like `cargo fix`
+/// use to decide whether to apply it automatically.
+#[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended, and can be applied automatically.
+    MachineApplicable,
+    /// The suggestion may be what the user intended, but it is uncertain. The suggestion
+    /// should result in valid Rust code if it is applied.
+    MaybeIncorrect,
+    /// The suggestion contains placeholders like `(...)` or `{ /* fields */ }`. The suggestion
+    /// cannot be applied automatically because it will not result in valid Rust code.
+    HasPlaceholders,
+    /// The applicability of the suggestion is unknown.
+    Unspecified,
+}
+
 /// Trait implemented by types that can be converted into a set of `Span`s.
 #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
 pub trait MultiSpan {
@@ -51,7 +69,17 @@ pub struct Diagnostic {
     level: Level,
     message: String,
     spans: Vec<Span>,
+    labels: Vec<(Span, String)>,
     children: Vec<Diagnostic>,
+    suggestions: Vec<Suggestion>,
+}
+
+/// A suggested change to the source code, made of one or more replacements.
+#[derive(Clone, Debug)]
+struct Suggestion {
+    message: String,
+    parts: Vec<(Span, String)>,
+    applicability: Applicability,
 }
 
 macro_rules! diagnostic_child_methods {
@@ -98,7 +126,7 @@ impl Diagnostic {
     /// Creates a new diagnostic with the given `level` and `message`.
     #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
     pub fn new<T: Into<String>>(level: Level, message: T) -> Diagnostic {
-        Diagnostic { level, message: message.into(), spans: vec![], children: vec![] }
+        Diagnostic::spanned(vec![], level, message)
     }
 
     /// Creates a new diagnostic with the given `level` and `message` pointing to
@@ -109,7 +137,14 @@ pub fn spanned<S, T>(spans: S, level: Level, message: T) -> Diagnostic
         S: MultiSpan,
         T: Into<String>,
     {
-        Diagnostic { level, message: message.into(), spans: spans.into_spans(), children: vec![] }
+        Diagnostic {
+            level,
+            message: message.into(),
+            spans: spans.into_spans(),
+            labels: vec![],
+            children: vec![],
+            suggestions: vec![],
+        }
     }
 
     diagnostic_child_methods!(span_error, error, Level::Error);
@@ -117,6 +152,46 @@ pub fn spanned<S, T>(spans: S, level: Level, message: T) -> Diagnostic
     diagnostic_child_methods!(span_note, note, Level::Note);
     diagnostic_child_methods!(span_help, help, Level::Help);
 
+    /// Adds a label to `span`, which is shown with the diagnostic whether or not `span` is one
+    /// of the spans of `self`.
+    #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+    pub fn span_label<T: Into<String>>(mut self, span: Span, label: T) -> Diagnostic {
+        self.labels.push((span, label.into()));
+        self
+    }
+
+    /// Suggests replacing the source code of `span` with `suggestion`.
+    ///
+    /// With `Applicability::MachineApplicable`, tools like `cargo fix` and IDEs apply the
+    /// suggestion without asking.
+    #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+    pub fn span_suggestion<T, U>(
+        self,
+        span: Span,
+        message: T,
+        suggestion: U,
+        applicability: Applicability,
+    ) -> Diagnostic
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        self.multipart_suggestion(message, vec![(span, suggestion.into())], applicability)
+    }
+
+    /// Suggests replacing the source code of several spans at once, e.g. adding both the
+    /// opening and the closing delimiter around an expression.
+    #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+    pub fn multipart_suggestion<T: Into<String>>(
+        mut self,
+        message: T,
+        parts: Vec<(Span, String)>,
+        applicability: Applicability,
+    ) -> Diagnostic {
+        self.suggestions.push(Suggestion { message: message.into(), parts, applicability });
+        self
+    }
+
     /// Returns the diagnostic `level` for `self`.
     #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
     pub fn level(&self) -> Level {
@@ -162,6 +237,21 @@ pub fn children(&self) -> Children<'_> {
     /// Emit the diagnostic.
     #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
     pub fn emit(self) {
+        self.to_internal().emit();
+    }
+
+    /// Emit the diagnostic as a lint named `lint`, which must be scoped by a tool registered
+    /// with `#![register_tool]` in the crate using the macro, e.g. `my_derive::missing_docs`.
+    ///
+    /// The lint is a warning by default, the level of `self` is ignored. It obeys
+    /// `#[allow]`, `#[warn]`, `#[deny]` and `#[forbid]` attributes naming it, and children
+    /// other than help messages are shown as notes.
+    #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+    pub fn emit_lint(self, lint: &str) {
+        self.to_internal().emit_lint(lint);
+    }
+
+    fn to_internal(self) -> crate::bridge::client::Diagnostic {
         fn to_internal(spans: Vec<Span>) -> crate::bridge::client::MultiSpan {
             let mut multi_span = crate::bridge::client::MultiSpan::new();
             for span in spans {
@@ -175,9 +265,16 @@ fn to_internal(spans: Vec<Span>) -> crate::bridge::client::MultiSpan {
             &self.message[..],
             to_internal(self.spans),
         );
+        for (span, label) in self.labels {
+            diag.span_label(span.0, &label[..]);
+        }
         for c in self.children {
             diag.sub(c.level, &c.message[..], to_internal(c.spans));
         }
-        diag.emit();
+        for s in self.suggestions {
+            let parts = s.parts.into_iter().map(|(span, snippet)| (span.0, snippet)).collect();
+            diag.suggestion(&s.message[..], parts, s.applicability);
+        }
+        diag
     }
 }
@@ -41,7 +41,7 @@
 mod diagnostic;
 
 #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
-pub use diagnostic::{Diagnostic, Level, MultiSpan};
+pub use diagnostic::{Applicability, Diagnostic, Level, MultiSpan};
 
 use std::cmp::Ordering;
 use std::ops::{Bound, RangeBounds};
@@ -105,6 +105,28 @@ impl !Send for LexError {}
 #[stable(feature = "proc_macro_lib", since = "1.15.0")]
 impl !Sync for LexError {}
 
+/// Error returned from `TokenStream::expand_expr`.
+#[unstable(feature = "proc_macro_expand", issue = "none")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ExpandError;
+
+#[unstable(feature = "proc_macro_expand", issue = "none")]
+impl fmt::Display for ExpandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("macro expansion failed")
+    }
+}
+
+#[unstable(feature = "proc_macro_expand", issue = "none")]
+impl error::Error for ExpandError {}
+
+#[unstable(feature = "proc_macro_expand", issue = "none")]
+impl !Send for ExpandError {}
+
+#[unstable(feature = "proc_macro_expand", issue = "none")]
+impl !Sync for ExpandError {}
+
 impl TokenStream {
     /// Returns an empty `TokenStream` containing no token trees.
     #[stable(feature = "proc_macro_lib2", since = "1.29.0")]
@@ -117,6 +139,24 @@ pub fn new() -> TokenStream {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Parses this `TokenStream` as an expression and eagerly expands any macros within it,
+    /// e.g. `concat!`, `env!` or `include_str!`. Returns the expanded `TokenStream`.
+    ///
+    /// Currently only expressions expanding to literals succeed, although this may be
+    /// relaxed in the future.
+    ///
+    /// NOTE: In error conditions, `expand_expr` may leave macros unexpanded, report an
+    /// error (failing compilation), and/or return an `Err(..)`. The specific behavior for
+    /// any error condition, and what conditions are considered errors, is unspecified and
+    /// may change in the future.
+    #[unstable(feature = "proc_macro_expand", issue = "none")]
+    pub fn expand_expr(&self) -> Result<TokenStream, ExpandError> {
+        match self.0.expand_expr() {
+            Ok(stream) => Ok(TokenStream(stream)),
+            Err(()) => Err(ExpandError),
+        }
+    }
 }
 
 /// Attempts to break the string into tokens and parse those tokens into a token stream.
@@ -1118,6 +1158,44 @@ pub fn byte_string(bytes: &[u8]) -> Literal {
         Literal(bridge::client::Literal::byte_string(bytes))
     }
 
+    /// Returns the unescaped value of a string literal, either cooked (`"..."`) or raw
+    /// (`r"..."`).
+    #[unstable(feature = "proc_macro_value", issue = "none")]
+    pub fn str_value(&self) -> Result<String, ConversionErrorKind> {
+        self.0.str_value()
+    }
+
+    /// Returns the unescaped value of a byte string literal, either cooked (`b"..."`) or raw
+    /// (`br"..."`).
+    #[unstable(feature = "proc_macro_value", issue = "none")]
+    pub fn byte_str_value(&self) -> Result<Vec<u8>, ConversionErrorKind> {
+        self.0.byte_str_value()
+    }
+
+    /// Returns the unescaped value of a character literal (`'...'`).
+    #[unstable(feature = "proc_macro_value", issue = "none")]
+    pub fn char_value(&self) -> Result<char, ConversionErrorKind> {
+        self.0.char_value()
+    }
+
+    /// Returns the value of an integer literal in any base, together with its suffix, if any.
+    ///
+    /// For example, `0x1F_u8` produces `(31, Some("u8"))`. Negative literals, which can only
+    /// be created by `Literal` constructors such as `Literal::i32_suffixed(-1)`, are rejected
+    /// as `ConversionErrorKind::InvalidLiteralKind`.
+    #[unstable(feature = "proc_macro_value", issue = "none")]
+    pub fn int_value(&self) -> Result<(u128, Option<String>), ConversionErrorKind> {
+        self.0.int_value()
+    }
+
+    /// Returns the value of a floating point literal, together with its suffix, if any.
+    ///
+    /// Integer literals with a float suffix, like `1f32`, are floating point literals too.
+    #[unstable(feature = "proc_macro_value", issue = "none")]
+    pub fn float_value(&self) -> Result<(f64, Option<String>), ConversionErrorKind> {
+        self.0.float_value()
+    }
+
     /// Returns the span encompassing this literal.
     #[stable(feature = "proc_macro_lib2", since = "1.29.0")]
     pub fn span(&self) -> Span {
@@ -1181,6 +1259,36 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// Error returned from the value accessors of `Literal`, such as `Literal::str_value`.
+#[unstable(feature = "proc_macro_value", issue = "none")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConversionErrorKind {
+    /// The literal is not of the requested kind, e.g. `str_value` was called on an integer.
+    InvalidLiteralKind,
+    /// The literal could not be decoded, e.g. because of an invalid escape sequence.
+    Malformed,
+    /// The literal has a suffix that is not valid for its kind.
+    InvalidSuffix,
+    /// The value of an integer literal does not fit in a `u128`.
+    OutOfRange,
+}
+
+#[unstable(feature = "proc_macro_value", issue = "none")]
+impl fmt::Display for ConversionErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ConversionErrorKind::InvalidLiteralKind => "literal is not of the requested kind",
+            ConversionErrorKind::Malformed => "literal could not be decoded",
+            ConversionErrorKind::InvalidSuffix => "literal has an invalid suffix",
+            ConversionErrorKind::OutOfRange => "integer literal is too large",
+        })
+    }
+}
+
+#[unstable(feature = "proc_macro_value", issue = "none")]
+impl error::Error for ConversionErrorKind {}
+
 /// Tracked access to environment variables.
 #[unstable(feature = "proc_macro_tracked_env", issue = "74690")]
 pub mod tracked_env {
@@ -1200,3 +1308,22 @@ pub fn var<K: AsRef<OsStr> + AsRef<str>>(key: K) -> Result<String, VarError> {
         value
     }
 }
+
+/// Tracked access to additional files.
+#[unstable(feature = "proc_macro_tracked_path", issue = "none")]
+pub mod tracked_path {
+    /// Track a file explicitly and add it to build dependency info.
+    /// Build system executing the compiler will know that the file was accessed during
+    /// compilation, and will be able to rerun the build when the contents of that file change.
+    /// Relative paths are resolved against the working directory of the compiler, not against
+    /// the source file invoking the macro.
+    ///
+    /// The contents of the file are also recorded as an input of incremental compilation, so
+    /// that changing the file invalidates the previous session's results that depend on the
+    /// crate hash.
+    #[unstable(feature = "proc_macro_tracked_path", issue = "none")]
+    pub fn path<P: AsRef<str>>(path: P) {
+        let path: &str = path.as_ref();
+        crate::bridge::client::FreeFunctions::track_path(path);
+    }
+}
@@ -421,6 +421,7 @@ macro_rules! describe {
                 test::Miri,
                 test::Clippy,
                 test::CompiletestTest,
+                test::DepGraphExplain,
                 test::RustdocJSStd,
                 test::RustdocJSNotStd,
                 test::RustdocGUI,
@@ -503,6 +503,42 @@ fn run(self, builder: &Builder<'_>) {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DepGraphExplain {
+    host: TargetSelection,
+}
+
+impl Step for DepGraphExplain {
+    type Output = ();
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("src/tools/dep-graph-explain")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(DepGraphExplain { host: run.target });
+    }
+
+    /// Runs `cargo test` for dep-graph-explain.
+    fn run(self, builder: &Builder<'_>) {
+        let host = self.host;
+        let compiler = builder.compiler(0, host);
+
+        let cargo = tool::prepare_tool_cargo(
+            builder,
+            compiler,
+            Mode::ToolBootstrap,
+            host,
+            "test",
+            "src/tools/dep-graph-explain",
+            SourceType::InTree,
+            &[],
+        );
+
+        try_run(builder, &mut cargo.into());
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Clippy {
     stage: u32,
@@ -1093,6 +1129,7 @@ fn run(self, builder: &Builder<'_>) {
 
         if mode == "run-make" && suite.ends_with("fulldeps") {
             cmd.arg("--rust-demangler-path").arg(builder.tool_exe(Tool::RustDemangler));
+            cmd.arg("--dep-graph-explain-path").arg(builder.tool_exe(Tool::DepGraphExplain));
         }
 
         cmd.arg("--src-base").arg(builder.src.join("src/test").join(suite));
@@ -1971,6 +2008,8 @@ fn run(self, builder: &Builder<'_>) {
         }
 
         cargo.arg("-p").arg("rustdoc-json-types");
+        // Checks that `schema.json` is up to date.
+        cargo.arg("--features").arg("schema");
 
         cargo.arg("--");
         cargo.args(&builder.config.cmd.test_args());
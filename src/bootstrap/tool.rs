@@ -374,6 +374,7 @@ fn run(self, builder: &Builder<'_>) -> PathBuf {
     ExpandYamlAnchors, "src/tools/expand-yaml-anchors", "expand-yaml-anchors";
     LintDocs, "src/tools/lint-docs", "lint-docs";
     JsonDocCk, "src/tools/jsondocck", "jsondocck";
+    DepGraphExplain, "src/tools/dep-graph-explain", "dep-graph-explain";
 );
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Ord, PartialOrd)]
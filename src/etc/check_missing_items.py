@@ -19,10 +19,18 @@ def get_local_item(item_id):
     sys.exit(1)
 
 
+local_crate = crate["index"][crate["root"]]["name"]
+
+
+# IDs are made of a namespace and a path starting with the name of the crate of the item
+def is_local(item_id):
+    return item_id.split(":", 1)[1].split("::", 1)[0] == local_crate
+
+
 # local IDs have to be in `index`, external ones can sometimes be in `index` but otherwise have
 # to be in `paths`
 def valid_id(item_id):
-    return item_id in crate["index"] or item_id[0] != "0" and item_id in crate["paths"]
+    return item_id in crate["index"] or not is_local(item_id) and item_id in crate["paths"]
 
 
 def check_generics(generics):
@@ -8,6 +8,7 @@
 use std::ops;
 
 use rustc_ast::{LitKind, MetaItem, MetaItemKind, NestedMetaItem};
+use rustc_data_structures::fx::FxHashSet;
 use rustc_feature::Features;
 use rustc_session::parse::ParseSess;
 use rustc_span::symbol::{sym, Symbol};
@@ -43,7 +44,7 @@
 
 impl Cfg {
     /// Parses a `NestedMetaItem` into a `Cfg`.
-    fn parse_nested(nested_cfg: &NestedMetaItem) -> Result<Cfg, InvalidCfgError> {
+    crate fn parse_nested(nested_cfg: &NestedMetaItem) -> Result<Cfg, InvalidCfgError> {
         match nested_cfg {
             NestedMetaItem::MetaItem(ref cfg) => Cfg::parse(cfg),
             NestedMetaItem::Literal(ref lit) => {
@@ -224,6 +225,28 @@ pub(crate) fn simplify_with(&self, assume: &Cfg) -> Option<Cfg> {
 
         Some(self.clone())
     }
+
+    /// Removes the options in `hidden` from this cfg, as if they were neither true nor false,
+    /// will return `None` if nothing is left.
+    ///
+    /// See `tests::test_strip_hidden` for examples.
+    crate fn strip_hidden(&self, hidden: &FxHashSet<Cfg>) -> Option<Cfg> {
+        match self {
+            Cfg::True | Cfg::False => Some(self.clone()),
+            Cfg::Cfg(..) => {
+                if hidden.contains(self) { None } else { Some(self.clone()) }
+            }
+            Cfg::Not(cfg) => cfg.strip_hidden(hidden).map(|cfg| !cfg),
+            Cfg::All(sub_cfgs) => sub_cfgs
+                .iter()
+                .filter_map(|cfg| cfg.strip_hidden(hidden))
+                .fold(None, |all, cfg| Some(all.unwrap_or(Cfg::True) & cfg)),
+            Cfg::Any(sub_cfgs) => sub_cfgs
+                .iter()
+                .filter_map(|cfg| cfg.strip_hidden(hidden))
+                .fold(None, |any, cfg| Some(any.unwrap_or(Cfg::False) | cfg)),
+        }
+    }
 }
 
 impl ops::Not for Cfg {
@@ -473,3 +473,32 @@ fn test_simplify_with() {
         assert_eq!(foobar.simplify_with(&foobarbaz), None);
     });
 }
+
+#[test]
+fn test_strip_hidden() {
+    with_default_session_globals(|| {
+        let doc = word_cfg("doc");
+        let test = word_cfg("test");
+        let unix = word_cfg("unix");
+        let feature = name_value_cfg("feature", "foo");
+        let hidden = vec![doc.clone(), test.clone()].into_iter().collect();
+
+        // Visible cfgs are kept
+        assert_eq!(unix.strip_hidden(&hidden).as_ref(), Some(&unix));
+        let unix_feature = unix.clone() & feature.clone();
+        assert_eq!(unix_feature.strip_hidden(&hidden).as_ref(), Some(&unix_feature));
+
+        // Hidden cfgs are removed entirely
+        assert_eq!(doc.strip_hidden(&hidden), None);
+        assert_eq!((!test.clone()).strip_hidden(&hidden), None);
+        assert_eq!((doc.clone() | test.clone()).strip_hidden(&hidden), None);
+
+        // Hidden cfgs are removed from `all` and `any`
+        assert_eq!((doc.clone() | unix.clone()).strip_hidden(&hidden).as_ref(), Some(&unix));
+        assert_eq!(
+            (unix.clone() & !test.clone() & feature.clone()).strip_hidden(&hidden).as_ref(),
+            Some(&unix_feature)
+        );
+        assert_eq!((!(test.clone() | unix.clone())).strip_hidden(&hidden), Some(!unix.clone()));
+    });
+}
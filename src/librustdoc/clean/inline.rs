@@ -521,13 +521,19 @@ fn build_static(cx: &mut DocContext<'_>, did: DefId, mutable: bool) -> clean::St
 fn build_macro(cx: &mut DocContext<'_>, did: DefId, name: Symbol) -> clean::ItemKind {
     let imported_from = cx.tcx.original_crate_name(did.krate);
     match cx.enter_resolver(|r| r.cstore().load_macro_untracked(did, cx.sess())) {
-        LoadedMacro::MacroDef(def, _) => {
-            let matchers: Vec<Span> = if let ast::ItemKind::MacroDef(ref def) = def.kind {
-                let tts: Vec<_> = def.body.inner_tokens().into_trees().collect();
-                tts.chunks(4).map(|arm| arm[0].span()).collect()
+        LoadedMacro::MacroDef(def, edition) => {
+            let tts: Vec<_> = if let ast::ItemKind::MacroDef(ref def) = def.kind {
+                def.body.inner_tokens().into_trees().collect()
             } else {
                 unreachable!()
             };
+            let hidden_prefix = clean::macros::hidden_arms_prefix(&def.attrs);
+            let matchers: Vec<Span> = tts
+                .chunks(4)
+                .map(|arm| &arm[0])
+                .filter(|matcher| !clean::macros::is_hidden_arm(matcher, hidden_prefix))
+                .map(|matcher| matcher.span())
+                .collect();
 
             let source = format!(
                 "macro_rules! {} {{\n{}}}",
@@ -538,7 +544,17 @@ fn build_macro(cx: &mut DocContext<'_>, did: DefId, name: Symbol) -> clean::Item
                     .collect::<String>()
             );
 
-            clean::MacroItem(clean::Macro { source, imported_from: Some(imported_from) })
+            let arms = clean::macros::parse_arms(&tts, hidden_prefix);
+            let doc = def.attrs.clean(cx).collapsed_doc_value().unwrap_or_default();
+            let expansions =
+                clean::macros::expand_examples(cx.sess(), &def, edition, &doc, imported_from);
+
+            clean::MacroItem(clean::Macro {
+                source,
+                imported_from: Some(imported_from),
+                arms,
+                expansions,
+            })
         }
         LoadedMacro::ProcMacro(ext) => clean::ProcMacroItem(clean::ProcMacro {
             kind: ext.macro_kind(),
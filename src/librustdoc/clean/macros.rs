@@ -0,0 +1,248 @@
+//! Parsing of the arms of declarative macros, used to render their grammar, and expansion of the
+//! examples of their documentation marked `expand`.
+//!
+//! Examples are expanded with the matcher and transcriber of the compiler, so an invocation uses
+//! the arm it uses when the example is compiled. Only one step of the expansion is done: the
+//! invocations in the expansion are left as they are.
+
+use rustc_ast as ast;
+use rustc_ast::token::{self, DelimToken, Token};
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_ast_pretty::pprust;
+use rustc_data_structures::sync::Lrc;
+use rustc_expand::base::{SyntaxExtensionKind, TTMacroExpander};
+use rustc_expand::compile_declarative_macro;
+use rustc_session::parse::ParseSess;
+use rustc_session::Session;
+use rustc_span::edition::Edition;
+use rustc_span::symbol::{kw, sym, Symbol};
+use rustc_span::FileName;
+
+use crate::clean::AttributesExt;
+use crate::doctest::Tester;
+use crate::html::markdown::{find_testable_code, ErrorCodes, LangString};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Debug)]
+crate enum MatcherNode {
+    Token(Token),
+    Delimited(DelimToken, Vec<MatcherNode>),
+    /// `$name:fragment`, without the fragment in invalid matchers.
+    MetaVar { name: Symbol, fragment: Option<Symbol> },
+    /// `$(...) separator kleene_op`.
+    Repetition { nodes: Vec<MatcherNode>, separator: Option<Token>, kleene_op: Token },
+}
+
+#[derive(Clone, Debug)]
+crate struct MacroArm {
+    crate matcher: Vec<MatcherNode>,
+    /// Whether the matcher starts with the prefix given by `#[doc(hidden_arms = "...")]`.
+    crate hidden: bool,
+}
+
+/// An invocation of a macro found in an example marked `expand`, with what it expands to.
+#[derive(Clone, Debug)]
+crate struct MacroExpansion {
+    crate invocation: String,
+    crate expansion: String,
+}
+
+/// Returns the prefix of the internal arms of a macro, given by `#[doc(hidden_arms = "...")]`.
+crate fn hidden_arms_prefix(attrs: &[ast::Attribute]) -> Option<Symbol> {
+    attrs
+        .lists(sym::doc)
+        .find(|item| item.has_name(sym::hidden_arms))
+        .and_then(|item| item.value_str())
+}
+
+/// Whether the matcher of an arm starts with `hidden_prefix`, ignoring whitespace.
+crate fn is_hidden_arm(matcher: &TokenTree, hidden_prefix: Option<Symbol>) -> bool {
+    match (matcher, hidden_prefix) {
+        (TokenTree::Delimited(_, _, matcher), Some(prefix)) => {
+            let prefix = prefix.as_str().split_whitespace().collect::<String>();
+            let source = pprust::tts_to_string(matcher);
+            source.split_whitespace().collect::<String>().starts_with(&prefix)
+        }
+        _ => false,
+    }
+}
+
+/// Parses the arms of a macro from the token trees of its body.
+crate fn parse_arms(body: &[TokenTree], hidden_prefix: Option<Symbol>) -> Vec<MacroArm> {
+    body.chunks(4)
+        .filter_map(|arm| match &arm[0] {
+            TokenTree::Delimited(_, _, matcher) => Some(MacroArm {
+                matcher: parse_nodes(&matcher.trees().collect::<Vec<_>>()),
+                hidden: is_hidden_arm(&arm[0], hidden_prefix),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_nodes(trees: &[TokenTree]) -> Vec<MatcherNode> {
+    let mut nodes = Vec::new();
+    let mut i = 0;
+    while i < trees.len() {
+        match &trees[i] {
+            TokenTree::Token(Token { kind: token::Dollar, .. }) => match trees.get(i + 1) {
+                Some(TokenTree::Token(Token { kind: token::Ident(name, _), .. })) => {
+                    let fragment = match (trees.get(i + 2), trees.get(i + 3)) {
+                        (
+                            Some(TokenTree::Token(Token { kind: token::Colon, .. })),
+                            Some(TokenTree::Token(Token { kind: token::Ident(fragment, _), .. })),
+                        ) => Some(*fragment),
+                        _ => None,
+                    };
+                    nodes.push(MatcherNode::MetaVar { name: *name, fragment });
+                    i += if fragment.is_some() { 4 } else { 2 };
+                }
+                Some(TokenTree::Delimited(_, DelimToken::Paren, inner)) => {
+                    let nodes_inner = parse_nodes(&inner.trees().collect::<Vec<_>>());
+                    let (separator, kleene_op) = match (trees.get(i + 2), trees.get(i + 3)) {
+                        (Some(TokenTree::Token(op)), _) if is_kleene_op(op) => (None, op.clone()),
+                        (Some(TokenTree::Token(sep)), Some(TokenTree::Token(op)))
+                            if is_kleene_op(op) =>
+                        {
+                            (Some(sep.clone()), op.clone())
+                        }
+                        // Not a valid repetition, so the macro doesn't compile anyway.
+                        _ => return nodes,
+                    };
+                    nodes.push(MatcherNode::Repetition {
+                        nodes: nodes_inner,
+                        separator: separator.clone(),
+                        kleene_op,
+                    });
+                    i += if separator.is_some() { 4 } else { 3 };
+                }
+                _ => {
+                    nodes.push(MatcherNode::Token(Token::new(token::Dollar, trees[i].span())));
+                    i += 1;
+                }
+            },
+            TokenTree::Token(token) => {
+                nodes.push(MatcherNode::Token(token.clone()));
+                i += 1;
+            }
+            TokenTree::Delimited(_, delim, inner) => {
+                nodes.push(MatcherNode::Delimited(
+                    *delim,
+                    parse_nodes(&inner.trees().collect::<Vec<_>>()),
+                ));
+                i += 1;
+            }
+        }
+    }
+    nodes
+}
+
+fn is_kleene_op(token: &Token) -> bool {
+    matches!(token.kind, token::BinOp(token::Star) | token::BinOp(token::Plus) | token::Question)
+}
+
+/// Replaces `$crate` with the name of the crate defining the macro.
+fn replace_dollar_crate(stream: TokenStream, crate_name: Symbol) -> TokenStream {
+    stream
+        .into_trees()
+        .map(|tree| match tree {
+            TokenTree::Token(Token { kind: token::Ident(kw::DollarCrate, _), span }) => {
+                TokenTree::token(token::Ident(crate_name, false), span)
+            }
+            TokenTree::Delimited(span, delim, inner) => {
+                TokenTree::Delimited(span, delim, replace_dollar_crate(inner, crate_name))
+            }
+            tree => tree,
+        })
+        .collect()
+}
+
+/// Finds the invocations of the macro `name` in `trees`, and pushes their expansions.
+fn expand_invocations(
+    sess: &ParseSess,
+    name: Symbol,
+    expander: &dyn TTMacroExpander,
+    trees: &[TokenTree],
+    crate_name: Symbol,
+    expansions: &mut Vec<MacroExpansion>,
+) {
+    let mut i = 0;
+    while i < trees.len() {
+        if let (
+            TokenTree::Token(Token { kind: token::Ident(ident, false), .. }),
+            Some(TokenTree::Token(Token { kind: token::Not, .. })),
+            Some(TokenTree::Delimited(_, _, input)),
+        ) = (&trees[i], trees.get(i + 1), trees.get(i + 2))
+        {
+            if *ident == name {
+                let args = pprust::tt_to_string(&trees[i + 2]);
+                if let Some(expansion) = expander.expand_tokens(sess, input.clone()) {
+                    let expansion = replace_dollar_crate(expansion, crate_name);
+                    expansions.push(MacroExpansion {
+                        invocation: format!("{}!{}", name, args),
+                        expansion: pprust::tts_to_string(&expansion),
+                    });
+                }
+                i += 3;
+                continue;
+            }
+        }
+        if let TokenTree::Delimited(_, _, inner) = &trees[i] {
+            let inner = inner.trees().collect::<Vec<_>>();
+            expand_invocations(sess, name, expander, &inner, crate_name, expansions);
+        }
+        i += 1;
+    }
+}
+
+struct ExpandedExamples(Vec<String>);
+
+impl Tester for ExpandedExamples {
+    fn add_test(&mut self, test: String, config: LangString, _: usize) {
+        if config.expand {
+            self.0.push(test);
+        }
+    }
+}
+
+/// Expands the invocations of the macro `def` in the code examples of `doc` marked `expand`.
+/// Examples are only expanded with `#![feature(doc_hidden_arms)]`.
+crate fn expand_examples(
+    sess: &Session,
+    def: &ast::Item,
+    edition: Edition,
+    doc: &str,
+    crate_name: Symbol,
+) -> Vec<MacroExpansion> {
+    if !sess.features_untracked().doc_hidden_arms {
+        return Vec::new();
+    }
+    let mut examples = ExpandedExamples(Vec::new());
+    find_testable_code(doc, &mut examples, ErrorCodes::No, false, None);
+    if examples.0.is_empty() {
+        return Vec::new();
+    }
+
+    let ext = compile_declarative_macro(sess, sess.features_untracked(), def, edition);
+    let expander = match ext.kind {
+        SyntaxExtensionKind::LegacyBang(ref expander) => expander,
+        _ => return Vec::new(),
+    };
+    let mut expansions = Vec::new();
+    for example in examples.0 {
+        // Errors in the example are reported when it is tested, so don't emit them here.
+        let sess = ParseSess::with_silent_emitter();
+        let file = sess.source_map().new_source_file(FileName::Custom("example".into()), example);
+        let trees = rustc_driver::catch_fatal_errors(|| {
+            rustc_parse::maybe_file_to_stream(&sess, Lrc::clone(&file), None)
+        });
+        if let Ok(Ok((stream, _))) = trees {
+            let trees = stream.trees().collect::<Vec<_>>();
+            let name = def.ident.name;
+            expand_invocations(&sess, name, &**expander, &trees, crate_name, &mut expansions);
+        }
+    }
+    expansions
+}
@@ -0,0 +1,30 @@
+use super::*;
+
+use rustc_span::with_default_session_globals;
+
+fn trees(source: &str) -> Vec<TokenTree> {
+    let sess = ParseSess::with_silent_emitter();
+    let file = sess.source_map().new_source_file(FileName::Custom("test".into()), source.into());
+    let (stream, _) = rustc_parse::maybe_file_to_stream(&sess, file, None).unwrap();
+    stream.trees().collect()
+}
+
+#[test]
+fn arms() {
+    with_default_session_globals(|| {
+        let body = "(@inner $x:tt) => { inner($x) }; ($($x:expr),*) => { $(m!(@inner $x);)* };";
+        let arms = parse_arms(&trees(body), Some(Symbol::intern("@")));
+        assert_eq!(arms.iter().map(|arm| arm.hidden).collect::<Vec<_>>(), [true, false]);
+
+        match &arms[1].matcher[..] {
+            [MatcherNode::Repetition { nodes, separator: Some(separator), .. }] => {
+                assert!(matches!(
+                    nodes[..],
+                    [MatcherNode::MetaVar { fragment: Some(sym::expr), .. }]
+                ));
+                assert_eq!(separator.kind, token::Comma);
+            }
+            matcher => panic!("unexpected matcher: {:?}", matcher),
+        }
+    })
+}
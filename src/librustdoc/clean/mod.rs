@@ -5,6 +5,7 @@
 mod blanket_impl;
 crate mod cfg;
 crate mod inline;
+crate mod macros;
 mod simplify;
 crate mod types;
 crate mod utils;
@@ -2276,8 +2277,16 @@ fn clean(&self, cx: &mut DocContext<'_>) -> Item {
         let (item, renamed) = self;
         let name = renamed.unwrap_or(item.ident.name);
         let tts = item.ast.body.inner_tokens().trees().collect::<Vec<_>>();
-        // Extract the spans of all matchers. They represent the "interface" of the macro.
-        let matchers = tts.chunks(4).map(|arm| arm[0].span()).collect::<Vec<_>>();
+        let attrs = cx.tcx.hir().attrs(item.hir_id());
+        let hidden_prefix = macros::hidden_arms_prefix(attrs);
+        // Extract the spans of all matchers, except the hidden ones. They represent the
+        // "interface" of the macro.
+        let matchers = tts
+            .chunks(4)
+            .map(|arm| &arm[0])
+            .filter(|matcher| !macros::is_hidden_arm(matcher, hidden_prefix))
+            .map(|matcher| matcher.span())
+            .collect::<Vec<_>>();
         let source = if item.ast.macro_rules {
             format!(
                 "macro_rules! {} {{\n{}}}",
@@ -2311,10 +2320,33 @@ fn clean(&self, cx: &mut DocContext<'_>) -> Item {
             }
         };
 
+        let arms = macros::parse_arms(&tts, hidden_prefix);
+        let doc = attrs.clean(cx).collapsed_doc_value().unwrap_or_default();
+        let def = ast::Item {
+            attrs: attrs.to_vec(),
+            id: ast::DUMMY_NODE_ID,
+            span: item.span,
+            vis: ast::Visibility {
+                kind: ast::VisibilityKind::Inherited,
+                span: item.span.shrink_to_lo(),
+                tokens: None,
+            },
+            ident: item.ident,
+            kind: ast::ItemKind::MacroDef(item.ast.clone()),
+            tokens: None,
+        };
+        let expansions = macros::expand_examples(
+            cx.sess(),
+            &def,
+            item.span.edition(),
+            &doc,
+            cx.tcx.crate_name(LOCAL_CRATE),
+        );
+
         Item::from_hir_id_and_parts(
             item.hir_id(),
             Some(name),
-            MacroItem(Macro { source, imported_from: None }),
+            MacroItem(Macro { source, imported_from: None, arms, expansions }),
             cx,
         )
     }
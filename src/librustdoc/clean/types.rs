@@ -33,6 +33,7 @@
 use crate::clean::cfg::Cfg;
 use crate::clean::external_path;
 use crate::clean::inline;
+use crate::clean::macros::{MacroArm, MacroExpansion};
 use crate::clean::types::Type::{QPath, ResolvedPath};
 use crate::clean::Clean;
 use crate::core::DocContext;
@@ -2020,6 +2021,10 @@ impl Import {
 crate struct Macro {
     crate source: String,
     crate imported_from: Option<Symbol>,
+    /// All the arms of the macro, including the hidden ones, which are needed to expand it.
+    crate arms: Vec<MacroArm>,
+    /// The invocations of the macro in the examples of its documentation marked `expand`.
+    crate expansions: Vec<MacroExpansion>,
 }
 
 #[derive(Clone, Debug)]
@@ -25,6 +25,7 @@
 use crate::html::static_files;
 use crate::opts;
 use crate::passes::{self, Condition, DefaultPassOption};
+use crate::scrape_examples::{self, AllCallLocations, ScrapeExamplesOptions};
 use crate::theme;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -124,6 +125,9 @@ fn try_from(value: &str) -> Result<Self, Self::Error> {
     /// The path to a rustc-like binary to build tests with. If not set, we
     /// default to loading from `$sysroot/bin/rustc`.
     crate test_builder: Option<PathBuf>,
+    /// Whether to compile the doctests that allow it together, into a single test executable,
+    /// instead of compiling each one on its own.
+    crate merge_doctests: bool,
 
     // Options that affect the documentation process
     /// The selected default set of passes to use.
@@ -153,6 +157,12 @@ fn try_from(value: &str) -> Result<Self, Self::Error> {
     /// If this option is set to `true`, rustdoc will only run checks and not generate
     /// documentation.
     crate run_check: bool,
+    /// If present, rustdoc collects the calls to the given crates as examples instead of
+    /// generating documentation.
+    crate scrape_examples_options: Option<ScrapeExamplesOptions>,
+    /// If present, the input is a JSON file written by `--output-format json`, whose public API
+    /// is compared to the one in this file instead of generating documentation.
+    crate api_diff_baseline: Option<PathBuf>,
 }
 
 impl fmt::Debug for Options {
@@ -194,7 +204,10 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("runtool", &self.runtool)
             .field("runtool_args", &self.runtool_args)
             .field("enable-per-target-ignores", &self.enable_per_target_ignores)
+            .field("merge_doctests", &self.merge_doctests)
             .field("run_check", &self.run_check)
+            .field("scrape_examples_options", &self.scrape_examples_options)
+            .field("api_diff_baseline", &self.api_diff_baseline)
             .finish()
     }
 }
@@ -266,6 +279,12 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     /// If `true`, generate a JSON file in the crate folder instead of HTML redirection files.
     crate generate_redirect_map: bool,
     crate unstable_features: rustc_feature::UnstableFeatures,
+    /// Calls to the documented functions found by previous runs with
+    /// `--scrape-examples-output-path`, shown as examples under their documentation.
+    crate call_locations: AllCallLocations,
+    /// Whether to write a full-text index of the documentation of items, used by the search in
+    /// addition to their names.
+    crate full_text_search: bool,
 }
 
 impl Options {
@@ -576,11 +595,21 @@ fn println_condition(condition: Condition) {
         let runtool = matches.opt_str("runtool");
         let runtool_args = matches.opt_strs("runtool-arg");
         let enable_per_target_ignores = matches.opt_present("enable-per-target-ignores");
-        let document_private = matches.opt_present("document-private-items");
+        let merge_doctests = matches.opt_present("merge-doctests");
         let document_hidden = matches.opt_present("document-hidden-items");
         let run_check = matches.opt_present("check");
         let generate_redirect_map = matches.opt_present("generate-redirect-map");
 
+        let scrape_examples_options = ScrapeExamplesOptions::new(&matches, &diag)?;
+        // The scraped calls link to the source pages of the files they are in, which are only
+        // generated for files containing documented items, and examples are mostly private.
+        let document_private =
+            matches.opt_present("document-private-items") || scrape_examples_options.is_some();
+        let with_examples = matches.opt_strs("with-examples");
+        let call_locations = scrape_examples::load_call_locations(with_examples, &diag)?;
+        let api_diff_baseline = matches.opt_str("api-diff-baseline").map(PathBuf::from);
+        let full_text_search = matches.opt_present("full-text-search");
+
         let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
 
         Ok(Options {
@@ -615,7 +644,10 @@ fn println_condition(condition: Condition) {
             runtool_args,
             enable_per_target_ignores,
             test_builder,
+            merge_doctests,
             run_check,
+            scrape_examples_options,
+            api_diff_baseline,
             render_options: RenderOptions {
                 output,
                 external_html,
@@ -641,6 +673,8 @@ fn println_condition(condition: Condition) {
                 unstable_features: rustc_feature::UnstableFeatures::from_environment(
                     crate_name.as_deref(),
                 ),
+                call_locations,
+                full_text_search,
             },
             crate_name,
             output_format,
@@ -451,7 +451,11 @@ impl<'tcx> DocContext<'tcx> {
             .filter(|trait_def_id| tcx.trait_is_auto(*trait_def_id))
             .collect(),
         module_trait_cache: FxHashMap::default(),
-        cache: Cache::new(access_levels, render_options.document_private),
+        cache: Cache::new(
+            access_levels,
+            render_options.document_private,
+            render_options.full_text_search,
+        ),
         inlined: FxHashSet::default(),
         output_format,
         render_options,
@@ -15,14 +15,16 @@
 use rustc_span::symbol::sym;
 use rustc_span::{BytePos, FileName, Pos, Span, DUMMY_SP};
 use rustc_target::spec::TargetTriple;
+use serde::Serialize;
 use tempfile::Builder as TempFileBuilder;
 
 use std::env;
 use std::io::{self, Write};
 use std::panic;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
+use std::sync::{Arc, Mutex};
 
 use crate::clean::Attributes;
 use crate::config::Options;
@@ -101,7 +103,7 @@
         registry: rustc_driver::diagnostics_registry(),
     };
 
-    let mut test_args = options.test_args.clone();
+    let test_args = libtest_args(&options);
     let display_warnings = options.display_warnings;
 
     let tests = interface::run_compiler(config, |compiler| {
@@ -159,8 +161,6 @@
         Err(ErrorReported) => return Err(ErrorReported),
     };
 
-    test_args.insert(0, "rustdoctest".to_string());
-
     testing::test_main(
         &test_args,
         tests,
@@ -170,6 +170,18 @@
     Ok(())
 }
 
+/// Returns the arguments to run libtest with, switching it to its JSON output when the doctests are
+/// reported as JSON.
+crate fn libtest_args(options: &Options) -> Vec<String> {
+    let mut args = vec!["rustdoctest".to_string()];
+    args.extend(options.test_args.iter().cloned());
+    if options.output_format.is_json() {
+        args.push("--format=json".to_string());
+        args.push("-Zunstable-options".to_string());
+    }
+    args
+}
+
 // Look for `#![doc(test(no_crate_inject))]`, used by crates in the std facade.
 fn scrape_test_config(attrs: &[ast::Attribute]) -> TestOptions {
     use rustc_ast_pretty::pprust;
@@ -220,6 +232,38 @@ enum TestFailure {
     UnexpectedRunPass,
 }
 
+impl TestFailure {
+    /// Whether the test failed while being compiled or while being run.
+    fn stage(&self) -> &'static str {
+        match self {
+            TestFailure::CompileError
+            | TestFailure::UnexpectedCompilePass
+            | TestFailure::MissingErrorCodes(_) => "compile",
+            TestFailure::ExecutionError(_)
+            | TestFailure::ExecutionFailure(_)
+            | TestFailure::UnexpectedRunPass => "run",
+        }
+    }
+}
+
+/// The result of a doctest, printed as a line of JSON with `--output-format json`, next to the
+/// events printed by libtest.
+#[derive(Serialize)]
+struct DoctestEvent<'a> {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    event: &'static str,
+    name: &'a str,
+    /// The path of the documented item, or the headers above the test in a Markdown file.
+    item: &'a str,
+    file: &'a str,
+    /// The lines of the opening and closing fences of the code block.
+    line: usize,
+    end_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure: Option<&'static str>,
+}
+
 enum DirState {
     Temp(tempfile::TempDir),
     Perm(PathBuf),
@@ -258,66 +302,17 @@ fn run_test(
 
     let output_file = outdir.path().join("rust_out");
 
-    let rustc_binary = options
-        .test_builder
-        .as_deref()
-        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
-    let mut compiler = Command::new(&rustc_binary);
+    let mut compiler = rustc_command(&options, edition, target, supports_color);
     compiler.arg("--crate-type").arg("bin");
-    for cfg in &options.cfgs {
-        compiler.arg("--cfg").arg(&cfg);
-    }
-    if let Some(sysroot) = options.maybe_sysroot {
-        compiler.arg("--sysroot").arg(sysroot);
-    }
-    compiler.arg("--edition").arg(&edition.to_string());
     compiler.env("UNSTABLE_RUSTDOC_TEST_PATH", path);
     compiler.env("UNSTABLE_RUSTDOC_TEST_LINE", format!("{}", line as isize - line_offset as isize));
     compiler.arg("-o").arg(&output_file);
     if as_test_harness {
         compiler.arg("--test");
     }
-    for lib_str in &options.lib_strs {
-        compiler.arg("-L").arg(&lib_str);
-    }
-    for extern_str in &options.extern_strs {
-        compiler.arg("--extern").arg(&extern_str);
-    }
-    compiler.arg("-Ccodegen-units=1");
-    for codegen_options_str in &options.codegen_options_strs {
-        compiler.arg("-C").arg(&codegen_options_str);
-    }
-    for debugging_option_str in &options.debugging_opts_strs {
-        compiler.arg("-Z").arg(&debugging_option_str);
-    }
     if no_run && !compile_fail {
         compiler.arg("--emit=metadata");
     }
-    compiler.arg("--target").arg(match target {
-        TargetTriple::TargetTriple(s) => s,
-        TargetTriple::TargetPath(path) => {
-            path.to_str().expect("target path must be valid unicode").to_string()
-        }
-    });
-    if let ErrorOutputType::HumanReadable(kind) = options.error_format {
-        let (short, color_config) = kind.unzip();
-
-        if short {
-            compiler.arg("--error-format").arg("short");
-        }
-
-        match color_config {
-            ColorConfig::Never => {
-                compiler.arg("--color").arg("never");
-            }
-            ColorConfig::Always => {
-                compiler.arg("--color").arg("always");
-            }
-            ColorConfig::Auto => {
-                compiler.arg("--color").arg(if supports_color { "always" } else { "never" });
-            }
-        }
-    }
 
     compiler.arg("-");
     compiler.stdin(Stdio::piped());
@@ -364,6 +359,85 @@ fn drop(&mut self) {
         return Ok(());
     }
 
+    run_executable(
+        &output_file,
+        &[],
+        should_panic,
+        runtool,
+        runtool_args,
+        options.test_run_directory,
+    )
+}
+
+/// Creates the command compiling doctests, with the arguments shared by all of them.
+fn rustc_command(
+    options: &Options,
+    edition: Edition,
+    target: TargetTriple,
+    supports_color: bool,
+) -> Command {
+    let rustc_binary = options
+        .test_builder
+        .as_deref()
+        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
+    let mut compiler = Command::new(&rustc_binary);
+    for cfg in &options.cfgs {
+        compiler.arg("--cfg").arg(&cfg);
+    }
+    if let Some(sysroot) = &options.maybe_sysroot {
+        compiler.arg("--sysroot").arg(sysroot);
+    }
+    compiler.arg("--edition").arg(&edition.to_string());
+    for lib_str in &options.lib_strs {
+        compiler.arg("-L").arg(&lib_str);
+    }
+    for extern_str in &options.extern_strs {
+        compiler.arg("--extern").arg(&extern_str);
+    }
+    compiler.arg("-Ccodegen-units=1");
+    for codegen_options_str in &options.codegen_options_strs {
+        compiler.arg("-C").arg(&codegen_options_str);
+    }
+    for debugging_option_str in &options.debugging_opts_strs {
+        compiler.arg("-Z").arg(&debugging_option_str);
+    }
+    compiler.arg("--target").arg(match target {
+        TargetTriple::TargetTriple(s) => s,
+        TargetTriple::TargetPath(path) => {
+            path.to_str().expect("target path must be valid unicode").to_string()
+        }
+    });
+    if let ErrorOutputType::HumanReadable(kind) = options.error_format {
+        let (short, color_config) = kind.unzip();
+
+        if short {
+            compiler.arg("--error-format").arg("short");
+        }
+
+        match color_config {
+            ColorConfig::Never => {
+                compiler.arg("--color").arg("never");
+            }
+            ColorConfig::Always => {
+                compiler.arg("--color").arg("always");
+            }
+            ColorConfig::Auto => {
+                compiler.arg("--color").arg(if supports_color { "always" } else { "never" });
+            }
+        }
+    }
+    compiler
+}
+
+/// Runs a compiled doctest, passing `args` to it, and checks its exit status.
+fn run_executable(
+    output_file: &Path,
+    args: &[&str],
+    should_panic: bool,
+    runtool: Option<String>,
+    runtool_args: Vec<String>,
+    run_directory: Option<PathBuf>,
+) -> Result<(), TestFailure> {
     // Run the code!
     let mut cmd;
 
@@ -374,7 +448,8 @@ fn drop(&mut self) {
     } else {
         cmd = Command::new(output_file);
     }
-    if let Some(run_directory) = options.test_run_directory {
+    cmd.args(args);
+    if let Some(run_directory) = run_directory {
         cmd.current_dir(run_directory);
     }
 
@@ -406,7 +481,6 @@ fn drop(&mut self) {
     let everything_else = everything_else.trim();
     let mut line_offset = 0;
     let mut prog = String::new();
-    let mut supports_color = false;
 
     if opts.attrs.is_empty() && !opts.display_warnings {
         // If there aren't any attributes supplied by #![doc(test(attr(...)))], then allow some
@@ -428,8 +502,91 @@ fn drop(&mut self) {
     prog.push_str(&crate_attrs);
     prog.push_str(&crates);
 
+    let (already_has_main, already_has_extern_crate, supports_color) =
+        match check_for_main_and_extern_crate(s, &crates, everything_else, cratename, edition) {
+            Ok(result) => result,
+            Err(ErrorReported) => {
+                // If the parser panicked due to a fatal error, pass the test code through
+                // unchanged. The error will be reported during compilation.
+                return (s.to_owned(), 0, false);
+            }
+        };
+
+    // Don't inject `extern crate std` because it's already injected by the
+    // compiler.
+    if !already_has_extern_crate && !opts.no_crate_inject && cratename != Some("std") {
+        if let Some(cratename) = cratename {
+            // Don't inject `extern crate` if the crate is never used.
+            // NOTE: this is terribly inaccurate because it doesn't actually
+            // parse the source, but only has false positives, not false
+            // negatives.
+            if s.contains(cratename) {
+                prog.push_str(&format!("extern crate r#{};\n", cratename));
+                line_offset += 1;
+            }
+        }
+    }
+
+    // FIXME: This code cannot yet handle no_std test cases yet
+    if dont_insert_main || already_has_main || prog.contains("![no_std]") {
+        prog.push_str(everything_else);
+    } else {
+        let returns_result = everything_else.trim_end().ends_with("(())");
+        // Give each doctest main function a unique name.
+        // This is for example needed for the tooling around `-Z instrument-coverage`.
+        let inner_fn_name = if let Some(test_id) = test_id {
+            format!("_doctest_main_{}", test_id)
+        } else {
+            "_inner".into()
+        };
+        let inner_attr = if test_id.is_some() { "#[allow(non_snake_case)] " } else { "" };
+        let (main_pre, main_post) = if returns_result {
+            (
+                format!(
+                    "fn main() {{ {}fn {}() -> Result<(), impl core::fmt::Debug> {{\n",
+                    inner_attr, inner_fn_name
+                ),
+                format!("\n}} {}().unwrap() }}", inner_fn_name),
+            )
+        } else if test_id.is_some() {
+            (
+                format!("fn main() {{ {}fn {}() {{\n", inner_attr, inner_fn_name),
+                format!("\n}} {}() }}", inner_fn_name),
+            )
+        } else {
+            ("fn main() {\n".into(), "\n}".into())
+        };
+        // Note on newlines: We insert a line/newline *before*, and *after*
+        // the doctest and adjust the `line_offset` accordingly.
+        // In the case of `-Z instrument-coverage`, this means that the generated
+        // inner `main` function spans from the doctest opening codeblock to the
+        // closing one. For example
+        // /// ``` <- start of the inner main
+        // /// <- code under doctest
+        // /// ``` <- end of the inner main
+        line_offset += 1;
+
+        prog.extend([&main_pre, everything_else, &main_post].iter().cloned());
+    }
+
+    debug!("final doctest:\n{}", prog);
+
+    (prog, line_offset, supports_color)
+}
+
+/// Parses the doctest `s`, split by `partition_source` into `crates` and `everything_else`, and
+/// returns whether it already has a `main` function and an `extern crate` of `cratename`, as well
+/// as if the output stream supports colors or not.
+fn check_for_main_and_extern_crate(
+    s: &str,
+    crates: &str,
+    everything_else: &str,
+    cratename: Option<&str>,
+    edition: Edition,
+) -> Result<(bool, bool, bool), ErrorReported> {
     // Uses librustc_ast to parse the doctest and find if there's a main fn and the extern
     // crate already is included.
+    let mut supports_color = false;
     let result = rustc_driver::catch_fatal_errors(|| {
         rustc_span::with_session_globals(edition, || {
             use rustc_errors::emitter::{Emitter, EmitterWriter};
@@ -440,7 +597,7 @@ fn drop(&mut self) {
             use rustc_span::source_map::FilePathMapping;
 
             let filename = FileName::anon_source_code(s);
-            let source = crates + everything_else;
+            let source = crates.to_owned() + everything_else;
 
             // Any errors in parsing should also appear when the doctest is compiled for real, so just
             // send all the errors that librustc_ast emits directly into a `Sink` instead of stderr.
@@ -522,14 +679,7 @@ fn drop(&mut self) {
             (found_main, found_extern_crate, found_macro)
         })
     });
-    let (already_has_main, already_has_extern_crate, found_macro) = match result {
-        Ok(result) => result,
-        Err(ErrorReported) => {
-            // If the parser panicked due to a fatal error, pass the test code through unchanged.
-            // The error will be reported during compilation.
-            return (s.to_owned(), 0, false);
-        }
-    };
+    let (already_has_main, already_has_extern_crate, found_macro) = result?;
 
     // If a doctest's `fn main` is being masked by a wrapper macro, the parsing loop above won't
     // see it. In that case, run the old text-based scan to see if they at least have a main
@@ -546,66 +696,7 @@ fn drop(&mut self) {
         already_has_main
     };
 
-    // Don't inject `extern crate std` because it's already injected by the
-    // compiler.
-    if !already_has_extern_crate && !opts.no_crate_inject && cratename != Some("std") {
-        if let Some(cratename) = cratename {
-            // Don't inject `extern crate` if the crate is never used.
-            // NOTE: this is terribly inaccurate because it doesn't actually
-            // parse the source, but only has false positives, not false
-            // negatives.
-            if s.contains(cratename) {
-                prog.push_str(&format!("extern crate r#{};\n", cratename));
-                line_offset += 1;
-            }
-        }
-    }
-
-    // FIXME: This code cannot yet handle no_std test cases yet
-    if dont_insert_main || already_has_main || prog.contains("![no_std]") {
-        prog.push_str(everything_else);
-    } else {
-        let returns_result = everything_else.trim_end().ends_with("(())");
-        // Give each doctest main function a unique name.
-        // This is for example needed for the tooling around `-Z instrument-coverage`.
-        let inner_fn_name = if let Some(test_id) = test_id {
-            format!("_doctest_main_{}", test_id)
-        } else {
-            "_inner".into()
-        };
-        let inner_attr = if test_id.is_some() { "#[allow(non_snake_case)] " } else { "" };
-        let (main_pre, main_post) = if returns_result {
-            (
-                format!(
-                    "fn main() {{ {}fn {}() -> Result<(), impl core::fmt::Debug> {{\n",
-                    inner_attr, inner_fn_name
-                ),
-                format!("\n}} {}().unwrap() }}", inner_fn_name),
-            )
-        } else if test_id.is_some() {
-            (
-                format!("fn main() {{ {}fn {}() {{\n", inner_attr, inner_fn_name),
-                format!("\n}} {}() }}", inner_fn_name),
-            )
-        } else {
-            ("fn main() {\n".into(), "\n}".into())
-        };
-        // Note on newlines: We insert a line/newline *before*, and *after*
-        // the doctest and adjust the `line_offset` accordingly.
-        // In the case of `-Z instrument-coverage`, this means that the generated
-        // inner `main` function spans from the doctest opening codeblock to the
-        // closing one. For example
-        // /// ``` <- start of the inner main
-        // /// <- code under doctest
-        // /// ``` <- end of the inner main
-        line_offset += 1;
-
-        prog.extend([&main_pre, everything_else, &main_post].iter().cloned());
-    }
-
-    debug!("final doctest:\n{}", prog);
-
-    (prog, line_offset, supports_color)
+    Ok((already_has_main, already_has_extern_crate, supports_color))
 }
 
 // FIXME(aburka): use a real parser to deal with multiline attributes
@@ -678,6 +769,142 @@ enum PartitionState {
     (before, after, crates)
 }
 
+/// Wraps a doctest into a function named `fn_name`, to be compiled into a merged doctest
+/// executable. Returns `None` if the doctest can't be compiled along with other ones,
+/// because it has crate attributes, imports macros from another crate or has its own `main`.
+fn make_merged_test(s: &str, cratename: &str, edition: Edition, fn_name: &str) -> Option<String> {
+    let (crate_attrs, everything_else, crates) = partition_source(s);
+    let has_crate_attrs = crate_attrs.lines().any(|line| line.trim().starts_with("#!["));
+    if has_crate_attrs || crates.contains("macro_use") {
+        return None;
+    }
+    let everything_else = everything_else.trim();
+    let (already_has_main, _, _) =
+        check_for_main_and_extern_crate(s, &crates, everything_else, Some(cratename), edition)
+            .ok()?;
+    if already_has_main {
+        return None;
+    }
+
+    let mut prog = format!("#[allow(non_snake_case)]\nfn {}() {{\n", fn_name);
+    if everything_else.trim_end().ends_with("(())") {
+        prog.push_str("fn _inner() -> Result<(), impl core::fmt::Debug> {\n");
+        prog.push_str(&crates);
+        prog.push_str(everything_else);
+        prog.push_str("\n}\n_inner().unwrap()\n}\n");
+    } else {
+        prog.push_str(&crates);
+        prog.push_str(everything_else);
+        prog.push_str("\n}\n");
+    }
+    Some(prog)
+}
+
+/// The doctests of an edition which are compiled together into a single executable. The
+/// executable is compiled by the first of these tests that runs.
+///
+/// Like a doctest compiled on its own, the executable is built without `--test`, so `cfg(test)`
+/// is not set in the doctests. Its `main` function runs the doctest whose function name is given
+/// as the first argument.
+struct MergedDoctests {
+    edition: Edition,
+    options: Options,
+    opts: TestOptions,
+    cratename: String,
+    outdir: DirState,
+    /// The function name and source of each test, and whether it mentions the crate being tested.
+    tests: Mutex<Vec<(String, String, bool)>>,
+    /// The path to the compiled executable, or `None` if it failed to compile. The outer `Option`
+    /// is `None` while it hasn't been compiled yet.
+    executable: Mutex<Option<Option<PathBuf>>>,
+}
+
+impl MergedDoctests {
+    fn new(edition: Edition, options: Options, opts: TestOptions, cratename: String) -> Self {
+        let outdir = if let Some(mut path) = options.persist_doctests.clone() {
+            path.push(format!("merged_{}", edition));
+
+            std::fs::create_dir_all(&path)
+                .expect("Couldn't create directory for doctest executables");
+
+            DirState::Perm(path)
+        } else {
+            DirState::Temp(
+                TempFileBuilder::new()
+                    .prefix("rustdoctest")
+                    .tempdir()
+                    .expect("rustdoc needs a tempdir"),
+            )
+        };
+        MergedDoctests {
+            edition,
+            options,
+            opts,
+            cratename,
+            outdir,
+            tests: Mutex::new(Vec::new()),
+            executable: Mutex::new(None),
+        }
+    }
+
+    fn add_test(&self, fn_name: String, source: String, mentions_crate: bool) {
+        self.tests.lock().unwrap().push((fn_name, source, mentions_crate));
+    }
+
+    /// Returns the path to the merged executable, compiling it first if needed, or `None` if
+    /// the tests failed to compile together.
+    fn executable(&self) -> Option<PathBuf> {
+        let mut executable = self.executable.lock().unwrap();
+        executable.get_or_insert_with(|| self.compile()).clone()
+    }
+
+    fn compile(&self) -> Option<PathBuf> {
+        let tests = self.tests.lock().unwrap();
+        let mut prog = String::new();
+        if self.opts.attrs.is_empty() {
+            prog.push_str("#![allow(unused)]\n");
+        }
+        for attr in &self.opts.attrs {
+            prog.push_str(&format!("#![{}]\n", attr));
+        }
+        if !self.opts.no_crate_inject
+            && self.cratename != "std"
+            && tests.iter().any(|&(_, _, mentions_crate)| mentions_crate)
+        {
+            prog.push_str(&format!("extern crate r#{};\n", self.cratename));
+        }
+        for (_, source, _) in tests.iter() {
+            prog.push_str(source);
+        }
+        prog.push_str("fn main() {\n    match std::env::args().nth(1).as_deref() {\n");
+        for (fn_name, _, _) in tests.iter() {
+            prog.push_str(&format!("        Some(\"{0}\") => {0}(),\n", fn_name));
+        }
+        prog.push_str("        name => panic!(\"unknown doctest {:?}\", name),\n    }\n}\n");
+        debug!("merged doctests:\n{}", prog);
+
+        let input_file = self.outdir.path().join("doctests.rs");
+        let output_file = self.outdir.path().join("rust_out");
+        std::fs::write(&input_file, prog).expect("Couldn't write merged doctests");
+
+        let mut compiler =
+            rustc_command(&self.options, self.edition, self.options.target.clone(), false);
+        compiler.arg("--crate-name").arg("rust_out");
+        compiler.arg("--crate-type").arg("bin");
+        compiler.arg("-o").arg(&output_file);
+        compiler.arg(&input_file);
+
+        let output = compiler.output().expect("Failed to spawn rustc process");
+        if output.status.success() {
+            Some(output_file)
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            debug!("merged doctests failed to compile, falling back:\n{}", stderr);
+            None
+        }
+    }
+}
+
 crate trait Tester {
     fn add_test(&mut self, test: String, config: LangString, line: usize);
     fn get_line(&self) -> usize {
@@ -720,6 +947,8 @@ fn register_header(&mut self, _name: &str, _level: u32) {}
     source_map: Option<Lrc<SourceMap>>,
     filename: Option<PathBuf>,
     visited_tests: FxHashMap<(String, usize), usize>,
+    /// The doctests compiled together with `--merge-doctests`, for each edition.
+    merged: FxHashMap<Edition, Arc<MergedDoctests>>,
 }
 
 impl Collector {
@@ -744,6 +973,7 @@ impl Collector {
             source_map,
             filename,
             visited_tests: FxHashMap::default(),
+            merged: FxHashMap::default(),
         }
     }
 
@@ -782,6 +1012,7 @@ impl Tester for Collector {
     fn add_test(&mut self, test: String, config: LangString, line: usize) {
         let filename = self.get_filename();
         let name = self.generate_name(line, &filename);
+        let file_name = filename.to_string();
         let cratename = self.cratename.to_string();
         let opts = self.opts.clone();
         let edition = config.edition.unwrap_or(self.options.edition);
@@ -790,6 +1021,9 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
         let runtool_args = self.options.runtool_args.clone();
         let target = self.options.target.clone();
         let target_str = target.to_string();
+        let report_json = self.options.output_format.is_json();
+        let item_path = self.names.join("::");
+        let end_line = line + test.lines().count() + 1;
 
         // FIXME(#44940): if doctests ever support path remapping, then this filename
         // needs to be the result of `SourceMap::span_to_unmapped_path`.
@@ -830,7 +1064,35 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
             )
         };
 
+        let merged = if self.options.merge_doctests
+            && !self.opts.display_warnings
+            && edition >= Edition::Edition2018
+            && config.ignore == Ignore::None
+            && !config.compile_fail
+            && !config.no_run
+            && !config.test_harness
+        {
+            let fn_name = format!("_doctest_{}", test_id);
+            make_merged_test(&test, &cratename, edition, &fn_name).map(|source| {
+                let options = &self.options;
+                let opts = &self.opts;
+                let merged = self.merged.entry(edition).or_insert_with(|| {
+                    Arc::new(MergedDoctests::new(
+                        edition,
+                        options.clone(),
+                        opts.clone(),
+                        cratename.clone(),
+                    ))
+                });
+                merged.add_test(fn_name.clone(), source, test.contains(&cratename));
+                (merged.clone(), fn_name)
+            })
+        } else {
+            None
+        };
+
         debug!("creating test {}: {}", name, test);
+        let test_name = name.clone();
         self.tests.push(testing::TestDescAndFn {
             desc: testing::TestDesc {
                 name: testing::DynTestName(name),
@@ -845,25 +1107,57 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
                 test_type: testing::TestType::DocTest,
             },
             testfn: testing::DynTestFn(box move || {
-                let res = run_test(
-                    &test,
-                    &cratename,
-                    line,
-                    options,
-                    config.should_panic,
-                    config.no_run,
-                    config.test_harness,
-                    runtool,
-                    runtool_args,
-                    target,
-                    config.compile_fail,
-                    config.error_codes,
-                    &opts,
-                    edition,
-                    outdir,
-                    path,
-                    &test_id,
-                );
+                // If the test was compiled along with others, run it from the merged executable.
+                // Otherwise, or if these failed to compile together, compile it on its own.
+                let merged_res = merged.and_then(|(merged, fn_name)| {
+                    let executable = merged.executable()?;
+                    Some(run_executable(
+                        &executable,
+                        &[&fn_name],
+                        config.should_panic,
+                        runtool.clone(),
+                        runtool_args.clone(),
+                        options.test_run_directory.clone(),
+                    ))
+                });
+                let res = merged_res.unwrap_or_else(|| {
+                    run_test(
+                        &test,
+                        &cratename,
+                        line,
+                        options,
+                        config.should_panic,
+                        config.no_run,
+                        config.test_harness,
+                        runtool,
+                        runtool_args,
+                        target,
+                        config.compile_fail,
+                        config.error_codes,
+                        &opts,
+                        edition,
+                        outdir,
+                        path,
+                        &test_id,
+                    )
+                });
+
+                if report_json {
+                    let event = DoctestEvent {
+                        ty: "doctest",
+                        event: if res.is_ok() { "ok" } else { "failed" },
+                        name: &test_name,
+                        item: &item_path,
+                        file: &file_name,
+                        line,
+                        end_line,
+                        failure: res.as_ref().err().map(TestFailure::stage),
+                    };
+                    // libtest captures what is printed with `print!` by the tests, so write to
+                    // stdout directly, to print the event next to the ones of libtest.
+                    let event = serde_json::to_string(&event).expect("failed to serialize event");
+                    let _ = writeln!(io::stdout().lock(), "{}", event);
+                }
 
                 if let Err(err) = res {
                     match err {
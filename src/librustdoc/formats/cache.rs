@@ -16,6 +16,7 @@
 use crate::formats::Impl;
 use crate::html::markdown::short_markdown_summary;
 use crate::html::render::cache::{extern_location, get_index_search_type, ExternalLocation};
+use crate::html::render::full_text;
 use crate::html::render::IndexItem;
 
 /// This cache is used to store information about the [`clean::Crate`] being
@@ -89,6 +90,9 @@
     /// This is stored in `Cache` so it doesn't need to be passed through all rustdoc functions.
     crate document_private: bool,
 
+    /// Whether to index the whole documentation of items for the search.
+    crate full_text_search: bool,
+
     /// Crates marked with [`#[doc(masked)]`][doc_masked].
     ///
     /// [doc_masked]: https://doc.rust-lang.org/nightly/unstable-book/language-features/doc-masked.html
@@ -129,13 +133,16 @@
 /// This struct is used to wrap the `cache` and `tcx` in order to run `DocFolder`.
 struct CacheBuilder<'a, 'tcx> {
     cache: &'a mut Cache,
-    empty_cache: Cache,
     tcx: TyCtxt<'tcx>,
 }
 
 impl Cache {
-    crate fn new(access_levels: AccessLevels<DefId>, document_private: bool) -> Self {
-        Cache { access_levels, document_private, ..Cache::default() }
+    crate fn new(
+        access_levels: AccessLevels<DefId>,
+        document_private: bool,
+        full_text_search: bool,
+    ) -> Self {
+        Cache { access_levels, document_private, full_text_search, ..Cache::default() }
     }
 
     /// Populates the `Cache` with more data. The returned `Crate` will be missing some data that was
@@ -184,7 +191,7 @@ impl Cache {
 
         self.stack.push(krate.name.to_string());
 
-        krate = CacheBuilder { tcx, cache: self, empty_cache: Cache::default() }.fold_crate(krate);
+        krate = CacheBuilder { tcx, cache: self }.fold_crate(krate);
 
         for (trait_did, dids, impl_) in self.orphan_trait_impls.drain(..) {
             if self.traits.contains_key(&trait_did) {
@@ -310,7 +317,12 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
                                 .map_or_else(String::new, |x| short_markdown_summary(&x.as_str())),
                             parent,
                             parent_idx: None,
-                            search_type: get_index_search_type(&item, &self.empty_cache, self.tcx),
+                            search_type: get_index_search_type(&item, self.tcx),
+                            doc_terms: if self.cache.full_text_search {
+                                full_text::doc_terms(&item)
+                            } else {
+                                Vec::new()
+                            },
                         });
 
                         for alias in item.attrs.get_doc_aliases() {
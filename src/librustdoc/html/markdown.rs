@@ -744,6 +744,10 @@ fn error_invalid_codeblock_attr(&self, msg: &str, help: &str) {
     crate error_codes: Vec<String>,
     crate allow_fail: bool,
     crate edition: Option<Edition>,
+    /// Whether the expansions of the invocations of the documented macro in this example are
+    /// shown in its documentation. This is ignored without `#![feature(doc_hidden_arms)]`, see
+    /// `clean::macros::expand_examples`.
+    crate expand: bool,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -766,6 +770,7 @@ fn default() -> Self {
             error_codes: Vec::new(),
             allow_fail: false,
             edition: None,
+            expand: false,
         }
     }
 }
@@ -857,6 +862,10 @@ fn parse(
                     seen_rust_tags = !seen_other_tags || seen_rust_tags;
                     data.no_run = true;
                 }
+                "expand" => {
+                    data.expand = true;
+                    seen_rust_tags = !seen_other_tags || seen_rust_tags;
+                }
                 x if x.starts_with("edition") => {
                     data.edition = x[7..].parse::<Edition>().ok();
                 }
@@ -1159,6 +1168,35 @@ fn push(s: &mut String, text_length: &mut usize, text: &str) {
     s
 }
 
+/// Renders the whole provided markdown as plain text, separated by spaces, to be indexed by the
+/// full-text search.
+///
+/// - Headings, links, and formatting are stripped.
+/// - Inline code is rendered as-is.
+/// - HTML and code blocks are ignored.
+crate fn plain_text(md: &str) -> String {
+    let mut s = String::with_capacity(md.len());
+    let mut in_code_block = false;
+
+    for event in Parser::new_ext(md, summary_opts()) {
+        match &event {
+            Event::Text(text) | Event::Code(text) if !in_code_block => {
+                s.push_str(text);
+            }
+            Event::Start(Tag::CodeBlock(..)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(..)) => in_code_block = false,
+            Event::HardBreak
+            | Event::SoftBreak
+            | Event::End(Tag::Paragraph | Tag::Heading(..) | Tag::Item | Tag::TableCell) => {
+                s.push(' ')
+            }
+            _ => (),
+        }
+    }
+
+    s
+}
+
 crate struct MarkdownLink {
     pub kind: LinkType,
     pub link: String,
@@ -1230,6 +1268,30 @@ fn push(s: &mut String, text_length: &mut usize, text: &str) {
     links.into_inner()
 }
 
+/// Returns the inline code spans of `md` which aren't part of a link, along with the range they
+/// occupy in `md`, including their backticks.
+crate fn markdown_code_spans(md: &str) -> Vec<(String, Range<usize>)> {
+    if md.is_empty() {
+        return vec![];
+    }
+
+    // Treat broken links as links too, since `[`Foo`]` is an intra-doc link to `Foo`.
+    let mut broken_link = |_: BrokenLink<'_>| Some(("".into(), "".into()));
+    let p = Parser::new_with_broken_link_callback(md, opts(), Some(&mut broken_link));
+
+    let mut code_spans = vec![];
+    let mut link_depth = 0;
+    for (event, range) in p.into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link(..)) => link_depth += 1,
+            Event::End(Tag::Link(..)) => link_depth -= 1,
+            Event::Code(code) if link_depth == 0 => code_spans.push((code.into_string(), range)),
+            _ => {}
+        }
+    }
+    code_spans
+}
+
 #[derive(Debug)]
 crate struct RustCodeBlock {
     /// The range in the markdown that the code block occupies. Note that this includes the fences
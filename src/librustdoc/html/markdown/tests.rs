@@ -1,4 +1,4 @@
-use super::{plain_text_summary, short_markdown_summary};
+use super::{markdown_code_spans, plain_text, plain_text_summary, short_markdown_summary};
 use super::{ErrorCodes, IdMap, Ignore, LangString, Markdown, MarkdownHtml};
 use rustc_span::edition::{Edition, DEFAULT_EDITION};
 
@@ -64,6 +64,8 @@ fn t(lg: LangString) {
     t(LangString { original: "should_panic".into(), should_panic: true, ..Default::default() });
     t(LangString { original: "no_run".into(), no_run: true, ..Default::default() });
     t(LangString { original: "test_harness".into(), test_harness: true, ..Default::default() });
+    t(LangString { original: "expand".into(), expand: true, ..Default::default() });
+    t(LangString { original: "rust,expand".into(), expand: true, ..Default::default() });
     t(LangString {
         original: "compile_fail".into(),
         no_run: true,
@@ -246,6 +248,20 @@ fn t(input: &str, expect: &str) {
     );
 }
 
+#[test]
+fn test_plain_text() {
+    fn t(input: &str, expect: &str) {
+        let output = plain_text(input);
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    t("hello [Rust](https://www.rust-lang.org) :)", "hello Rust :) ");
+    t("**bold** and `code`", "bold and code ");
+    t("# header\n\nfirst paragraph\n\nsecond\nline", "header first paragraph second line ");
+    t("before\n\n```\nfn main() {}\n```\n\nafter", "before after ");
+    t("<div>hello</div>", "");
+}
+
 #[test]
 fn test_plain_text_summary() {
     fn t(input: &str, expect: &str) {
@@ -286,3 +302,22 @@ fn t(input: &str, expect: &str) {
     t("Struct<'a, T>", "<p>Struct&lt;’a, T&gt;</p>\n");
     t("Struct<br>", "<p>Struct&lt;br&gt;</p>\n");
 }
+
+#[test]
+fn test_markdown_code_spans() {
+    fn t(input: &str, expect: &[(&str, &str)]) {
+        let output = markdown_code_spans(input);
+        let output = output
+            .iter()
+            .map(|(code, range)| (code.as_str(), &input[range.clone()]))
+            .collect::<Vec<_>>();
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    t("uses `Foo::bar` and `baz()`", &[("Foo::bar", "`Foo::bar`"), ("baz()", "`baz()`")]);
+    t("a [`Foo::bar`] link", &[]);
+    t("a [`Foo::bar`](Foo::bar) link", &[]);
+    t("a [`Foo::bar`][foo] link\n\n[foo]: Foo::bar", &[]);
+    t("```\nlet x = `y`;\n```", &[]);
+    t("nested ``a `b` c``", &[("a `b` c", "``a `b` c``")]);
+}
@@ -1,19 +1,21 @@
 use std::collections::BTreeMap;
 use std::path::Path;
 
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir as hir;
 use rustc_middle::ty::TyCtxt;
-use rustc_span::symbol::{sym, Symbol};
+use rustc_span::symbol::{kw, sym, Symbol};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 use crate::clean::types::{
-    FnDecl, FnRetTy, GenericBound, Generics, GetDefId, Type, TypeKind, WherePredicate,
+    FnRetTy, GenericArg, GenericArgs, GenericBound, Generics, Type, TypeBindingKind, TypeKind,
+    WherePredicate,
 };
 use crate::clean::{self, AttributesExt};
 use crate::formats::cache::Cache;
 use crate::formats::item_type::ItemType;
 use crate::html::markdown::short_markdown_summary;
-use crate::html::render::{Generic, IndexItem, IndexItemFunctionType, RenderType, TypeWithKind};
+use crate::html::render::{full_text, IndexItem, IndexItemFunctionType, RenderType, RenderTypeName};
 
 /// Indicates where an external crate can be found.
 crate enum ExternalLocation {
@@ -81,7 +83,12 @@
                 desc: item.doc_value().map_or_else(String::new, |s| short_markdown_summary(&s)),
                 parent: Some(did),
                 parent_idx: None,
-                search_type: get_index_search_type(&item, cache, tcx),
+                search_type: get_index_search_type(&item, tcx),
+                doc_terms: if cache.full_text_search {
+                    full_text::doc_terms(&item)
+                } else {
+                    Vec::new()
+                },
             });
             for alias in item.attrs.get_doc_aliases() {
                 cache
@@ -141,6 +148,8 @@ struct CrateData<'a> {
         //
         // To be noted: the `usize` elements are indexes to `items`.
         aliases: &'a BTreeMap<String, Vec<usize>>,
+        // Whether `search-fulltext/{crate}{suffix}.js` exists.
+        full_text: bool,
     }
 
     impl<'a> Serialize for CrateData<'a> {
@@ -149,8 +158,8 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             S: Serializer,
         {
             let has_aliases = !self.aliases.is_empty();
-            let mut crate_data =
-                serializer.serialize_struct("CrateData", if has_aliases { 9 } else { 8 })?;
+            let len = 8 + has_aliases as usize + self.full_text as usize;
+            let mut crate_data = serializer.serialize_struct("CrateData", len)?;
             crate_data.serialize_field("doc", &self.doc)?;
             crate_data.serialize_field(
                 "t",
@@ -192,6 +201,9 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             if has_aliases {
                 crate_data.serialize_field("a", &self.aliases)?;
             }
+            if self.full_text {
+                crate_data.serialize_field("b", &1)?;
+            }
             crate_data.end()
         }
     }
@@ -205,6 +217,7 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             items: crate_items,
             paths: crate_paths,
             aliases,
+            full_text: cache.full_text_search,
         })
         .expect("failed serde conversion")
         // All these `replace` calls are because we have to go through JS string for JSON content.
@@ -217,201 +230,135 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 
 crate fn get_index_search_type<'tcx>(
     item: &clean::Item,
-    cache: &Cache,
     tcx: TyCtxt<'tcx>,
 ) -> Option<IndexItemFunctionType> {
-    let (all_types, ret_types) = match *item.kind {
-        clean::FunctionItem(ref f) => get_all_types(&f.generics, &f.decl, tcx),
-        clean::MethodItem(ref m, _) => get_all_types(&m.generics, &m.decl, tcx),
-        clean::TyMethodItem(ref m) => get_all_types(&m.generics, &m.decl, tcx),
+    let (generics, decl) = match *item.kind {
+        clean::FunctionItem(ref f) => (&f.generics, &f.decl),
+        clean::MethodItem(ref m, _) => (&m.generics, &m.decl),
+        clean::TyMethodItem(ref m) => (&m.generics, &m.decl),
         _ => return None,
     };
 
-    let inputs = all_types
-        .iter()
-        .map(|(ty, kind)| TypeWithKind::from((get_index_type(&ty, &cache), *kind)))
-        .filter(|a| a.ty.name.is_some())
-        .collect();
-    let output = ret_types
-        .iter()
-        .map(|(ty, kind)| TypeWithKind::from((get_index_type(&ty, &cache), *kind)))
-        .filter(|a| a.ty.name.is_some())
-        .collect::<Vec<_>>();
-    let output = if output.is_empty() { None } else { Some(output) };
-
-    Some(IndexItemFunctionType { inputs, output })
-}
-
-fn get_index_type(clean_type: &clean::Type, cache: &Cache) -> RenderType {
-    RenderType {
-        ty: clean_type.def_id_full(cache),
-        idx: None,
-        name: get_index_type_name(clean_type, true).map(|s| s.as_str().to_ascii_lowercase()),
-        generics: get_generics(clean_type, cache),
-    }
-}
-
-fn get_index_type_name(clean_type: &clean::Type, accept_generic: bool) -> Option<Symbol> {
-    match *clean_type {
-        clean::ResolvedPath { ref path, .. } => {
-            let segments = &path.segments;
-            let path_segment = segments.iter().last().unwrap_or_else(|| {
-                panic!(
-                "get_index_type_name(clean_type: {:?}, accept_generic: {:?}) had length zero path",
-                clean_type, accept_generic
-            )
-            });
-            Some(path_segment.name)
-        }
-        clean::Generic(s) if accept_generic => Some(s),
-        clean::Primitive(ref p) => Some(p.as_sym()),
-        clean::BorrowedRef { ref type_, .. } => get_index_type_name(type_, accept_generic),
-        clean::Generic(_)
-        | clean::BareFunction(_)
-        | clean::Tuple(_)
-        | clean::Slice(_)
-        | clean::Array(_, _)
-        | clean::Never
-        | clean::RawPointer(_, _)
-        | clean::QPath { .. }
-        | clean::Infer
-        | clean::ImplTrait(_) => None,
-    }
-}
+    let mut builder =
+        SearchTypeBuilder { tcx, generics, params: FxHashMap::default(), bounds: vec![] };
+    let inputs =
+        decl.inputs.values.iter().filter_map(|arg| builder.render_type(&arg.type_)).collect();
+    let output = match decl.output {
+        FnRetTy::Return(ref ty) => builder.render_type(ty),
+        FnRetTy::DefaultReturn => None,
+    };
 
-fn get_generics(clean_type: &clean::Type, cache: &Cache) -> Option<Vec<Generic>> {
-    clean_type.generics().and_then(|types| {
-        let r = types
-            .iter()
-            .filter_map(|t| {
-                get_index_type_name(t, false).map(|name| Generic {
-                    name: name.as_str().to_ascii_lowercase(),
-                    defid: t.def_id_full(cache),
-                    idx: None,
-                })
-            })
-            .collect::<Vec<_>>();
-        if r.is_empty() { None } else { Some(r) }
-    })
+    Some(IndexItemFunctionType { inputs, output, bounds: builder.bounds })
 }
 
-/// The point of this function is to replace bounds with types.
-///
-/// i.e. `[T, U]` when you have the following bounds: `T: Display, U: Option<T>` will return
-/// `[Display, Option]` (we just returns the list of the types, we don't care about the
-/// wrapped types in here).
-crate fn get_real_types<'tcx>(
-    generics: &Generics,
-    arg: &Type,
+/// Converts the types of a function signature into [`RenderType`]s, numbering its generic
+/// parameters as they are encountered.
+struct SearchTypeBuilder<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
-    recurse: i32,
-    res: &mut FxHashSet<(Type, TypeKind)>,
-) -> usize {
-    fn insert(res: &mut FxHashSet<(Type, TypeKind)>, tcx: TyCtxt<'_>, ty: Type) -> usize {
-        if let Some(kind) = ty.def_id().map(|did| tcx.def_kind(did).into()) {
-            res.insert((ty, kind));
-            1
-        } else if ty.is_primitive() {
-            // This is a primitive, let's store it as such.
-            res.insert((ty, TypeKind::Primitive));
-            1
-        } else {
-            0
-        }
-    }
-
-    if recurse >= 10 {
-        // FIXME: remove this whole recurse thing when the recursion bug is fixed
-        return 0;
-    }
-    let mut nb_added = 0;
+    generics: &'a Generics,
+    /// The number of each named generic parameter encountered so far.
+    params: FxHashMap<Symbol, usize>,
+    /// The trait bounds of each generic parameter, including the anonymous ones of `impl Trait`.
+    bounds: Vec<Vec<RenderType>>,
+}
 
-    if let &Type::Generic(arg_s) = arg {
-        if let Some(where_pred) = generics.where_predicates.iter().find(|g| match g {
-            WherePredicate::BoundPredicate { ty, .. } => ty.def_id() == arg.def_id(),
-            _ => false,
-        }) {
-            let bounds = where_pred.get_bounds().unwrap_or_else(|| &[]);
-            for bound in bounds.iter() {
-                if let GenericBound::TraitBound(poly_trait, _) = bound {
-                    for x in poly_trait.generic_params.iter() {
-                        if !x.is_type() {
-                            continue;
-                        }
-                        if let Some(ty) = x.get_type() {
-                            let adds = get_real_types(generics, &ty, tcx, recurse + 1, res);
-                            nb_added += adds;
-                            if adds == 0 && !ty.is_full_generic() {
-                                nb_added += insert(res, tcx, ty);
-                            }
-                        }
-                    }
-                }
+impl SearchTypeBuilder<'_, '_> {
+    /// Returns `None` for the types that can't be searched for, like projections.
+    fn render_type(&mut self, ty: &Type) -> Option<RenderType> {
+        let (name, kind, generics): (Symbol, TypeKind, Vec<&Type>) = match *ty {
+            Type::ResolvedPath { is_generic: true, .. } | Type::QPath { .. } | Type::Infer => {
+                return None;
             }
-        }
-        if let Some(bound) = generics.params.iter().find(|g| g.is_type() && g.name == arg_s) {
-            for bound in bound.get_bounds().unwrap_or(&[]) {
-                if let Some(ty) = bound.get_trait_type() {
-                    let adds = get_real_types(generics, &ty, tcx, recurse + 1, res);
-                    nb_added += adds;
-                    if adds == 0 && !ty.is_full_generic() {
-                        nb_added += insert(res, tcx, ty);
+            Type::Generic(kw::SelfUpper) => return Some(RenderType::self_type()),
+            Type::Generic(name) => return Some(RenderType::param(self.named_param(name))),
+            Type::ImplTrait(ref bounds) => {
+                let param = self.bounds.len();
+                self.bounds.push(vec![]);
+                self.bounds[param] = self.render_bounds(bounds);
+                return Some(RenderType::param(param));
+            }
+            Type::BorrowedRef { ref type_, .. } => return self.render_type(type_),
+            Type::ResolvedPath { ref path, did, .. } => {
+                let segment = path.segments.last()?;
+                let generics = match segment.args {
+                    GenericArgs::AngleBracketed { ref args, ref bindings } => args
+                        .iter()
+                        .filter_map(|arg| match arg {
+                            GenericArg::Type(ty) => Some(ty),
+                            _ => None,
+                        })
+                        // `Iterator<Item = u8>` is searched for like `Iterator<u8>`.
+                        .chain(bindings.iter().filter_map(|binding| match binding.kind {
+                            TypeBindingKind::Equality { ref ty } => Some(ty),
+                            TypeBindingKind::Constraint { .. } => None,
+                        }))
+                        .collect(),
+                    GenericArgs::Parenthesized { ref inputs, ref output } => {
+                        inputs.iter().chain(output).collect()
                     }
-                }
+                };
+                (segment.name, TypeKind::from(self.tcx.def_kind(did)), generics)
             }
-        }
-    } else {
-        nb_added += insert(res, tcx, arg.clone());
-        if let Some(gens) = arg.generics() {
-            for gen in gens.iter() {
-                if gen.is_full_generic() {
-                    nb_added += get_real_types(generics, gen, tcx, recurse + 1, res);
-                } else {
-                    nb_added += insert(res, tcx, (*gen).clone());
-                }
+            Type::Primitive(p) => (p.as_sym(), TypeKind::Primitive, vec![]),
+            Type::Slice(ref ty) => (sym::slice, TypeKind::Primitive, vec![&**ty]),
+            Type::Array(ref ty, _) => (sym::array, TypeKind::Primitive, vec![&**ty]),
+            Type::RawPointer(_, ref ty) => (sym::pointer, TypeKind::Primitive, vec![&**ty]),
+            Type::Tuple(ref tys) if tys.is_empty() => (sym::unit, TypeKind::Primitive, vec![]),
+            Type::Tuple(ref tys) => (sym::tuple, TypeKind::Primitive, tys.iter().collect()),
+            Type::BareFunction(ref f) => {
+                let output = match f.decl.output {
+                    FnRetTy::Return(ref ty) => Some(ty),
+                    FnRetTy::DefaultReturn => None,
+                };
+                let inputs = f.decl.inputs.values.iter().map(|arg| &arg.type_);
+                (kw::Fn, TypeKind::Primitive, inputs.chain(output).collect())
             }
-        }
+            Type::Never => (sym::never, TypeKind::Primitive, vec![]),
+        };
+
+        Some(RenderType {
+            name: RenderTypeName::Named(name.as_str().to_ascii_lowercase(), kind),
+            generics: generics.into_iter().filter_map(|ty| self.render_type(ty)).collect(),
+        })
     }
-    nb_added
-}
 
-/// Return the full list of types when bounds have been resolved.
-///
-/// i.e. `fn foo<A: Display, B: Option<A>>(x: u32, y: B)` will return
-/// `[u32, Display, Option]`.
-crate fn get_all_types<'tcx>(
-    generics: &Generics,
-    decl: &FnDecl,
-    tcx: TyCtxt<'tcx>,
-) -> (Vec<(Type, TypeKind)>, Vec<(Type, TypeKind)>) {
-    let mut all_types = FxHashSet::default();
-    for arg in decl.inputs.values.iter() {
-        if arg.type_.is_self_type() {
-            continue;
+    /// Returns the number of the generic parameter `name`, collecting its bounds from the
+    /// generics of the function the first time.
+    fn named_param(&mut self, name: Symbol) -> usize {
+        if let Some(&param) = self.params.get(&name) {
+            return param;
         }
-        let mut args = FxHashSet::default();
-        get_real_types(generics, &arg.type_, tcx, 0, &mut args);
-        if !args.is_empty() {
-            all_types.extend(args);
-        } else {
-            if let Some(kind) = arg.type_.def_id().map(|did| tcx.def_kind(did).into()) {
-                all_types.insert((arg.type_.clone(), kind));
+        let param = self.bounds.len();
+        self.params.insert(name, param);
+        self.bounds.push(vec![]);
+
+        // Parameters of the parent impl or trait aren't found here, and have no bounds.
+        let generics = self.generics;
+        let inline_bounds = generics
+            .params
+            .iter()
+            .filter(|p| p.name == name)
+            .flat_map(|p| p.get_bounds().unwrap_or(&[]));
+        let where_bounds = generics.where_predicates.iter().flat_map(|pred| match pred {
+            WherePredicate::BoundPredicate { ty: Type::Generic(ty), bounds } if *ty == name => {
+                &bounds[..]
             }
-        }
+            _ => &[],
+        });
+        let bounds: Vec<_> = inline_bounds.chain(where_bounds).cloned().collect();
+        self.bounds[param] = self.render_bounds(&bounds);
+        param
     }
 
-    let ret_types = match decl.output {
-        FnRetTy::Return(ref return_type) => {
-            let mut ret = FxHashSet::default();
-            get_real_types(generics, &return_type, tcx, 0, &mut ret);
-            if ret.is_empty() {
-                if let Some(kind) = return_type.def_id().map(|did| tcx.def_kind(did).into()) {
-                    ret.insert((return_type.clone(), kind));
+    fn render_bounds(&mut self, bounds: &[GenericBound]) -> Vec<RenderType> {
+        bounds
+            .iter()
+            .filter_map(|bound| match bound {
+                GenericBound::TraitBound(poly_trait, hir::TraitBoundModifier::None) => {
+                    self.render_type(&poly_trait.trait_)
                 }
-            }
-            ret.into_iter().collect()
-        }
-        _ => Vec::new(),
-    };
-    (all_types.into_iter().collect(), ret_types)
+                _ => None,
+            })
+            .collect()
+    }
 }
@@ -14,6 +14,7 @@
 use rustc_span::symbol::sym;
 
 use super::cache::{build_index, ExternalLocation};
+use super::full_text::build_full_text_index;
 use super::print_item::{full_path, item_path, print_item};
 use super::write_shared::write_shared;
 use super::{
@@ -228,15 +229,21 @@ fn build_sidebar_items(&self, m: &clean::Module) -> BTreeMap<String, Vec<NameDoc
     /// may happen, for example, with externally inlined items where the source
     /// of their crate documentation isn't known.
     pub(super) fn src_href(&self, item: &clean::Item) -> Option<String> {
-        if item.source.is_dummy() {
+        self.href_from_span(&item.source, true)
+    }
+
+    /// Generates a url to the source page of the file containing `span`, pointing at the lines
+    /// of `span` if `with_lines` is `true`.
+    crate fn href_from_span(&self, span: &clean::Span, with_lines: bool) -> Option<String> {
+        if span.is_dummy() {
             return None;
         }
         let mut root = self.root_path();
         let mut path = String::new();
-        let cnum = item.source.cnum(self.sess());
+        let cnum = span.cnum(self.sess());
 
         // We can safely ignore synthetic `SourceFile`s.
-        let file = match item.source.filename(self.sess()) {
+        let file = match span.filename(self.sess()) {
             FileName::Real(ref path) => path.local_path().to_path_buf(),
             _ => return None,
         };
@@ -270,16 +277,23 @@ pub(super) fn src_href(&self, item: &clean::Item) -> Option<String> {
             (&*symbol, &path)
         };
 
-        let loline = item.source.lo(self.sess()).line;
-        let hiline = item.source.hi(self.sess()).line;
-        let lines =
-            if loline == hiline { loline.to_string() } else { format!("{}-{}", loline, hiline) };
+        let anchor = if with_lines {
+            let loline = span.lo(self.sess()).line;
+            let hiline = span.hi(self.sess()).line;
+            if loline == hiline {
+                format!("#{}", loline)
+            } else {
+                format!("#{}-{}", loline, hiline)
+            }
+        } else {
+            String::new()
+        };
         Some(format!(
-            "{root}src/{krate}/{path}#{lines}",
+            "{root}src/{krate}/{path}{anchor}",
             root = Escape(&root),
             krate = krate,
             path = path,
-            lines = lines
+            anchor = anchor
         ))
     }
 }
@@ -313,6 +327,7 @@ fn init(
             generate_search_filter,
             unstable_features,
             generate_redirect_map,
+            call_locations,
             ..
         } = options;
 
@@ -389,6 +404,7 @@ fn init(
             all: RefCell::new(AllTypes::new()),
             errors: receiver,
             redirections: if generate_redirect_map { Some(Default::default()) } else { None },
+            call_locations,
         };
 
         // Add the default themes to the `Vec` of stylepaths
@@ -410,6 +426,8 @@ fn init(
 
         // Build our search index
         let index = build_index(&krate, &mut cache, tcx);
+        let full_text_index =
+            if cache.full_text_search { Some(build_full_text_index(&krate, &cache)) } else { None };
 
         let mut cx = Context {
             current: Vec::new(),
@@ -425,7 +443,7 @@ fn init(
 
         // Write shared runs within a flock; disable thread dispatching of IO temporarily.
         Rc::get_mut(&mut cx.shared).unwrap().fs.set_sync_only(true);
-        write_shared(&cx, &krate, index, &md_opts)?;
+        write_shared(&cx, &krate, index, full_text_index, &md_opts)?;
         Rc::get_mut(&mut cx.shared).unwrap().fs.set_sync_only(false);
         Ok((cx, krate))
     }
@@ -0,0 +1,120 @@
+//! The full-text search index, enabled with `--full-text-search`.
+//!
+//! The documentation of each item is split into terms, which are lowercased, stemmed and
+//! filtered from stop words, and written to `search-fulltext/{crate}{suffix}.js` as a map from
+//! terms to the items containing them. Each crate gets its own file so that `search-index.js`
+//! stays small, and the files are loaded with `<script>` tags so that they work with `file://`
+//! URLs.
+//!
+//! `main.js` splits search queries into terms the same way, so any change to [`terms`] must be
+//! mirrored there.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::clean;
+use crate::formats::cache::Cache;
+use crate::html::markdown::plain_text;
+
+#[cfg(test)]
+mod tests;
+
+/// Words that are too common to be worth indexing, the same as `FULL_TEXT_STOP_WORDS` in
+/// `main.js`.
+const STOP_WORDS: &[&str] = &[
+    "an", "and", "are", "as", "at", "be", "by", "for", "from", "if", "in", "into", "is", "it",
+    "its", "of", "on", "or", "that", "the", "this", "to", "was", "will", "with",
+];
+
+/// Splits `text` into the terms used by the full-text index.
+crate fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| word.len() > 1)
+        .map(|word| word.to_ascii_lowercase())
+        .filter(|word| !STOP_WORDS.contains(&&**word))
+        .map(|word| stem(&word))
+}
+
+/// A light stemmer, removing plurals and common suffixes so that `parse`, `parses`, `parsed` and
+/// `parsing` are all indexed as `pars`. It doesn't need to produce actual words, only to agree
+/// with the stemmer of `main.js`.
+fn stem(word: &str) -> String {
+    if word.len() <= 3 {
+        return word.to_string();
+    }
+
+    let mut word = if let Some(stem) = word.strip_suffix("sses") {
+        format!("{}ss", stem)
+    } else if let Some(stem) = word.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if word.ends_with('s') && !word.ends_with("ss") && !word.ends_with("us") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    };
+
+    for &(suffix, replacement) in &[("ation", "at"), ("ing", ""), ("ed", ""), ("ly", "")] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            if stem.len() + replacement.len() >= 3 {
+                word = format!("{}{}", stem, replacement);
+                // `mapping` becomes `mapp`, which should be `map`.
+                let bytes = word.as_bytes();
+                let n = bytes.len();
+                if replacement.is_empty()
+                    && bytes[n - 1] == bytes[n - 2]
+                    && !b"lsz".contains(&bytes[n - 1])
+                {
+                    word.pop();
+                }
+            }
+            break;
+        }
+    }
+
+    if word.len() > 3 && word.ends_with('e') {
+        word.pop();
+    }
+    word
+}
+
+/// The terms of the documentation of `item`, to be stored in its `IndexItem`.
+crate fn doc_terms(item: &clean::Item) -> Vec<String> {
+    item.collapsed_doc_value().map_or_else(Vec::new, |doc| terms(&plain_text(&doc)).collect())
+}
+
+/// Builds the contents of `search-fulltext/{crate}{suffix}.js`.
+///
+/// The items are numbered like in `search-index.js`: 0 is the crate itself, and the items of
+/// `cache.search_index` follow in order.
+crate fn build_full_text_index(krate: &clean::Crate, cache: &Cache) -> String {
+    #[derive(Serialize)]
+    struct FullTextIndex {
+        /// The number of items.
+        n: usize,
+        /// For each term, the items containing it as a flat list of `[item, count, ...]`.
+        t: BTreeMap<String, Vec<usize>>,
+    }
+
+    let crate_terms = krate.module.as_ref().map_or_else(Vec::new, doc_terms);
+    let items =
+        Some(&crate_terms).into_iter().chain(cache.search_index.iter().map(|item| &item.doc_terms));
+
+    let mut index = FullTextIndex { n: 0, t: BTreeMap::new() };
+    for (i, terms) in items.enumerate() {
+        let mut counts = BTreeMap::new();
+        for term in terms {
+            *counts.entry(term).or_insert(0) += 1;
+        }
+        for (term, count) in counts {
+            index.t.entry(term.clone()).or_insert_with(Vec::new).extend(&[i, count]);
+        }
+        index.n += 1;
+    }
+
+    format!(
+        "addFullTextIndex(\"{}\", JSON.parse('{}'));\n",
+        krate.name,
+        serde_json::to_string(&index).expect("failed serde conversion")
+    )
+}
@@ -0,0 +1,33 @@
+use super::{stem, terms};
+
+#[test]
+fn test_stem() {
+    fn t(words: &[&str], expect: &str) {
+        for word in words {
+            assert_eq!(stem(word), expect, "original: {}", word);
+        }
+    }
+
+    t(&["parse", "parses", "parsed", "parsing"], "pars");
+    t(&["map", "maps", "mapped", "mapping"], "map");
+    t(&["allocate", "allocates", "allocated", "allocation"], "allocat");
+    t(&["entry", "entries"], "entry");
+    t(&["quick", "quickly"], "quick");
+    t(&["class", "classes"], "class");
+    t(&["status"], "status");
+    t(&["fill", "filled", "filling"], "fill");
+    t(&["use"], "use");
+}
+
+#[test]
+fn test_terms() {
+    fn t(text: &str, expect: &[&str]) {
+        assert_eq!(terms(text).collect::<Vec<_>>(), expect, "original: {}", text);
+    }
+
+    t("Returns the number of elements in the vector.", &["return", "number", "element", "vector"]);
+    t("Calls `Iterator::next_back` on it", &["call", "iterator", "next", "back"]);
+    t("A u8, i.e. one byte", &["u8", "one", "byt"]);
+    t("Encodes as UTF-8", &["encod", "utf"]);
+    t("", &[]);
+}
@@ -24,6 +24,7 @@
 //! both occur before the crate is rendered.
 
 crate mod cache;
+crate mod full_text;
 
 #[cfg(test)]
 mod tests;
@@ -39,6 +40,7 @@
 use std::collections::VecDeque;
 use std::default::Default;
 use std::fmt;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::str;
 use std::string::ToString;
@@ -70,8 +72,10 @@
     href, print_abi_with_space, print_default_space, print_generic_bounds, Buffer, Function,
     PrintWithSpace, WhereClause,
 };
+use crate::html::highlight;
 use crate::html::layout;
 use crate::html::markdown::{self, ErrorCodes, Markdown, MarkdownHtml, MarkdownSummaryLine};
+use crate::scrape_examples::{self, AllCallLocations, CallData};
 
 /// A pair of name and its optional document.
 crate type NameDoc = (String, Option<String>);
@@ -130,6 +134,8 @@
     /// to `Some(...)`, it'll store redirections and then generate a JSON file at the top level of
     /// the crate.
     redirections: Option<RefCell<FxHashMap<String, String>>>,
+    /// Calls to the documented functions found in other crates, see [`crate::scrape_examples`].
+    crate call_locations: AllCallLocations,
 }
 
 impl SharedContext<'_> {
@@ -164,112 +170,90 @@ impl SharedContext<'_> {
     crate parent: Option<DefId>,
     crate parent_idx: Option<usize>,
     crate search_type: Option<IndexItemFunctionType>,
+    /// The terms of the documentation for the full-text search, empty unless it is enabled.
+    crate doc_terms: Vec<String>,
 }
 
 /// A type used for the search index.
+///
+/// It is serialized as `[name, kind]`, followed by its generic arguments if it has any, as
+/// `[param]` for the generic parameters of the function, or as `["self"]` for `Self`, which the
+/// search replaces with the parent of the function.
 #[derive(Debug)]
 crate struct RenderType {
-    ty: Option<DefId>,
-    idx: Option<usize>,
-    name: Option<String>,
-    generics: Option<Vec<Generic>>,
-}
-
-impl Serialize for RenderType {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        if let Some(name) = &self.name {
-            let mut seq = serializer.serialize_seq(None)?;
-            if let Some(id) = self.idx {
-                seq.serialize_element(&id)?;
-            } else {
-                seq.serialize_element(&name)?;
-            }
-            if let Some(generics) = &self.generics {
-                seq.serialize_element(&generics)?;
-            }
-            seq.end()
-        } else {
-            serializer.serialize_none()
-        }
-    }
+    name: RenderTypeName,
+    /// The generic arguments, including the associated types bound with `Trait<Assoc = Type>`,
+    /// the arguments and output of `Fn` traits, and the elements of slices and tuples.
+    generics: Vec<RenderType>,
 }
 
-/// A type used for the search index.
 #[derive(Debug)]
-crate struct Generic {
-    name: String,
-    defid: Option<DefId>,
-    idx: Option<usize>,
+crate enum RenderTypeName {
+    /// The lowercased name of the type.
+    Named(String, TypeKind),
+    /// The index of a generic parameter of the function in [`IndexItemFunctionType::bounds`].
+    /// `impl Trait` types are anonymous generic parameters.
+    Param(usize),
+    SelfType,
 }
 
-impl Serialize for Generic {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        if let Some(id) = self.idx {
-            serializer.serialize_some(&id)
-        } else {
-            serializer.serialize_some(&self.name)
-        }
+impl RenderType {
+    fn param(param: usize) -> RenderType {
+        RenderType { name: RenderTypeName::Param(param), generics: vec![] }
     }
-}
 
-/// Full type of functions/methods in the search index.
-#[derive(Debug)]
-crate struct IndexItemFunctionType {
-    inputs: Vec<TypeWithKind>,
-    output: Option<Vec<TypeWithKind>>,
+    fn self_type() -> RenderType {
+        RenderType { name: RenderTypeName::SelfType, generics: vec![] }
+    }
 }
 
-impl Serialize for IndexItemFunctionType {
+impl Serialize for RenderType {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        // If we couldn't figure out a type, just write `null`.
-        let mut iter = self.inputs.iter();
-        if match self.output {
-            Some(ref output) => iter.chain(output.iter()).any(|ref i| i.ty.name.is_none()),
-            None => iter.any(|ref i| i.ty.name.is_none()),
-        } {
-            serializer.serialize_none()
-        } else {
-            let mut seq = serializer.serialize_seq(None)?;
-            seq.serialize_element(&self.inputs)?;
-            if let Some(output) = &self.output {
-                if output.len() > 1 {
-                    seq.serialize_element(&output)?;
-                } else {
-                    seq.serialize_element(&output[0])?;
+        let mut seq = serializer.serialize_seq(None)?;
+        match self.name {
+            RenderTypeName::Named(ref name, kind) => {
+                seq.serialize_element(&name)?;
+                seq.serialize_element(&ItemType::from(kind))?;
+                if !self.generics.is_empty() {
+                    seq.serialize_element(&self.generics)?;
                 }
             }
-            seq.end()
+            RenderTypeName::Param(param) => seq.serialize_element(&param)?,
+            RenderTypeName::SelfType => seq.serialize_element("self")?,
         }
+        seq.end()
     }
 }
 
+/// Full type of functions/methods in the search index.
+///
+/// It is serialized as `[inputs, output, bounds]`, where the trailing elements are omitted when
+/// they are empty, and `output` is `null` for functions without an output but with bounds.
 #[derive(Debug)]
-crate struct TypeWithKind {
-    ty: RenderType,
-    kind: TypeKind,
-}
-
-impl From<(RenderType, TypeKind)> for TypeWithKind {
-    fn from(x: (RenderType, TypeKind)) -> TypeWithKind {
-        TypeWithKind { ty: x.0, kind: x.1 }
-    }
+crate struct IndexItemFunctionType {
+    inputs: Vec<RenderType>,
+    output: Option<RenderType>,
+    /// The trait bounds of each generic parameter.
+    bounds: Vec<Vec<RenderType>>,
 }
 
-impl Serialize for TypeWithKind {
+impl Serialize for IndexItemFunctionType {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        (&self.ty.name, ItemType::from(self.kind)).serialize(serializer)
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element(&self.inputs)?;
+        if self.output.is_some() || !self.bounds.is_empty() {
+            seq.serialize_element(&self.output)?;
+        }
+        if !self.bounds.is_empty() {
+            seq.serialize_element(&self.bounds)?;
+        }
+        seq.end()
     }
 }
 
@@ -614,6 +598,7 @@ fn document(w: &mut Buffer, cx: &Context<'_>, item: &clean::Item, parent: Option
     }
     document_item_info(w, cx, item, false, parent);
     document_full(w, item, cx, "", false);
+    render_call_locations(w, cx, item);
 }
 
 /// Render md_text as markdown.
@@ -737,6 +722,138 @@ fn document_item_info(
     }
 }
 
+/// The number of scraped examples rendered in full, the other ones are only linked to.
+const MAX_FULL_EXAMPLES: usize = 5;
+
+/// Renders the calls to `item` found in other crates, as given with `--with-examples`.
+///
+/// Every file calling the item contributes one example, which shows the item enclosing its
+/// first call. The shortest example is shown right away and the other ones are collapsed.
+fn render_call_locations(w: &mut Buffer, cx: &Context<'_>, item: &clean::Item) {
+    if cx.shared.call_locations.is_empty() {
+        return;
+    }
+    let key = scrape_examples::def_id_key(cx.tcx(), item.def_id);
+    let call_locations = match cx.shared.call_locations.get(&key) {
+        Some(call_locations) if !call_locations.is_empty() => call_locations,
+        _ => return,
+    };
+
+    let mut examples: Vec<(&PathBuf, &CallData)> = call_locations.iter().collect();
+    examples.sort_by_key(|(path, call_data)| {
+        let (lo, hi) = call_data.locations[0].enclosing_item.line_span;
+        (hi - lo, *path)
+    });
+
+    w.write_str(
+        "<div class=\"docblock scraped-example-list\">\
+            <h5 class=\"scraped-examples-title\">Examples found in repository</h5>",
+    );
+    let mut examples = examples.into_iter();
+    for (path, call_data) in examples.by_ref() {
+        if render_scraped_example(w, cx, path, call_data) {
+            break;
+        }
+    }
+
+    let rest: Vec<_> = examples.collect();
+    if !rest.is_empty() {
+        write!(
+            w,
+            "<details class=\"scraped-examples\"><summary>More examples ({})</summary>",
+            rest.len()
+        );
+        let (full, linked) = rest.split_at(rest.len().min(MAX_FULL_EXAMPLES));
+        for (path, call_data) in full {
+            render_scraped_example(w, cx, path, call_data);
+        }
+        if !linked.is_empty() {
+            w.write_str("<p>Additional examples can be found in:</p><ul>");
+            for (_, call_data) in linked {
+                write!(
+                    w,
+                    "<li><a href=\"{root}{url}\">{name}</a></li>",
+                    root = cx.root_path(),
+                    url = call_data.url,
+                    name = Escape(&call_data.display_name),
+                );
+            }
+            w.write_str("</ul>");
+        }
+        w.write_str("</details>");
+    }
+    w.write_str("</div>");
+}
+
+/// Renders the item enclosing the first call of `call_data`, with line numbers and the lines of
+/// the calls it contains highlighted. Returns `false` if the example couldn't be read, e.g.
+/// because the file changed since the examples were scraped.
+fn render_scraped_example(
+    w: &mut Buffer,
+    cx: &Context<'_>,
+    path: &Path,
+    call_data: &CallData,
+) -> bool {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            warn!("failed to read scraped example `{}`: {}", path.display(), e);
+            return false;
+        }
+    };
+    let enclosing_item = &call_data.locations[0].enclosing_item;
+    let (lo, hi) = (enclosing_item.byte_span.0 as usize, enclosing_item.byte_span.1 as usize);
+    // Start at the beginning of the line, so that the indentation of the item is kept.
+    let lo = match contents.get(..lo) {
+        Some(before) => before.rfind('\n').map_or(0, |i| i + 1),
+        None => return false,
+    };
+    let snippet = match contents.get(lo..hi) {
+        Some(snippet) => snippet,
+        None => return false,
+    };
+
+    let calls: Vec<_> = call_data
+        .locations
+        .iter()
+        .filter(|location| location.enclosing_item == *enclosing_item)
+        .map(|location| location.call_expr.line_span)
+        .collect();
+    let (first_call_lo, first_call_hi) = calls[0];
+    let line_anchor = if first_call_lo == first_call_hi {
+        first_call_lo.to_string()
+    } else {
+        format!("{}-{}", first_call_lo, first_call_hi)
+    };
+    write!(
+        w,
+        "<div class=\"scraped-example\">\
+            <div class=\"scraped-example-title\">\
+                <a href=\"{root}{url}#{lines}\">{name}</a> (line {line})\
+            </div>\
+            <pre class=\"line-numbers\">",
+        root = cx.root_path(),
+        url = call_data.url,
+        lines = line_anchor,
+        name = Escape(&call_data.display_name),
+        line = first_call_lo,
+    );
+    let (first_line, last_line) = enclosing_item.line_span;
+    for line in first_line..=last_line {
+        let in_call = calls.iter().any(|&(lo, hi)| lo <= line && line <= hi);
+        if in_call {
+            writeln!(w, "<span class=\"line-highlighted\">{}</span>", line);
+        } else {
+            writeln!(w, "<span>{}</span>", line);
+        }
+    }
+    w.write_str("</pre>");
+    let edition = call_data.edition().unwrap_or(cx.shared.edition);
+    highlight::render_with_highlighting(snippet, w, None, None, None, edition);
+    w.write_str("</div>");
+    true
+}
+
 fn portability(item: &clean::Item, parent: Option<&clean::Item>) -> Option<String> {
     let cfg = match (&item.attrs.cfg, parent.and_then(|p| p.attrs.cfg.as_ref())) {
         (Some(cfg), Some(parent_cfg)) => cfg.simplify_with(parent_cfg),
@@ -1608,6 +1725,9 @@ fn doc_impl_item(
                     document_item_info(w, cx, item, is_hidden, Some(parent));
                     if show_def_docs {
                         document_full(w, item, cx, "", is_hidden);
+                        if render_mode == RenderMode::Normal {
+                            render_call_locations(w, cx, item);
+                        }
                     }
                 }
             } else {
@@ -1,5 +1,7 @@
 use std::cmp::Ordering;
 
+use rustc_ast::token::DelimToken;
+use rustc_ast_pretty::pprust;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir as hir;
 use rustc_hir::def::CtorKind;
@@ -14,6 +16,7 @@
     render_assoc_items, render_attributes, render_impl, render_stability_since_raw, spotlight_decl,
     write_srclink, AssocItemLink, Context,
 };
+use crate::clean::macros::MatcherNode;
 use crate::clean::{self, GetDefId};
 use crate::formats::cache::Cache;
 use crate::formats::item_type::ItemType;
@@ -940,7 +943,109 @@ fn item_macro(w: &mut Buffer, cx: &Context<'_>, it: &clean::Item, t: &clean::Mac
             it.source.span().edition(),
         );
     });
-    document(w, cx, it, None)
+    document(w, cx, it, None);
+
+    let name = it.name.as_ref().expect("macros always have names");
+    if t.arms.iter().any(|arm| !arm.hidden) {
+        write!(
+            w,
+            "<h2 id=\"grammar\" class=\"small-section-header\">\
+                 Grammar<a href=\"#grammar\" class=\"anchor\"></a></h2>\
+             <pre class=\"rust macro-grammar\">"
+        );
+        for arm in t.arms.iter().filter(|arm| !arm.hidden) {
+            write!(w, "{}!(", name);
+            write_macro_grammar(w, &arm.matcher);
+            w.write_str(")\n");
+        }
+        w.write_str("</pre>");
+    }
+
+    if !t.expansions.is_empty() {
+        write!(
+            w,
+            "<h2 id=\"expansions\" class=\"small-section-header\">\
+                 Expansions<a href=\"#expansions\" class=\"anchor\"></a></h2>"
+        );
+        for expansion in &t.expansions {
+            highlight::render_with_highlighting(
+                &format!("{}\n\n// Expands to:\n{}", expansion.invocation, expansion.expansion),
+                w,
+                Some("macro-expansion"),
+                None,
+                None,
+                it.source.span().edition(),
+            );
+        }
+    }
+}
+
+/// Writes the nodes of a matcher, linking the fragment specifiers of its metavariables to the
+/// Reference.
+fn write_macro_grammar(w: &mut Buffer, nodes: &[MatcherNode]) {
+    for (i, node) in nodes.iter().enumerate() {
+        if i > 0 {
+            w.write_str(" ");
+        }
+        match node {
+            MatcherNode::Token(token) => {
+                write!(w, "{}", Escape(&pprust::token_to_string(token)));
+            }
+            MatcherNode::Delimited(delim, nodes) => {
+                let (open, close) = match delim {
+                    DelimToken::Paren => ("(", ")"),
+                    DelimToken::Bracket => ("[", "]"),
+                    DelimToken::Brace => ("{", "}"),
+                    DelimToken::NoDelim => ("", ""),
+                };
+                w.write_str(open);
+                write_macro_grammar(w, nodes);
+                w.write_str(close);
+            }
+            MatcherNode::MetaVar { name, fragment } => {
+                write!(w, "<span class=\"macro-var\">${}</span>", name);
+                if let Some(fragment) = fragment {
+                    write!(
+                        w,
+                        ": <a href=\"{}{}\">{}</a>",
+                        REFERENCE_URL,
+                        fragment_reference_page(*fragment),
+                        fragment
+                    );
+                }
+            }
+            MatcherNode::Repetition { nodes, separator, kleene_op } => {
+                w.write_str("<span class=\"macro-repetition\">$(</span>");
+                write_macro_grammar(w, nodes);
+                w.write_str("<span class=\"macro-repetition\">)");
+                if let Some(separator) = separator {
+                    write!(w, "{}", Escape(&pprust::token_to_string(separator)));
+                }
+                write!(w, "{}</span>", Escape(&pprust::token_to_string(kleene_op)));
+            }
+        }
+    }
+}
+
+const REFERENCE_URL: &str = "https://doc.rust-lang.org/reference/";
+
+/// The page of the Reference describing what a fragment specifier matches.
+fn fragment_reference_page(fragment: Symbol) -> &'static str {
+    match &*fragment.as_str() {
+        "expr" => "expressions.html",
+        "ty" => "types.html",
+        "ident" => "identifiers.html",
+        "path" => "paths.html",
+        "block" => "expressions/block-expr.html",
+        "stmt" => "statements.html",
+        "pat" | "pat_param" => "patterns.html",
+        "item" => "items.html",
+        "lifetime" => "tokens.html#lifetimes-and-loop-labels",
+        "literal" => "expressions/literal-expr.html",
+        "meta" => "attributes.html#meta-item-attribute-syntax",
+        "vis" => "visibility-and-privacy.html",
+        _ => "macros-by-example.html#metavariables",
+    }
 }
 
 fn item_proc_macro(w: &mut Buffer, cx: &Context<'_>, it: &clean::Item, m: &clean::ProcMacro) {
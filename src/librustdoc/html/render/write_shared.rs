@@ -43,6 +43,7 @@ pub(super) fn write_shared(
     cx: &Context<'_>,
     krate: &Crate,
     search_index: String,
+    full_text_index: Option<String>,
     options: &RenderOptions,
 ) -> Result<(), Error> {
     // Write out the shared files. Note that these are shared among all rustdoc
@@ -394,6 +395,15 @@ fn to_json_string(&self) -> String {
         cx.shared.fs.write(&dst, &v)?;
     }
 
+    // The full-text index is only loaded for the crates it is enabled for, so it can be left
+    // alone otherwise.
+    if let Some(full_text_index) = full_text_index {
+        let dir = cx.dst.join("search-fulltext");
+        cx.shared.ensure_dir(&dir)?;
+        let dst = dir.join(&format!("{}{}.js", krate.name, cx.shared.resource_suffix));
+        cx.shared.fs.write(&dst, &full_text_index)?;
+    }
+
     let crate_list_dst = cx.dst.join(&format!("crates{}.js", cx.shared.resource_suffix));
     let crate_list =
         format!("window.ALL_CRATES = [{}];", krates.iter().map(|k| format!("\"{}\"", k)).join(","));
@@ -43,6 +43,7 @@ if (!DOMTokenList.prototype.remove) {
         window.rootPath = rustdocVars.attributes["data-root-path"].value;
         window.currentCrate = rustdocVars.attributes["data-current-crate"].value;
         window.searchJS = rustdocVars.attributes["data-search-js"].value;
+        window.resourceSuffix = rustdocVars.attributes["data-resource-suffix"].value;
     }
     var sidebarVars = document.getElementById("sidebar-vars");
     if (sidebarVars) {
@@ -646,13 +647,24 @@ function defocusSearchBar() {
     window.initSearch = function(rawSearchIndex) {
         var MAX_LEV_DISTANCE = 3;
         var MAX_RESULTS = 200;
-        var GENERICS_DATA = 1;
+        var GENERICS_DATA = 2;
         var NAME = 0;
         var INPUTS_DATA = 0;
         var OUTPUT_DATA = 1;
+        var BOUNDS_DATA = 2;
         var NO_TYPE_FILTER = -1;
         var currentResults, index, searchIndex;
         var ALIASES = {};
+        // The index in `searchIndex` of each crate, followed by its items.
+        var CRATE_OFFSETS = {};
+        // The full-text index of each crate that has one: `null` until it is requested, `false`
+        // while it is loading.
+        var FULL_TEXT_INDEXES = {};
+        // Must be the same as `STOP_WORDS` in `full_text.rs`.
+        var FULL_TEXT_STOP_WORDS = [
+            "an", "and", "are", "as", "at", "be", "by", "for", "from", "if", "in", "into", "is",
+            "it", "its", "of", "on", "or", "that", "the", "this", "to", "was", "will", "with",
+        ];
         var params = getQueryStringParams();
 
         // Populate search bar with query string search term when provided,
@@ -745,6 +757,11 @@ function defocusSearchBar() {
                     b = (bbb.lev);
                     if (a !== b) { return a - b; }
 
+                    // sort by how well the documentation matches (no match goes later)
+                    a = (aaa.bodyScore || 0);
+                    b = (bbb.bodyScore || 0);
+                    if (a !== b) { return b - a; }
+
                     // sort by crate (non-current crate goes later)
                     a = (aaa.item.crate !== window.currentCrate);
                     b = (bbb.item.crate !== window.currentCrate);
@@ -833,46 +850,62 @@ function defocusSearchBar() {
                 };
             }
 
-            function getObjectFromId(id) {
-                if (typeof id === "number") {
-                    return searchIndex[id];
+            // The names of the generic arguments of a type of the search index, leaving out the
+            // generic parameters of the function and `Self`.
+            function genericNames(obj) {
+                var names = [];
+                if (obj.length > GENERICS_DATA) {
+                    var generics = obj[GENERICS_DATA];
+                    for (var i = 0, len = generics.length; i < len; ++i) {
+                        if (typeof generics[i][NAME] === "string" && generics[i][NAME] !== "self") {
+                            names.push(generics[i][NAME]);
+                        }
+                    }
                 }
-                return {'name': id};
+                return names;
+            }
+
+            // The types to look at in place of `obj` when searching for a type name: the trait
+            // bounds of the generic parameters of the function, and nothing for `Self`.
+            function typesForNameSearch(obj, bounds) {
+                if (typeof obj[NAME] === "number") {
+                    return bounds[obj[NAME]] || [];
+                }
+                if (obj[NAME] === "self") {
+                    return [];
+                }
+                return [obj];
             }
 
             function checkGenerics(obj, val) {
                 // The names match, but we need to be sure that all generics kinda
                 // match as well.
-                if (val.generics.length > 0) {
-                    if (obj.length > GENERICS_DATA &&
-                          obj[GENERICS_DATA].length >= val.generics.length) {
-                        var elems = obj[GENERICS_DATA].slice(0);
-                        var total = 0;
-                        var done = 0;
-                        // We need to find the type that matches the most to remove it in order
-                        // to move forward.
-                        var vlength = val.generics.length;
-                        for (var y = 0; y < vlength; ++y) {
-                            var lev = { pos: -1, lev: MAX_LEV_DISTANCE + 1};
-                            var firstGeneric = getObjectFromId(val.generics[y]).name;
-                            for (var x = 0, elength = elems.length; x < elength; ++x) {
-                                var tmp_lev = levenshtein(getObjectFromId(elems[x]).name,
-                                                                          firstGeneric);
-                                if (tmp_lev < lev.lev) {
-                                    lev.lev = tmp_lev;
-                                    lev.pos = x;
-                                }
-                            }
-                            if (lev.pos !== -1) {
-                                elems.splice(lev.pos, 1);
-                                total += lev.lev;
-                                done += 1;
-                            } else {
-                                return MAX_LEV_DISTANCE + 1;
+                var generics = genericNames(obj);
+                if (val.generics.length > 0 && generics.length >= val.generics.length) {
+                    var elems = generics.slice(0);
+                    var total = 0;
+                    var done = 0;
+                    // We need to find the type that matches the most to remove it in order
+                    // to move forward.
+                    var vlength = val.generics.length;
+                    for (var y = 0; y < vlength; ++y) {
+                        var lev = { pos: -1, lev: MAX_LEV_DISTANCE + 1};
+                        for (var x = 0, elength = elems.length; x < elength; ++x) {
+                            var tmp_lev = levenshtein(elems[x], val.generics[y]);
+                            if (tmp_lev < lev.lev) {
+                                lev.lev = tmp_lev;
+                                lev.pos = x;
                             }
                         }
-                        return Math.ceil(total / done);
+                        if (lev.pos !== -1) {
+                            elems.splice(lev.pos, 1);
+                            total += lev.lev;
+                            done += 1;
+                        } else {
+                            return MAX_LEV_DISTANCE + 1;
+                        }
                     }
+                    return Math.ceil(total / done);
                 }
                 return MAX_LEV_DISTANCE + 1;
             }
@@ -880,22 +913,21 @@ function defocusSearchBar() {
             // Check for type name and type generics (if any).
             function checkType(obj, val, literalSearch) {
                 var lev_distance = MAX_LEV_DISTANCE + 1;
-                var len, x, y, e_len, firstGeneric;
+                var generics = genericNames(obj);
+                var len, x, y, e_len;
                 if (obj[NAME] === val.name) {
                     if (literalSearch === true) {
                         if (val.generics && val.generics.length !== 0) {
-                            if (obj.length > GENERICS_DATA &&
-                                  obj[GENERICS_DATA].length >= val.generics.length) {
-                                var elems = obj[GENERICS_DATA].slice(0);
+                            if (generics.length >= val.generics.length) {
+                                var elems = generics.slice(0);
                                 var allFound = true;
 
                                 len = val.generics.length;
                                 for (y = 0; allFound === true && y < len; ++y) {
                                     allFound = false;
-                                    firstGeneric = getObjectFromId(val.generics[y]).name;
                                     e_len = elems.length;
                                     for (x = 0; allFound === false && x < e_len; ++x) {
-                                        allFound = getObjectFromId(elems[x]).name === firstGeneric;
+                                        allFound = elems[x] === val.generics[y];
                                     }
                                     if (allFound === true) {
                                         elems.splice(x - 1, 1);
@@ -912,7 +944,7 @@ function defocusSearchBar() {
                     }
                     // If the type has generics but don't match, then it won't return at this point.
                     // Otherwise, `checkGenerics` will return 0 and it'll return.
-                    if (obj.length > GENERICS_DATA && obj[GENERICS_DATA].length !== 0) {
+                    if (generics.length !== 0) {
                         var tmp_lev = checkGenerics(obj, val);
                         if (tmp_lev <= MAX_LEV_DISTANCE) {
                             return tmp_lev;
@@ -923,13 +955,10 @@ function defocusSearchBar() {
                 }
                 // Names didn't match so let's check if one of the generic types could.
                 if (literalSearch === true) {
-                     if (obj.length > GENERICS_DATA && obj[GENERICS_DATA].length > 0) {
-                        return obj[GENERICS_DATA].some(
-                            function(name) {
-                                return name === val.name;
-                            });
-                    }
-                    return false;
+                    return generics.some(
+                        function(name) {
+                            return name === val.name;
+                        });
                 }
                 lev_distance = Math.min(levenshtein(obj[NAME], val.name), lev_distance);
                 if (lev_distance <= MAX_LEV_DISTANCE) {
@@ -937,12 +966,10 @@ function defocusSearchBar() {
                     // a levenshtein distance value that isn't *this* good so it goes
                     // into the search results but not too high.
                     lev_distance = Math.ceil((checkGenerics(obj, val) + lev_distance) / 2);
-                } else if (obj.length > GENERICS_DATA && obj[GENERICS_DATA].length > 0) {
+                } else {
                     // We can check if the type we're looking for is inside the generics!
-                    var olength = obj[GENERICS_DATA].length;
-                    for (x = 0; x < olength; ++x) {
-                        lev_distance = Math.min(levenshtein(obj[GENERICS_DATA][x], val.name),
-                                                lev_distance);
+                    for (x = 0, len = generics.length; x < len; ++x) {
+                        lev_distance = Math.min(levenshtein(generics[x], val.name), lev_distance);
                     }
                 }
                 // Now whatever happens, the returned distance is "less good" so we should mark it
@@ -950,13 +977,14 @@ function defocusSearchBar() {
                 return lev_distance + 1;
             }
 
-            function findArg(obj, val, literalSearch, typeFilter) {
+            // Check `types`, the inputs or the output of a function, for the type `val`.
+            function checkTypes(types, bounds, val, literalSearch, typeFilter) {
                 var lev_distance = MAX_LEV_DISTANCE + 1;
 
-                if (obj && obj.type && obj.type[INPUTS_DATA] && obj.type[INPUTS_DATA].length > 0) {
-                    var length = obj.type[INPUTS_DATA].length;
-                    for (var i = 0; i < length; i++) {
-                        var tmp = obj.type[INPUTS_DATA][i];
+                for (var i = 0, len = types.length; i < len; i++) {
+                    var candidates = typesForNameSearch(types[i], bounds);
+                    for (var j = 0, clen = candidates.length; j < clen; j++) {
+                        var tmp = candidates[j];
                         if (typePassesFilter(typeFilter, tmp[1]) === false) {
                             continue;
                         }
@@ -976,33 +1004,240 @@ function defocusSearchBar() {
                 return literalSearch === true ? false : lev_distance;
             }
 
+            function findArg(obj, val, literalSearch, typeFilter) {
+                if (obj && obj.type && obj.type[INPUTS_DATA]) {
+                    return checkTypes(obj.type[INPUTS_DATA], obj.type[BOUNDS_DATA] || [], val,
+                                      literalSearch, typeFilter);
+                }
+                return literalSearch === true ? false : MAX_LEV_DISTANCE + 1;
+            }
+
             function checkReturned(obj, val, literalSearch, typeFilter) {
-                var lev_distance = MAX_LEV_DISTANCE + 1;
+                if (obj && obj.type && obj.type[OUTPUT_DATA]) {
+                    return checkTypes([obj.type[OUTPUT_DATA]], obj.type[BOUNDS_DATA] || [], val,
+                                      literalSearch, typeFilter);
+                }
+                return literalSearch === true ? false : MAX_LEV_DISTANCE + 1;
+            }
+
+            // Type signature queries, like `Vec<T>, usize -> Option<T>`, are parsed into a list of
+            // inputs and an output, which are `null` when they are `*` or left out. Each type is
+            // a `{name, generics}` object, with the associated types of `Trait<Assoc = Type>`, the
+            // arguments and output of `Fn(A) -> B`, and the elements of slices and tuples as
+            // generics. `&`, `mut`, `impl` and `dyn` are ignored.
+            function parseSignatureQuery(val) {
+                // Lifetimes are matched with `\x27` so that `tester.js` can extract this function.
+                var tokens = val.match(/->|[a-z0-9_]+(?:::[a-z0-9_]+)*|\x27[a-z_]+|\S/g) || [];
+                var pos = 0;
 
-                if (obj && obj.type && obj.type.length > OUTPUT_DATA) {
-                    var ret = obj.type[OUTPUT_DATA];
-                    if (typeof ret[0] === "string") {
-                        ret = [ret];
+                function peek(token) {
+                    return tokens[pos] === token;
+                }
+                function eat(token) {
+                    if (peek(token)) {
+                        pos += 1;
+                        return true;
                     }
-                    for (var x = 0, len = ret.length; x < len; ++x) {
-                        var tmp = ret[x];
-                        if (typePassesFilter(typeFilter, tmp[1]) === false) {
-                            continue;
+                    return false;
+                }
+                // Parses a list of types up to `end`, or up to the output for the inputs.
+                function parseList(end) {
+                    var types = [];
+                    while (pos < tokens.length && !peek(end) && !peek("->")) {
+                        var type = parseType();
+                        if (type !== null) {
+                            types.push(type);
                         }
-                        tmp = checkType(tmp, val, literalSearch);
-                        if (literalSearch === true) {
-                            if (tmp === true) {
-                                return true;
+                        if (!eat(",")) {
+                            break;
+                        }
+                    }
+                    eat(end);
+                    return types;
+                }
+                function parseType() {
+                    var token = tokens[pos];
+                    if (token === undefined) {
+                        return null;
+                    }
+                    pos += 1;
+                    if (token === "&" || token === "mut" || token === "impl" || token === "dyn" ||
+                        token.charAt(0) === "'") {
+                        return parseType();
+                    }
+                    if (token === "[") {
+                        var elem = parseType();
+                        while (pos < tokens.length && !eat("]")) {
+                            pos += 1;
+                        }
+                        return {name: "slice", generics: elem === null ? [] : [elem]};
+                    }
+                    if (token === "(") {
+                        var elems = parseList(")");
+                        if (elems.length === 1) {
+                            return elems[0];
+                        }
+                        return {name: elems.length === 0 ? "unit" : "tuple", generics: elems};
+                    }
+                    if (token === "!") {
+                        return {name: "never", generics: []};
+                    }
+                    if (!/^[a-z0-9_]/.test(token)) {
+                        return null;
+                    }
+                    var type = {name: token.split("::").pop(), generics: []};
+                    if (eat("<")) {
+                        while (pos < tokens.length && !eat(">")) {
+                            // `Trait<Assoc = Type>` is searched for like `Trait<Type>`.
+                            if (tokens[pos + 1] === "=") {
+                                pos += 2;
                             }
-                            continue;
+                            var generic = parseType();
+                            if (generic !== null) {
+                                type.generics.push(generic);
+                            }
+                            eat(",");
                         }
-                        lev_distance = Math.min(tmp, lev_distance);
-                        if (lev_distance === 0) {
-                            return 0;
+                    } else if (eat("(")) {
+                        type.generics = parseList(")");
+                        if (eat("->")) {
+                            var fnOutput = parseType();
+                            if (fnOutput !== null) {
+                                type.generics.push(fnOutput);
+                            }
                         }
                     }
+                    // Only the first bound of `impl TraitA + TraitB` is used.
+                    while (eat("+")) {
+                        parseType();
+                    }
+                    return type;
                 }
-                return literalSearch === true ? false : lev_distance;
+
+                var inputs = eat("*") ? null : parseList(null);
+                var output = null;
+                if (eat("->") && !eat("*")) {
+                    output = parseType();
+                }
+                return {inputs: inputs, output: output};
+            }
+
+            // Single letters in type signature queries stand for any type, as long as they stand
+            // for the same one everywhere.
+            function isTypeVariable(name) {
+                return /^[a-z]$/.test(name);
+            }
+
+            // Unifies `queryType` with `fnType`, a type of the function `ctx.row`, extending the
+            // bindings of the type variables in `env`. `cont` is called with the extended bindings,
+            // and the first of its results that isn't `null` is returned, so that it can reject
+            // them and let another way of unifying the types be tried.
+            function unifyType(queryType, fnType, ctx, env, cont) {
+                var name = fnType[NAME];
+                var i, len, res;
+                if (isTypeVariable(queryType.name)) {
+                    return bind(env, queryType.name, JSON.stringify(fnType), cont);
+                }
+                if (typeof name === "number") {
+                    // A generic parameter matches the traits it is bound by, or any type if it
+                    // has no bounds. The numbers of the parameters can't clash with the names of
+                    // the type variables in `env`.
+                    var bounds = ctx.bounds[name] || [];
+                    if (bounds.length === 0) {
+                        return bind(env, name, JSON.stringify(queryType), cont);
+                    }
+                    for (i = 0, len = bounds.length; i < len; ++i) {
+                        res = unifyType(queryType, bounds[i], ctx, env, cont);
+                        if (res !== null) {
+                            return res;
+                        }
+                    }
+                    return null;
+                }
+                if (name === "self") {
+                    var parent = ctx.row.parent;
+                    if (parent && parent.name.toLowerCase() === queryType.name) {
+                        return cont(env);
+                    }
+                    return null;
+                }
+                if (name !== queryType.name) {
+                    return null;
+                }
+                return unifyTypeList(queryType.generics, fnType[GENERICS_DATA] || [], ctx, env,
+                                     cont);
+            }
+
+            // Binds `variable` to `key` in `env` and calls `cont` with the new bindings, unless
+            // it is already bound to something else.
+            function bind(env, variable, key, cont) {
+                if (hasOwnProperty(env, variable)) {
+                    return env[variable] === key ? cont(env) : null;
+                }
+                var newEnv = {};
+                for (var v in env) {
+                    if (hasOwnProperty(env, v)) {
+                        newEnv[v] = env[v];
+                    }
+                }
+                newEnv[variable] = key;
+                return cont(newEnv);
+            }
+
+            // Unifies each of `queryTypes` with a different type of `fnTypes`, in any order.
+            function unifyTypeList(queryTypes, fnTypes, ctx, env, cont) {
+                if (queryTypes.length === 0) {
+                    return cont(env);
+                }
+                var rest = queryTypes.slice(1);
+                var unifyRest = function(others) {
+                    return function(env) {
+                        return unifyTypeList(rest, others, ctx, env, cont);
+                    };
+                };
+                for (var i = 0, len = fnTypes.length; i < len; ++i) {
+                    var others = fnTypes.slice(0, i).concat(fnTypes.slice(i + 1));
+                    var res = unifyType(queryTypes[0], fnTypes[i], ctx, env, unifyRest(others));
+                    if (res !== null) {
+                        return res;
+                    }
+                }
+                return null;
+            }
+
+            // Checks if the output of the function `row` matches the one of `query`, and if its
+            // inputs do too when `checkInputs` is `true`. Returns the number of generic parameters
+            // of the function standing for types of the query, so that exact matches go first, or
+            // `MAX_LEV_DISTANCE + 1` if it doesn't match.
+            function checkSignature(query, row, checkInputs) {
+                var ctx = {row: row, bounds: row.type[BOUNDS_DATA] || []};
+                var checkOutput = function(env) {
+                    var output = row.type[OUTPUT_DATA];
+                    if (query.output === null) {
+                        return env;
+                    } else if (!output) {
+                        return query.output.name === "unit" ? env : null;
+                    }
+                    return unifyType(query.output, output, ctx, env, function(env) {
+                        return env;
+                    });
+                };
+                var env;
+                if (checkInputs !== true || query.inputs === null) {
+                    env = checkOutput({});
+                } else {
+                    env = unifyTypeList(query.inputs, row.type[INPUTS_DATA], ctx, {}, checkOutput);
+                }
+                if (env === null) {
+                    return MAX_LEV_DISTANCE + 1;
+                }
+                var lev = 0;
+                for (var variable in env) {
+                    if (hasOwnProperty(env, variable) && !isTypeVariable(variable)) {
+                        lev += 1;
+                    }
+                }
+                return Math.min(lev, MAX_LEV_DISTANCE);
             }
 
             function checkPath(contains, lastElem, ty) {
@@ -1141,7 +1376,7 @@ function defocusSearchBar() {
 
             // quoted values mean literal search
             var nSearchWords = searchWords.length;
-            var i, it;
+            var i;
             var ty;
             var fullId;
             var returned;
@@ -1189,68 +1424,52 @@ function defocusSearchBar() {
                 query.search = val;
             // searching by type
             } else if (val.search("->") > -1) {
-                var trimmer = function(s) { return s.trim(); };
-                var parts = val.split("->").map(trimmer);
-                var input = parts[0];
-                // sort inputs so that order does not matter
-                var inputs = input.split(",").map(trimmer).sort();
-                for (i = 0, len = inputs.length; i < len; ++i) {
-                    inputs[i] = extractGenerics(inputs[i]);
-                }
-                var output = extractGenerics(parts[1]);
-
+                var signature = parseSignatureQuery(val);
                 for (i = 0; i < nSearchWords; ++i) {
                     if (filterCrates !== undefined && searchIndex[i].crate !== filterCrates) {
                         continue;
                     }
-                    var type = searchIndex[i].type;
                     ty = searchIndex[i];
-                    if (!type) {
+                    if (!ty.type) {
                         continue;
                     }
                     fullId = generateId(ty);
 
-                    returned = checkReturned(ty, output, true, NO_TYPE_FILTER);
-                    if (output.name === "*" || returned === true) {
-                        in_args = false;
-                        var is_module = false;
-
-                        if (input === "*") {
-                            is_module = true;
-                        } else {
-                            var allFound = true;
-                            for (it = 0, len = inputs.length; allFound === true && it < len; it++) {
-                                allFound = checkType(type, inputs[it], true);
-                            }
-                            in_args = allFound;
-                        }
-                        if (in_args === true) {
+                    returned = checkSignature(signature, ty, false);
+                    if (returned > MAX_LEV_DISTANCE) {
+                        continue;
+                    }
+                    if (signature.inputs === null) {
+                        results[fullId] = {
+                            id: i,
+                            index: -1,
+                            lev: returned,
+                            dontValidate: true,
+                        };
+                    } else {
+                        in_args = checkSignature(signature, ty, true);
+                        if (in_args <= MAX_LEV_DISTANCE) {
                             results_in_args[fullId] = {
                                 id: i,
                                 index: -1,
-                                dontValidate: true,
-                            };
-                        }
-                        if (returned === true) {
-                            results_returned[fullId] = {
-                                id: i,
-                                index: -1,
-                                dontValidate: true,
-                            };
-                        }
-                        if (is_module === true) {
-                            results[fullId] = {
-                                id: i,
-                                index: -1,
+                                lev: in_args,
                                 dontValidate: true,
                             };
                         }
                     }
+                    if (signature.output !== null) {
+                        results_returned[fullId] = {
+                            id: i,
+                            index: -1,
+                            lev: returned,
+                            dontValidate: true,
+                        };
+                    }
                 }
-                query.inputs = inputs.map(function(input) {
+                query.inputs = (signature.inputs || []).map(function(input) {
                     return input.name;
                 });
-                query.output = output.name;
+                query.output = signature.output === null ? "*" : signature.output.name;
             } else {
                 query.inputs = [val];
                 query.output = val;
@@ -1357,6 +1576,25 @@ function defocusSearchBar() {
                         results[fullId].lev = Math.min(results[fullId].lev, lev);
                     }
                 }
+
+                // Items whose documentation matches come after the ones whose name matches,
+                // unless they match both.
+                var bodyScores = fullTextScores(fullTextTerms(valLower), filterCrates);
+                for (var id in bodyScores) {
+                    if (!hasOwnProperty(bodyScores, id)) { continue; }
+                    ty = searchIndex[id];
+                    if (typePassesFilter(typeFilter, ty.ty) === false) { continue; }
+                    fullId = generateId(ty);
+                    if (results[fullId] === undefined) {
+                        results[fullId] = {
+                            id: parseInt(id, 10),
+                            index: -1,
+                            lev: MAX_LEV_DISTANCE + 1,
+                        };
+                    }
+                    results[fullId].bodyScore = bodyScores[id];
+                    results[fullId].dontValidate = true;
+                }
             }
 
             var ret = {
@@ -1368,6 +1606,141 @@ function defocusSearchBar() {
             return ret;
         }
 
+        /**
+         * Stems a word the same way as `full_text::stem` in rustdoc.
+         * @param  {string} word
+         * @return {string}
+         */
+        function stemWord(word) {
+            if (word.length <= 3) {
+                return word;
+            }
+
+            if (word.endsWith("sses")) {
+                word = word.slice(0, -2);
+            } else if (word.endsWith("ies")) {
+                word = word.slice(0, -3) + "y";
+            } else if (word.endsWith("s") && !word.endsWith("ss") && !word.endsWith("us")) {
+                word = word.slice(0, -1);
+            }
+
+            var suffixes = [["ation", "at"], ["ing", ""], ["ed", ""], ["ly", ""]];
+            for (var i = 0, len = suffixes.length; i < len; ++i) {
+                var suffix = suffixes[i][0], replacement = suffixes[i][1];
+                if (word.endsWith(suffix)) {
+                    var stem = word.slice(0, -suffix.length);
+                    if (stem.length + replacement.length >= 3) {
+                        word = stem + replacement;
+                        // `mapping` becomes `mapp`, which should be `map`.
+                        var last = word.charAt(word.length - 1);
+                        if (replacement === "" && last === word.charAt(word.length - 2) &&
+                            "lsz".indexOf(last) === -1) {
+                            word = word.slice(0, -1);
+                        }
+                    }
+                    break;
+                }
+            }
+
+            if (word.length > 3 && word.endsWith("e")) {
+                word = word.slice(0, -1);
+            }
+            return word;
+        }
+
+        /**
+         * Splits a query into terms the same way as `full_text::terms` in rustdoc, without
+         * duplicates.
+         * @param  {string} text
+         * @return {[string]}
+         */
+        function fullTextTerms(text) {
+            var terms = [];
+            var words = text.toLowerCase().split(/[^a-z0-9]+/);
+            for (var i = 0, len = words.length; i < len; ++i) {
+                if (words[i].length > 1 && FULL_TEXT_STOP_WORDS.indexOf(words[i]) === -1) {
+                    var term = stemWord(words[i]);
+                    if (terms.indexOf(term) === -1) {
+                        terms.push(term);
+                    }
+                }
+            }
+            return terms;
+        }
+
+        /**
+         * Finds the items whose documentation contains all the `terms`, in the full-text indexes
+         * that are loaded.
+         * @param  {[string]} terms
+         * @param  {string} filterCrates [Crate to search in if defined]
+         * @return {Object}              [The score of each item, keyed by its index]
+         */
+        function fullTextScores(terms, filterCrates) {
+            var scores = {};
+            if (terms.length === 0) {
+                return scores;
+            }
+            for (var crate in FULL_TEXT_INDEXES) {
+                if (!hasOwnProperty(FULL_TEXT_INDEXES, crate) || !FULL_TEXT_INDEXES[crate] ||
+                    (filterCrates !== undefined && crate !== filterCrates)) {
+                    continue;
+                }
+                var fullTextIndex = FULL_TEXT_INDEXES[crate];
+                var matches = {};
+                var i, len, item;
+                for (i = 0, len = terms.length; i < len; ++i) {
+                    if (!hasOwnProperty(fullTextIndex.t, terms[i])) {
+                        break;
+                    }
+                    // `postings` is a list of `[item, count, ...]`.
+                    var postings = fullTextIndex.t[terms[i]];
+                    // Rare terms count more than common ones.
+                    var weight = Math.log(1 + fullTextIndex.n * 2 / postings.length);
+                    for (var j = 0, nb = postings.length; j < nb; j += 2) {
+                        item = postings[j];
+                        if (matches[item] === undefined) {
+                            matches[item] = {terms: 0, score: 0};
+                        }
+                        matches[item].terms += 1;
+                        matches[item].score += (1 + Math.log(postings[j + 1])) * weight;
+                    }
+                }
+                if (i < len) {
+                    continue;
+                }
+                for (item in matches) {
+                    if (hasOwnProperty(matches, item) && matches[item].terms === terms.length) {
+                        scores[CRATE_OFFSETS[crate] + parseInt(item, 10)] = matches[item].score;
+                    }
+                }
+            }
+            return scores;
+        }
+
+        /**
+         * Loads the full-text indexes that haven't been yet, the search is run again as each of
+         * them arrives.
+         * @param  {string} filterCrates [Crate to search in if defined]
+         */
+        function loadFullTextIndexes(filterCrates) {
+            for (var crate in FULL_TEXT_INDEXES) {
+                if (hasOwnProperty(FULL_TEXT_INDEXES, crate) &&
+                    FULL_TEXT_INDEXES[crate] === null &&
+                    (filterCrates === undefined || crate === filterCrates)) {
+                    FULL_TEXT_INDEXES[crate] = false;
+                    loadScript(window.rootPath + "search-fulltext/" + crate +
+                        window.resourceSuffix + ".js");
+                }
+            }
+        }
+
+        window.addFullTextIndex = function(crate, fullTextIndex) {
+            FULL_TEXT_INDEXES[crate] = fullTextIndex;
+            if (search_input.value.trim().length !== 0) {
+                search(undefined, true);
+            }
+        };
+
         /**
          * Validate performs the following boolean logic. For example:
          * "File::open" will give IF A PARENT EXISTS => ("file" && "open")
@@ -1748,7 +2121,8 @@ function defocusSearchBar() {
                 return ret;
             }
 
-            var queries = query.raw.split(",");
+            // The commas of type signature queries separate their inputs, not several queries.
+            var queries = query.raw.indexOf("->") !== -1 ? [query.raw] : query.raw.split(",");
             var results = {
                 "in_args": [],
                 "returned": [],
@@ -1822,6 +2196,7 @@ function defocusSearchBar() {
             }
 
             var filterCrates = getFilterCrates();
+            loadFullTextIndexes(filterCrates);
             showResults(execSearch(query, index, filterCrates));
         }
 
@@ -1836,6 +2211,10 @@ function defocusSearchBar() {
 
                 var crateSize = 0;
 
+                CRATE_OFFSETS[crate] = currentIndex;
+                if (rawSearchIndex[crate].b) {
+                    FULL_TEXT_INDEXES[crate] = null;
+                }
                 searchWords.push(crate);
                 searchIndex.push({
                     crate: crate,
@@ -2894,8 +3273,9 @@ function defocusSearchBar() {
             "Accepted kinds are: <code>fn</code>, <code>mod</code>, <code>struct</code>, \
              <code>enum</code>, <code>trait</code>, <code>type</code>, <code>macro</code>, \
              and <code>const</code>.",
-            "Search functions by type signature (e.g., <code>vec -&gt; usize</code> or \
-             <code>* -&gt; vec</code>)",
+            "Search functions by type signature (e.g., <code>vec -&gt; usize</code>, \
+             <code>* -&gt; vec</code> or <code>Vec&lt;T&gt;, usize -&gt; Option&lt;T&gt;</code>, \
+             where single letters stand for any type).",
             "Search multiple things at once by splitting your query with comma (e.g., \
              <code>str,u8</code> or <code>String,struct:Vec,test</code>)",
             "You can look for items with an exact name by putting double quotes around \
@@ -0,0 +1,755 @@
+//! Comparison of the public API of two versions of a crate, as described by the JSON output of
+//! rustdoc, to find out which version bump the changes between them require under [semver].
+//!
+//! Only the information in the JSON output is taken into account, so some changes are classified
+//! more conservatively than they could be. For example, adding a required method to a trait is
+//! always a major change, even if the trait is sealed.
+//!
+//! [semver]: https://doc.rust-lang.org/cargo/reference/semver.html
+
+#![allow(rustc::default_hash_types)]
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use rustdoc_json_types::*;
+
+#[cfg(test)]
+mod tests;
+
+/// How much a change breaks the users of a crate, from least to most.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+crate enum Severity {
+    /// Needs a new patch version, like changes to the documentation.
+    Patch,
+    /// Needs a new minor version, like new items.
+    Minor,
+    /// Needs a new major version, like removed items or changed signatures.
+    Major,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Patch => "patch",
+            Severity::Minor => "minor",
+            Severity::Major => "major",
+        })
+    }
+}
+
+crate struct Change {
+    crate severity: Severity,
+    crate description: String,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.description)
+    }
+}
+
+/// Prints the changes from `baseline` to `new`, two files written by `--output-format json`.
+/// Fails if the changes need a bigger version bump than the one between the `--crate-version`s
+/// the files were generated with.
+crate fn run(baseline: &Path, new: &Path) -> Result<(), String> {
+    let old = load(baseline)?;
+    let new = load(new)?;
+
+    let changes = diff(&old, &new);
+    for change in &changes {
+        println!("{}", change);
+    }
+
+    let required = changes.iter().map(|change| change.severity).max();
+    let old_version = old.crate_version.as_deref().and_then(parse_version);
+    let new_version = new.crate_version.as_deref().and_then(parse_version);
+    match (required, old_version, new_version) {
+        (Some(required), Some(old_version), Some(new_version))
+            if allowed_severity(old_version, new_version) < required =>
+        {
+            Err(format!(
+                "these changes require a new {} version, but the version went from {} to {}",
+                required,
+                old.crate_version.unwrap(),
+                new.crate_version.unwrap()
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn load(path: &Path) -> Result<Crate, String> {
+    let error = |e: &dyn fmt::Display| format!("failed to load `{}`: {}", path.display(), e);
+    let contents = fs::read_to_string(path).map_err(|e| error(&e))?;
+    let value: serde_json::Value = serde_json::from_str(&contents).map_err(|e| error(&e))?;
+    // Check the version first, deserializing another version would fail with a confusing error.
+    if value["format_version"] != FORMAT_VERSION {
+        let message = format!(
+            "format version {} is not supported, only {} is",
+            value["format_version"], FORMAT_VERSION
+        );
+        return Err(error(&message));
+    }
+    serde_json::from_value(value).map_err(|e| error(&e))
+}
+
+/// Parses the `major.minor.patch` part of a semver version.
+fn parse_version(version: &str) -> Option<[u64; 3]> {
+    let version = version.split(|c| c == '-' || c == '+').next()?;
+    let mut parts = version.split('.').map(|part| part.parse().ok());
+    let version = [parts.next()??, parts.next()??, parts.next()??];
+    if parts.next().is_some() { None } else { Some(version) }
+}
+
+/// The most severe change allowed when going from version `old` to `new`. Like in Cargo, the
+/// first non-zero component is the major version: `0.2.0` and `0.3.0` are incompatible.
+fn allowed_severity(old: [u64; 3], new: [u64; 3]) -> Severity {
+    let major = old.iter().position(|&component| component != 0).unwrap_or(2);
+    if new[..=major] != old[..=major] {
+        Severity::Major
+    } else if major < 2 && new[major + 1] != old[major + 1] {
+        Severity::Minor
+    } else {
+        Severity::Patch
+    }
+}
+
+/// Lists the changes to the public API between `old` and `new`.
+crate fn diff(old: &Crate, new: &Crate) -> Vec<Change> {
+    let old = Api::new(old);
+    let new = Api::new(new);
+    let mut changes = Vec::new();
+
+    for (path, old_entry) in &old.entries {
+        match new.entries.get(path) {
+            Some(new_entry) => compare(&old, old_entry, &new, new_entry, path, &mut changes),
+            // Only the removal of the parent is reported.
+            None if old_entry.parent.as_ref().map_or(false, |p| !new.entries.contains_key(p)) => {}
+            None => {
+                let item = old_entry.item;
+                let description = match new.krate.index.get(&item.id) {
+                    Some(new_item) if new_item.visibility != Visibility::Public => {
+                        format!("`{}` is no longer public", path)
+                    }
+                    _ => format!("removed {} `{}`", kind(item), path),
+                };
+                // Removing a negative impl means the trait may now be implemented.
+                let severity = match &item.inner {
+                    ItemEnum::Impl(impl_) if impl_.negative => Severity::Minor,
+                    _ => Severity::Major,
+                };
+                changes.push(Change { severity, description });
+            }
+        }
+    }
+
+    for (path, new_entry) in &new.entries {
+        if old.entries.contains_key(path)
+            || new_entry.parent.as_ref().map_or(false, |p| !old.entries.contains_key(p))
+        {
+            continue;
+        }
+        let item = new_entry.item;
+        let old_item = old.krate.index.get(&item.id);
+        if old_item.map_or(false, |old_item| old_item.visibility != Visibility::Public) {
+            changes.push(Change {
+                severity: Severity::Minor,
+                description: format!("`{}` is now public", path),
+            });
+            continue;
+        }
+
+        let description = format!("added {} `{}`", kind(item), path);
+        let parent = new_entry.parent.as_ref().map(|parent| old.entries[parent].item);
+        changes.push(match parent.and_then(|parent| breaking_addition(item, parent)) {
+            Some(reason) => Change {
+                severity: Severity::Major,
+                description: format!("{} {}", description, reason),
+            },
+            None => Change { severity: Severity::Minor, description },
+        });
+    }
+
+    changes
+}
+
+/// Lists the changes to an item that is part of both `old` and `new`.
+fn compare(
+    old: &Api<'_>,
+    old_entry: &Entry<'_>,
+    new: &Api<'_>,
+    new_entry: &Entry<'_>,
+    path: &str,
+    changes: &mut Vec<Change>,
+) {
+    let (old_item, new_item) = (old_entry.item, new_entry.item);
+    let mut push = |severity, description| changes.push(Change { severity, description });
+
+    let (old_signature, new_signature) = (old.signature(old_item), new.signature(new_item));
+    if old_signature != new_signature {
+        push(
+            Severity::Major,
+            format!(
+                "changed {} `{}` from `{}` to `{}`",
+                kind(new_item),
+                path,
+                old_signature,
+                new_signature
+            ),
+        );
+    }
+
+    match (is_non_exhaustive(old_item), is_non_exhaustive(new_item)) {
+        (false, true) => push(Severity::Major, format!("`{}` is now #[non_exhaustive]", path)),
+        (true, false) => {
+            push(Severity::Minor, format!("`{}` is no longer #[non_exhaustive]", path))
+        }
+        _ => {}
+    }
+
+    if let (ItemEnum::Struct(old_struct), ItemEnum::Struct(new_struct)) =
+        (&old_item.inner, &new_item.inner)
+    {
+        match (old_struct.fields_stripped, new_struct.fields_stripped) {
+            (false, true) => push(Severity::Major, format!("`{}` now has private fields", path)),
+            (true, false) => {
+                push(Severity::Minor, format!("`{}` no longer has private fields", path))
+            }
+            _ => {}
+        }
+    }
+
+    // Only trait items can have defaults.
+    if let Some(ItemEnum::Trait(_)) = old_entry.parent.as_ref().map(|p| &old.entries[p].item.inner)
+    {
+        match (has_default(old_item), has_default(new_item)) {
+            (true, false) => push(Severity::Major, format!("`{}` no longer has a default", path)),
+            (false, true) => push(Severity::Minor, format!("`{}` now has a default", path)),
+            _ => {}
+        }
+    }
+
+    match (&old_item.deprecation, &new_item.deprecation) {
+        (None, Some(_)) => push(Severity::Minor, format!("deprecated `{}`", path)),
+        (Some(_), None) => push(Severity::Patch, format!("`{}` is no longer deprecated", path)),
+        _ => {}
+    }
+
+    if old_item.docs != new_item.docs {
+        push(Severity::Patch, format!("changed the documentation of `{}`", path));
+    }
+}
+
+/// Why adding `item` to `parent`, which is already public, is a major change, if it is.
+fn breaking_addition(item: &Item, parent: &Item) -> Option<&'static str> {
+    match (&item.inner, &parent.inner) {
+        (ItemEnum::StructField(_), ItemEnum::Struct(Struct { fields_stripped: false, .. }))
+        | (ItemEnum::StructField(_), ItemEnum::Variant(_))
+            if !is_non_exhaustive(parent) =>
+        {
+            Some("which breaks struct expressions and patterns")
+        }
+        (ItemEnum::Variant(_), ItemEnum::Enum(_)) if !is_non_exhaustive(parent) => {
+            Some("which breaks exhaustive matches")
+        }
+        (_, ItemEnum::Trait(_)) if !has_default(item) => {
+            Some("without a default, which breaks implementations")
+        }
+        _ => None,
+    }
+}
+
+fn is_non_exhaustive(item: &Item) -> bool {
+    item.attrs.iter().any(|attr| attr == "#[non_exhaustive]")
+}
+
+/// Whether a trait item has a default, i.e. doesn't need to be given by implementations.
+fn has_default(item: &Item) -> bool {
+    match &item.inner {
+        ItemEnum::Method(method) => method.has_body,
+        ItemEnum::AssocConst { default, .. } => default.is_some(),
+        ItemEnum::AssocType { default, .. } => default.is_some(),
+        _ => true,
+    }
+}
+
+fn kind(item: &Item) -> &'static str {
+    match &item.inner {
+        ItemEnum::Module(_) => "module",
+        ItemEnum::ExternCrate { .. } => "extern crate",
+        ItemEnum::Import(_) => "re-export",
+        ItemEnum::Union(_) => "union",
+        ItemEnum::Struct(_) => "struct",
+        ItemEnum::StructField(_) => "field",
+        ItemEnum::Enum(_) => "enum",
+        ItemEnum::Variant(_) => "variant",
+        ItemEnum::Function(_) => "function",
+        ItemEnum::Trait(_) => "trait",
+        ItemEnum::TraitAlias(_) => "trait alias",
+        ItemEnum::Method(_) => "method",
+        ItemEnum::Impl(impl_) if impl_.synthetic => "auto trait impl",
+        ItemEnum::Impl(_) => "impl",
+        ItemEnum::Typedef(_) => "type alias",
+        ItemEnum::OpaqueTy(_) => "opaque type",
+        ItemEnum::Constant(_) => "constant",
+        ItemEnum::Static(_) => "static",
+        ItemEnum::ForeignType => "foreign type",
+        ItemEnum::Macro(_) => "macro",
+        ItemEnum::ProcMacro(_) => "proc macro",
+        ItemEnum::AssocConst { .. } => "associated constant",
+        ItemEnum::AssocType { .. } => "associated type",
+    }
+}
+
+fn items<'a>(krate: &'a Crate, ids: &'a [Id]) -> impl Iterator<Item = &'a Item> {
+    ids.iter().filter_map(move |id| krate.index.get(id))
+}
+
+fn name(item: &Item) -> &str {
+    item.name.as_deref().unwrap_or("_")
+}
+
+struct Entry<'a> {
+    item: &'a Item,
+    /// The path of the item this one is a part of, like the module of an item, the struct of a
+    /// field or the type of an impl. Only the crate root has none.
+    parent: Option<String>,
+}
+
+/// The public API of a crate.
+struct Api<'a> {
+    krate: &'a Crate,
+    /// The public items, keyed by the path they can be named with. Items that can't be named,
+    /// like trait impls, are keyed by a description of what they are instead.
+    entries: BTreeMap<String, Entry<'a>>,
+    /// The modules whose items are being added, to stop at cyclic re-exports.
+    module_stack: Vec<&'a Id>,
+}
+
+impl<'a> Api<'a> {
+    fn new(krate: &'a Crate) -> Self {
+        let mut api = Api { krate, entries: BTreeMap::new(), module_stack: Vec::new() };
+        let root = &krate.index[&krate.root];
+        api.add_item(root, name(root).to_string(), None);
+        api
+    }
+
+    fn add_item(&mut self, item: &'a Item, path: String, parent: Option<&str>) {
+        if self.entries.contains_key(&path) {
+            return;
+        }
+        let parent = parent.map(str::to_string);
+        self.entries.insert(path.clone(), Entry { item, parent });
+
+        let krate = self.krate;
+        match &item.inner {
+            ItemEnum::Module(_) => self.add_module_items(item, &path),
+            ItemEnum::Struct(Struct { fields, impls, .. })
+            | ItemEnum::Union(Union { fields, impls, .. }) => {
+                self.add_fields(fields, &path, false);
+                self.add_impls(impls, &path);
+            }
+            ItemEnum::Enum(enum_) => {
+                for variant in items(krate, &enum_.variants) {
+                    let variant_path = format!("{}::{}", path, name(variant));
+                    self.add_item(variant, variant_path, Some(&*path));
+                }
+                self.add_impls(&enum_.impls, &path);
+            }
+            // The fields of variants are as public as the enum.
+            ItemEnum::Variant(Variant::Struct(fields)) => self.add_fields(fields, &path, true),
+            ItemEnum::Trait(trait_) => {
+                for trait_item in items(krate, &trait_.items) {
+                    let trait_item_path = format!("{}::{}", path, name(trait_item));
+                    self.add_item(trait_item, trait_item_path, Some(&*path));
+                }
+                self.add_impls(&trait_.implementors, &path);
+            }
+            _ => {}
+        }
+    }
+
+    fn add_module_items(&mut self, module: &'a Item, path: &str) {
+        let module_items = match &module.inner {
+            ItemEnum::Module(module) => &module.items,
+            _ => return,
+        };
+        if self.module_stack.contains(&&module.id) {
+            return;
+        }
+        self.module_stack.push(&module.id);
+
+        let krate = self.krate;
+        for item in items(krate, module_items) {
+            if item.visibility != Visibility::Public {
+                continue;
+            }
+            match &item.inner {
+                // Re-exports of local items are added as the items themselves.
+                ItemEnum::Import(import) => {
+                    match import.id.as_ref().and_then(|id| krate.index.get(id)) {
+                        Some(target) if import.glob => self.add_module_items(target, path),
+                        Some(target) => {
+                            self.add_item(target, format!("{}::{}", path, import.name), Some(path))
+                        }
+                        None if import.glob => {
+                            let key = format!("{}::{{use {}::*}}", path, import.span);
+                            self.add_item(item, key, Some(path))
+                        }
+                        None => {
+                            self.add_item(item, format!("{}::{}", path, import.name), Some(path))
+                        }
+                    }
+                }
+                _ => self.add_item(item, format!("{}::{}", path, name(item)), Some(path)),
+            }
+        }
+
+        self.module_stack.pop();
+    }
+
+    fn add_fields(&mut self, fields: &'a [Id], path: &str, all_public: bool) {
+        for field in items(self.krate, fields) {
+            if all_public || field.visibility == Visibility::Public {
+                self.add_item(field, format!("{}::{}", path, name(field)), Some(path));
+            }
+        }
+    }
+
+    fn add_impls(&mut self, impls: &'a [Id], path: &str) {
+        let krate = self.krate;
+        for item in items(krate, impls) {
+            let impl_ = match &item.inner {
+                ItemEnum::Impl(impl_) => impl_,
+                _ => continue,
+            };
+            match &impl_.trait_ {
+                // The items of trait impls are the ones of the trait.
+                Some(trait_) => {
+                    let negative = if impl_.negative { "!" } else { "" };
+                    let trait_ = self.ty(trait_);
+                    let key = format!("impl {}{} for {}", negative, trait_, self.ty(&impl_.for_));
+                    self.add_item(item, key, Some(path));
+                }
+                // Inherent impls are only containers for their items.
+                None => {
+                    for impl_item in items(krate, &impl_.items) {
+                        if impl_item.visibility == Visibility::Public {
+                            let impl_item_path = format!("{}::{}", path, name(impl_item));
+                            self.add_item(impl_item, impl_item_path, Some(path));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// A summary of everything about `item` that users can rely on, apart from its children
+    /// which are compared on their own.
+    fn signature(&self, item: &Item) -> String {
+        match &item.inner {
+            ItemEnum::Module(_) => "mod".to_string(),
+            ItemEnum::ExternCrate { name, rename: Some(rename) } => {
+                format!("extern crate {} as {}", name, rename)
+            }
+            ItemEnum::ExternCrate { name, rename: None } => format!("extern crate {}", name),
+            ItemEnum::Import(import) => {
+                let target = match &import.id {
+                    Some(id) => self.path(id, &import.span),
+                    None => import.span.clone(),
+                };
+                format!("use {}{}", target, if import.glob { "::*" } else { "" })
+            }
+            ItemEnum::Union(union_) => format!(
+                "union{}{}",
+                self.generics(&union_.generics),
+                self.where_clause(&union_.generics)
+            ),
+            ItemEnum::Struct(struct_) => format!(
+                "struct{}{}{}",
+                self.generics(&struct_.generics),
+                match struct_.struct_type {
+                    StructType::Plain => " {..}",
+                    StructType::Tuple => "(..)",
+                    StructType::Unit => "",
+                },
+                self.where_clause(&struct_.generics)
+            ),
+            ItemEnum::StructField(ty) => self.ty(ty),
+            ItemEnum::Enum(enum_) => format!(
+                "enum{}{}",
+                self.generics(&enum_.generics),
+                self.where_clause(&enum_.generics)
+            ),
+            ItemEnum::Variant(Variant::Plain) => "variant".to_string(),
+            ItemEnum::Variant(Variant::Tuple(types)) => format!("variant({})", self.types(types)),
+            ItemEnum::Variant(Variant::Struct(_)) => "variant {..}".to_string(),
+            ItemEnum::Function(f) => self.fn_signature(&f.decl, &f.generics, &f.header, &f.abi),
+            ItemEnum::Method(m) => self.fn_signature(&m.decl, &m.generics, &m.header, &m.abi),
+            ItemEnum::Trait(trait_) => format!(
+                "{}{}trait{}{}{}",
+                if trait_.is_unsafe { "unsafe " } else { "" },
+                if trait_.is_auto { "auto " } else { "" },
+                self.generics(&trait_.generics),
+                self.bounds(": ", &trait_.bounds),
+                self.where_clause(&trait_.generics)
+            ),
+            ItemEnum::TraitAlias(alias) => format!(
+                "trait{}{}",
+                self.generics(&alias.generics),
+                self.bounds(" = ", &alias.params)
+            ),
+            ItemEnum::Impl(impl_) => format!(
+                "{}impl{} {}{} for {}{}",
+                if impl_.is_unsafe { "unsafe " } else { "" },
+                self.generics(&impl_.generics),
+                if impl_.negative { "!" } else { "" },
+                impl_.trait_.as_ref().map(|trait_| self.ty(trait_)).unwrap_or_default(),
+                self.ty(&impl_.for_),
+                self.where_clause(&impl_.generics)
+            ),
+            ItemEnum::Typedef(typedef) => format!(
+                "type{} = {}{}",
+                self.generics(&typedef.generics),
+                self.ty(&typedef.type_),
+                self.where_clause(&typedef.generics)
+            ),
+            ItemEnum::OpaqueTy(opaque) => format!(
+                "type{} = impl{}",
+                self.generics(&opaque.generics),
+                self.bounds(" ", &opaque.bounds)
+            ),
+            ItemEnum::Constant(constant) => format!("const: {}", self.ty(&constant.type_)),
+            ItemEnum::Static(static_) => format!(
+                "static{}: {}",
+                if static_.mutable { " mut" } else { "" },
+                self.ty(&static_.type_)
+            ),
+            ItemEnum::ForeignType => "extern type".to_string(),
+            // Changes to the rules of a macro can't be classified.
+            ItemEnum::Macro(_) => "macro".to_string(),
+            ItemEnum::ProcMacro(proc_macro) => format!(
+                "{} macro{}",
+                match proc_macro.kind {
+                    MacroKind::Bang => "function-like",
+                    MacroKind::Attr => "attribute",
+                    MacroKind::Derive => "derive",
+                },
+                match &proc_macro.helpers[..] {
+                    [] => String::new(),
+                    helpers => format!(" with attributes({})", helpers.join(", ")),
+                }
+            ),
+            ItemEnum::AssocConst { type_, .. } => format!("const: {}", self.ty(type_)),
+            ItemEnum::AssocType { bounds, .. } => format!("type{}", self.bounds(": ", bounds)),
+        }
+    }
+
+    fn fn_signature(
+        &self,
+        decl: &FnDecl,
+        generics: &Generics,
+        header: &HashSet<Qualifiers>,
+        abi: &str,
+    ) -> String {
+        format!(
+            "{}fn{}{}{}",
+            self.qualifiers(header, abi),
+            self.generics(generics),
+            self.fn_decl(decl),
+            self.where_clause(generics)
+        )
+    }
+
+    fn qualifiers(&self, header: &HashSet<Qualifiers>, abi: &str) -> String {
+        let mut qualifiers = String::new();
+        for (qualifier, s) in &[
+            (Qualifiers::Const, "const "),
+            (Qualifiers::Async, "async "),
+            (Qualifiers::Unsafe, "unsafe "),
+        ] {
+            if header.contains(qualifier) {
+                qualifiers.push_str(s);
+            }
+        }
+        if abi != "\"Rust\"" {
+            qualifiers.push_str(&format!("extern {} ", abi));
+        }
+        qualifiers
+    }
+
+    /// The types of the arguments and the return type, names of arguments don't matter.
+    fn fn_decl(&self, decl: &FnDecl) -> String {
+        let mut inputs: Vec<_> = decl.inputs.iter().map(|(_, ty)| self.ty(ty)).collect();
+        if decl.c_variadic {
+            inputs.push("...".to_string());
+        }
+        match &decl.output {
+            Some(output) => format!("({}) -> {}", inputs.join(", "), self.ty(output)),
+            None => format!("({})", inputs.join(", ")),
+        }
+    }
+
+    fn generics(&self, generics: &Generics) -> String {
+        self.generic_params(&generics.params)
+    }
+
+    fn generic_params(&self, params: &[GenericParamDef]) -> String {
+        if params.is_empty() {
+            return String::new();
+        }
+        let params: Vec<_> = params
+            .iter()
+            .map(|param| match &param.kind {
+                GenericParamDefKind::Lifetime => param.name.clone(),
+                GenericParamDefKind::Type { bounds, default } => format!(
+                    "{}{}{}",
+                    param.name,
+                    self.bounds(": ", bounds),
+                    default.as_ref().map(|ty| format!(" = {}", self.ty(ty))).unwrap_or_default()
+                ),
+                GenericParamDefKind::Const(ty) => format!("const {}: {}", param.name, self.ty(ty)),
+            })
+            .collect();
+        format!("<{}>", params.join(", "))
+    }
+
+    fn where_clause(&self, generics: &Generics) -> String {
+        if generics.where_predicates.is_empty() {
+            return String::new();
+        }
+        let predicates: Vec<_> = generics
+            .where_predicates
+            .iter()
+            .map(|predicate| match predicate {
+                WherePredicate::BoundPredicate { ty, bounds } => {
+                    format!("{}{}", self.ty(ty), self.bounds(": ", bounds))
+                }
+                WherePredicate::RegionPredicate { lifetime, bounds } => {
+                    format!("{}{}", lifetime, self.bounds(": ", bounds))
+                }
+                WherePredicate::EqPredicate { lhs, rhs } => {
+                    format!("{} == {}", self.ty(lhs), self.ty(rhs))
+                }
+            })
+            .collect();
+        format!(" where {}", predicates.join(", "))
+    }
+
+    /// `bounds` joined with ` + ` and preceded by `prefix`, or nothing if there are no bounds.
+    fn bounds(&self, prefix: &str, bounds: &[GenericBound]) -> String {
+        if bounds.is_empty() {
+            return String::new();
+        }
+        let bounds: Vec<_> = bounds
+            .iter()
+            .map(|bound| match bound {
+                GenericBound::TraitBound { trait_, generic_params, modifier } => {
+                    let for_lifetimes = match self.generic_params(generic_params) {
+                        params if params.is_empty() => params,
+                        params => format!("for{} ", params),
+                    };
+                    let modifier = match modifier {
+                        TraitBoundModifier::None => "",
+                        TraitBoundModifier::Maybe => "?",
+                        TraitBoundModifier::MaybeConst => "?const ",
+                    };
+                    format!("{}{}{}", for_lifetimes, modifier, self.ty(trait_))
+                }
+                GenericBound::Outlives(lifetime) => lifetime.clone(),
+            })
+            .collect();
+        format!("{}{}", prefix, bounds.join(" + "))
+    }
+
+    fn types(&self, types: &[Type]) -> String {
+        types.iter().map(|ty| self.ty(ty)).collect::<Vec<_>>().join(", ")
+    }
+
+    /// The full path of the item `id`, or `name` if it isn't known.
+    fn path(&self, id: &Id, name: &str) -> String {
+        match self.krate.paths.get(id) {
+            Some(summary) => summary.path.join("::"),
+            None => name.to_string(),
+        }
+    }
+
+    fn ty(&self, ty: &Type) -> String {
+        match ty {
+            Type::ResolvedPath { name, id, args, param_names } => {
+                let path = format!(
+                    "{}{}",
+                    self.path(id, name),
+                    args.as_ref().map(|args| self.generic_args(args)).unwrap_or_default()
+                );
+                // Trait objects with more than one bound.
+                if param_names.is_empty() {
+                    path
+                } else {
+                    format!("dyn {}{}", path, self.bounds(" + ", param_names))
+                }
+            }
+            Type::Generic(name) | Type::Primitive(name) => name.clone(),
+            Type::FunctionPointer(f) => format!(
+                "{}{}fn{}",
+                match self.generic_params(&f.generic_params) {
+                    params if params.is_empty() => params,
+                    params => format!("for{} ", params),
+                },
+                self.qualifiers(&f.header, &f.abi),
+                self.fn_decl(&f.decl)
+            ),
+            Type::Tuple(types) => format!("({})", self.types(types)),
+            Type::Slice(ty) => format!("[{}]", self.ty(ty)),
+            Type::Array { type_, len } => format!("[{}; {}]", self.ty(type_), len),
+            Type::ImplTrait(bounds) => format!("impl{}", self.bounds(" ", bounds)),
+            Type::Never => "!".to_string(),
+            Type::Infer => "_".to_string(),
+            Type::RawPointer { mutable, type_ } => {
+                format!("*{} {}", if *mutable { "mut" } else { "const" }, self.ty(type_))
+            }
+            Type::BorrowedRef { lifetime, mutable, type_ } => format!(
+                "&{}{}{}",
+                lifetime.as_ref().map(|lifetime| format!("{} ", lifetime)).unwrap_or_default(),
+                if *mutable { "mut " } else { "" },
+                self.ty(type_)
+            ),
+            Type::QualifiedPath { name, self_type, trait_ } => {
+                format!("<{} as {}>::{}", self.ty(self_type), self.ty(trait_), name)
+            }
+        }
+    }
+
+    fn generic_args(&self, args: &GenericArgs) -> String {
+        match args {
+            GenericArgs::AngleBracketed { args, bindings } => {
+                let args = args
+                    .iter()
+                    .map(|arg| match arg {
+                        GenericArg::Lifetime(lifetime) => lifetime.clone(),
+                        GenericArg::Type(ty) => self.ty(ty),
+                        GenericArg::Const(constant) => constant.expr.clone(),
+                    })
+                    .chain(bindings.iter().map(|binding| match &binding.binding {
+                        TypeBindingKind::Equality(ty) => {
+                            format!("{} = {}", binding.name, self.ty(ty))
+                        }
+                        TypeBindingKind::Constraint(bounds) => {
+                            format!("{}{}", binding.name, self.bounds(": ", bounds))
+                        }
+                    }))
+                    .collect::<Vec<_>>();
+                if args.is_empty() { String::new() } else { format!("<{}>", args.join(", ")) }
+            }
+            GenericArgs::Parenthesized { inputs, output: Some(output) } => {
+                format!("({}) -> {}", self.types(inputs), self.ty(output))
+            }
+            GenericArgs::Parenthesized { inputs, output: None } => {
+                format!("({})", self.types(inputs))
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use super::{allowed_severity, parse_version, Severity};
+
+#[test]
+fn test_parse_version() {
+    assert_eq!(parse_version("1.2.3"), Some([1, 2, 3]));
+    assert_eq!(parse_version("0.10.0-alpha.1+build.5"), Some([0, 10, 0]));
+    assert_eq!(parse_version("1.2"), None);
+    assert_eq!(parse_version("1.2.3.4"), None);
+    assert_eq!(parse_version("1.x.3"), None);
+}
+
+#[test]
+fn test_allowed_severity() {
+    assert_eq!(allowed_severity([1, 2, 3], [1, 2, 4]), Severity::Patch);
+    assert_eq!(allowed_severity([1, 2, 3], [1, 3, 0]), Severity::Minor);
+    assert_eq!(allowed_severity([1, 2, 3], [2, 0, 0]), Severity::Major);
+    assert_eq!(allowed_severity([1, 2, 3], [1, 2, 3]), Severity::Patch);
+    // The first non-zero component is the major version.
+    assert_eq!(allowed_severity([0, 2, 3], [0, 2, 4]), Severity::Minor);
+    assert_eq!(allowed_severity([0, 2, 3], [0, 3, 0]), Severity::Major);
+    assert_eq!(allowed_severity([0, 0, 3], [0, 0, 4]), Severity::Major);
+}
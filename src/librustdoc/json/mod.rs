@@ -5,6 +5,7 @@
 //! docs for usage and details.
 
 mod conversions;
+crate mod diff;
 
 use std::cell::RefCell;
 use std::fs::File;
@@ -14,6 +15,7 @@
 use rustc_data_structures::fx::FxHashMap;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::Session;
+use rustc_span::def_id::LOCAL_CRATE;
 use rustc_span::edition::Edition;
 
 use rustdoc_json_types as types;
@@ -24,7 +26,7 @@
 use crate::formats::cache::Cache;
 use crate::formats::FormatRenderer;
 use crate::html::render::cache::ExternalLocation;
-use crate::json::conversions::from_def_id;
+use crate::json::conversions::{from_def_id, from_item_id, IntoWithTcx};
 
 #[derive(Clone)]
 crate struct JsonRenderer<'tcx> {
@@ -52,7 +54,7 @@ fn get_trait_implementors(&mut self, id: rustc_span::def_id::DefId) -> Vec<types
                     .map(|i| {
                         let item = &i.impl_item;
                         self.item(item.clone()).unwrap();
-                        from_def_id(item.def_id)
+                        from_item_id(item, self.tcx)
                     })
                     .collect()
             })
@@ -70,7 +72,7 @@ fn get_impls(&mut self, id: rustc_span::def_id::DefId) -> Vec<types::Id> {
                         let item = &i.impl_item;
                         if item.def_id.is_local() {
                             self.item(item.clone()).unwrap();
-                            Some(from_def_id(item.def_id))
+                            Some(from_item_id(item, self.tcx))
                         } else {
                             None
                         }
@@ -90,9 +92,9 @@ fn get_trait_items(&mut self) -> Vec<(types::Id, types::Item)> {
                     let trait_item = &trait_item.trait_;
                     trait_item.items.clone().into_iter().for_each(|i| self.item(i).unwrap());
                     Some((
-                        from_def_id(id),
+                        from_def_id(id, self.tcx),
                         types::Item {
-                            id: from_def_id(id),
+                            id: from_def_id(id, self.tcx),
                             crate_id: id.krate.as_u32(),
                             name: self
                                 .cache
@@ -108,12 +110,13 @@ fn get_trait_items(&mut self) -> Vec<(types::Id, types::Item)> {
                                 .last()
                                 .map(Clone::clone),
                             visibility: types::Visibility::Public,
-                            inner: types::ItemEnum::Trait(trait_item.clone().into()),
+                            inner: types::ItemEnum::Trait(trait_item.clone().into_tcx(self.tcx)),
                             source: None,
                             docs: Default::default(),
                             links: Default::default(),
                             attrs: Default::default(),
                             deprecation: Default::default(),
+                            layout: None,
                         },
                     ))
                 } else {
@@ -168,7 +171,7 @@ fn item(&mut self, item: clean::Item) -> Result<(), Error> {
             } else if let types::ItemEnum::Enum(ref mut e) = new_item.inner {
                 e.impls = self.get_impls(id)
             }
-            let removed = self.index.borrow_mut().insert(from_def_id(id), new_item.clone());
+            let removed = self.index.borrow_mut().insert(new_item.id.clone(), new_item.clone());
             // FIXME(adotinthevoid): Currently, the index is duplicated. This is a sanity check
             // to make sure the items are unique.
             if let Some(old_item) = removed {
@@ -212,7 +215,7 @@ fn after_krate(
         // rustdoc-json
         #[allow(rustc::default_hash_types)]
         let output = types::Crate {
-            root: types::Id(String::from("0:0")),
+            root: from_def_id(LOCAL_CRATE.as_def_id(), self.tcx),
             crate_version: self.cache.crate_version.clone(),
             includes_private: self.cache.document_private,
             index: index.into_iter().collect(),
@@ -224,7 +227,7 @@ fn after_krate(
                 .chain(self.cache.external_paths.clone().into_iter())
                 .map(|(k, (path, kind))| {
                     (
-                        from_def_id(k),
+                        from_def_id(k, self.tcx),
                         types::ItemSummary { crate_id: k.krate.as_u32(), path, kind: kind.into() },
                     )
                 })
@@ -246,7 +249,7 @@ fn after_krate(
                     )
                 })
                 .collect(),
-            format_version: 4,
+            format_version: types::FORMAT_VERSION,
         };
         let mut p = self.out_path.clone();
         p.push(output.index.get(&output.root).unwrap().name.clone().unwrap());
@@ -108,6 +108,7 @@ macro_rules! map {
 crate mod lint;
 mod markdown;
 mod passes;
+mod scrape_examples;
 mod theme;
 mod visit_ast;
 mod visit_lib;
@@ -516,6 +517,13 @@ fn opts() -> Vec<RustcOptGroup> {
         unstable("test-builder", |o| {
             o.optopt("", "test-builder", "The rustc-like binary to use as the test builder", "PATH")
         }),
+        unstable("merge-doctests", |o| {
+            o.optflag(
+                "",
+                "merge-doctests",
+                "Compile the doctests that allow it into a single test executable",
+            )
+        }),
         unstable("check", |o| o.optflag("", "check", "Run rustdoc checks")),
         unstable("generate-redirect-map", |o| {
             o.optflag(
@@ -527,6 +535,45 @@ fn opts() -> Vec<RustcOptGroup> {
         unstable("print", |o| {
             o.optmulti("", "print", "Rustdoc information to print on stdout", "[unversioned-files]")
         }),
+        unstable("scrape-examples-output-path", |o| {
+            o.optopt(
+                "",
+                "scrape-examples-output-path",
+                "collect function call information and write it to the given file",
+                "PATH",
+            )
+        }),
+        unstable("scrape-examples-target-crate", |o| {
+            o.optmulti(
+                "",
+                "scrape-examples-target-crate",
+                "collect function calls to the given crate",
+                "CRATE",
+            )
+        }),
+        unstable("with-examples", |o| {
+            o.optmulti(
+                "",
+                "with-examples",
+                "path to function call information (for displaying examples in the documentation)",
+                "PATH",
+            )
+        }),
+        unstable("full-text-search", |o| {
+            o.optflag(
+                "",
+                "full-text-search",
+                "index the whole documentation of items for the search, not only their names",
+            )
+        }),
+        unstable("api-diff-baseline", |o| {
+            o.optopt(
+                "",
+                "api-diff-baseline",
+                "compare the public API in the JSON file given as input to the one in this file",
+                "PATH",
+            )
+        }),
     ]
 }
 
@@ -607,6 +654,10 @@ fn run_renderer<'tcx, T: formats::FormatRenderer<'tcx>>(
 fn main_options(options: config::Options) -> MainResult {
     let diag = core::new_handler(options.error_format, None, &options.debugging_opts);
 
+    if let Some(baseline) = &options.api_diff_baseline {
+        return wrap_return(&diag, json::diff::run(baseline, &options.input));
+    }
+
     match (options.should_test, options.markdown_input()) {
         (true, true) => return wrap_return(&diag, markdown::test(options)),
         (true, false) => return doctest::run(options),
@@ -624,6 +675,7 @@ fn main_options(options: config::Options) -> MainResult {
     let diag_opts = (options.error_format, options.edition, options.debugging_opts.clone());
     let show_coverage = options.show_coverage;
     let run_check = options.run_check;
+    let scrape_examples_options = options.scrape_examples_options.clone();
 
     // First, parse the crate and extract all relevant information.
     info!("starting to run rustc");
@@ -681,8 +733,19 @@ fn main_options(options: config::Options) -> MainResult {
                     return Ok(());
                 }
 
-                info!("going to format");
                 let (error_format, edition, debugging_options) = diag_opts;
+                if let Some(scrape_examples_options) = scrape_examples_options {
+                    return scrape_examples::run(
+                        krate,
+                        render_opts,
+                        cache,
+                        edition,
+                        tcx,
+                        scrape_examples_options,
+                    );
+                }
+
+                info!("going to format");
                 let diag = core::new_handler(error_format, None, &debugging_options);
                 match output_format {
                     config::OutputFormat::Html => sess.time("render_html", || {
@@ -158,6 +158,18 @@ macro_rules! declare_rustdoc_lint {
     "detects URLs that could be written using only angle brackets"
 }
 
+declare_rustdoc_lint! {
+    /// The `bare_code_references` lint detects paths written in plain
+    /// backticks which don't resolve to any item, or which could be
+    /// intra-doc links. This is a `rustdoc` only lint, see the
+    /// documentation in the [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#bare_code_references
+    BARE_CODE_REFERENCES,
+    Allow,
+    "detects paths in plain backticks that don't resolve or could be intra-doc links"
+}
+
 crate static RUSTDOC_LINTS: Lazy<Vec<&'static Lint>> = Lazy::new(|| {
     vec![
         BROKEN_INTRA_DOC_LINKS,
@@ -168,6 +180,7 @@ macro_rules! declare_rustdoc_lint {
         INVALID_HTML_TAGS,
         NON_AUTOLINKS,
         MISSING_CRATE_LEVEL_DOCS,
+        BARE_CODE_REFERENCES,
     ]
 });
 
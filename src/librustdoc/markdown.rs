@@ -6,7 +6,7 @@
 use rustc_span::source_map::DUMMY_SP;
 
 use crate::config::{Options, RenderOptions};
-use crate::doctest::{Collector, TestOptions};
+use crate::doctest::{libtest_args, Collector, TestOptions};
 use crate::html::escape::Escape;
 use crate::html::markdown;
 use crate::html::markdown::{find_testable_code, ErrorCodes, IdMap, Markdown, MarkdownWithToc};
@@ -114,12 +114,13 @@ fn extract_leading_metadata(s: &str) -> (Vec<&str>, &str) {
 }
 
 /// Runs any tests/code examples in the markdown file `input`.
-crate fn test(mut options: Options) -> Result<(), String> {
+crate fn test(options: Options) -> Result<(), String> {
     let input_str = read_to_string(&options.input)
         .map_err(|err| format!("{}: {}", options.input.display(), err))?;
     let mut opts = TestOptions::default();
     opts.no_crate_inject = true;
     opts.display_warnings = options.display_warnings;
+    let test_args = libtest_args(&options);
     let mut collector = Collector::new(
         options.input.display().to_string(),
         options.clone(),
@@ -134,9 +135,8 @@ fn extract_leading_metadata(s: &str) -> (Vec<&str>, &str) {
 
     find_testable_code(&input_str, &mut collector, codes, options.enable_per_target_ignores, None);
 
-    options.test_args.insert(0, "rustdoctest".to_string());
     testing::test_main(
-        &options.test_args,
+        &test_args,
         collector.tests,
         Some(testing::Options::new().display_output(options.display_warnings)),
     );
@@ -21,7 +21,12 @@
 };
 
 fn calculate_doc_coverage(krate: clean::Crate, ctx: &mut DocContext<'_>) -> clean::Crate {
-    let mut calc = CoverageCalculator { items: Default::default(), ctx };
+    let mut calc = CoverageCalculator {
+        items: Default::default(),
+        item_details: Default::default(),
+        path: vec![krate.name.to_string()],
+        ctx,
+    };
     let krate = calc.fold_crate(krate);
 
     calc.print_results();
@@ -35,6 +40,7 @@ struct ItemCount {
     with_docs: u64,
     total_examples: u64,
     with_examples: u64,
+    with_runnable_examples: u64,
 }
 
 impl ItemCount {
@@ -42,6 +48,7 @@ fn count_item(
         &mut self,
         has_docs: bool,
         has_doc_example: bool,
+        has_runnable_doc_example: bool,
         should_have_doc_examples: bool,
         should_have_docs: bool,
     ) {
@@ -58,6 +65,9 @@ fn count_item(
         if has_doc_example {
             self.with_examples += 1;
         }
+        if has_runnable_doc_example {
+            self.with_runnable_examples += 1;
+        }
     }
 
     fn percentage(&self) -> Option<f64> {
@@ -86,6 +96,7 @@ fn sub(self, rhs: Self) -> Self {
             with_docs: self.with_docs - rhs.with_docs,
             total_examples: self.total_examples - rhs.total_examples,
             with_examples: self.with_examples - rhs.with_examples,
+            with_runnable_examples: self.with_runnable_examples - rhs.with_runnable_examples,
         }
     }
 }
@@ -96,11 +107,33 @@ fn add_assign(&mut self, rhs: Self) {
         self.with_docs += rhs.with_docs;
         self.total_examples += rhs.total_examples;
         self.with_examples += rhs.with_examples;
+        self.with_runnable_examples += rhs.with_runnable_examples;
     }
 }
 
+/// Whether a single item is documented, only listed in the JSON output.
+#[derive(Serialize, Debug)]
+struct ItemCoverage {
+    path: String,
+    line: usize,
+    has_docs: bool,
+    has_example: bool,
+    /// Whether one of the examples is run by `rustdoc --test`, and not only compiled.
+    has_runnable_example: bool,
+}
+
+#[derive(Serialize)]
+struct FileCoverage<'a> {
+    #[serde(flatten)]
+    count: &'a ItemCount,
+    items: &'a [ItemCoverage],
+}
+
 struct CoverageCalculator<'a, 'b> {
     items: BTreeMap<FileName, ItemCount>,
+    item_details: BTreeMap<FileName, Vec<ItemCoverage>>,
+    /// The path of the item being visited, starting with the crate name.
+    path: Vec<String>,
     ctx: &'a mut DocContext<'b>,
 }
 
@@ -120,8 +153,11 @@ fn to_json(&self) -> String {
             &self
                 .items
                 .iter()
-                .map(|(k, v)| (k.to_string(), v))
-                .collect::<BTreeMap<String, &ItemCount>>(),
+                .map(|(k, count)| {
+                    let items = self.item_details.get(k).map_or(&[][..], |items| &items[..]);
+                    (k.to_string(), FileCoverage { count, items })
+                })
+                .collect::<BTreeMap<String, FileCoverage<'_>>>(),
         )
         .expect("failed to convert JSON data to string")
     }
@@ -183,6 +219,7 @@ fn print_table_record(
 
 impl<'a, 'b> fold::DocFolder for CoverageCalculator<'a, 'b> {
     fn fold_item(&mut self, i: clean::Item) -> Option<clean::Item> {
+        let mut name = i.name.map(|name| name.to_string());
         match *i.kind {
             _ if !i.def_id.is_local() => {
                 // non-local items are skipped because they can be out of the users control,
@@ -229,11 +266,12 @@ fn fold_item(&mut self, i: clean::Item) -> Option<clean::Item> {
                     // cases it doesn't make sense, as all methods on a type are in one single
                     // impl block
                     debug!("impl {:#} in {}", impl_.for_.print(&self.ctx.cache), filename);
+                    name = Some(format!("{:#}", impl_.for_.print(&self.ctx.cache)));
                 }
             }
             _ => {
                 let has_docs = !i.attrs.doc_strings.is_empty();
-                let mut tests = Tests { found_tests: 0 };
+                let mut tests = Tests { found_tests: 0, found_runnable_tests: 0 };
 
                 find_testable_code(
                     &i.attrs.collapsed_doc_value().unwrap_or_default(),
@@ -245,6 +283,7 @@ fn fold_item(&mut self, i: clean::Item) -> Option<clean::Item> {
 
                 let filename = i.source.filename(self.ctx.sess());
                 let has_doc_example = tests.found_tests != 0;
+                let has_runnable_doc_example = tests.found_runnable_tests != 0;
                 let hir_id = self.ctx.tcx.hir().local_def_id_to_hir_id(i.def_id.expect_local());
                 let (level, source) = self.ctx.tcx.lint_level_at_node(MISSING_DOCS, hir_id);
                 // `missing_docs` is allow-by-default, so don't treat this as ignoring the item
@@ -252,15 +291,34 @@ fn fold_item(&mut self, i: clean::Item) -> Option<clean::Item> {
                 let should_have_docs =
                     level != lint::Level::Allow || matches!(source, LintLevelSource::Default);
                 debug!("counting {:?} {:?} in {}", i.type_(), i.name, filename);
+                let path = self.path.iter().chain(&name).cloned().collect::<Vec<_>>();
+                self.item_details.entry(filename.clone()).or_default().push(ItemCoverage {
+                    path: path.join("::"),
+                    line: i.source.lo(self.ctx.sess()).line,
+                    has_docs,
+                    has_example: has_doc_example,
+                    has_runnable_example: has_runnable_doc_example,
+                });
                 self.items.entry(filename).or_default().count_item(
                     has_docs,
                     has_doc_example,
+                    has_runnable_doc_example,
                     should_have_doc_example(self.ctx, &i),
                     should_have_docs,
                 );
             }
         }
 
-        Some(self.fold_item_recur(i))
+        let pushed = if let Some(name) = name {
+            self.path.push(name);
+            true
+        } else {
+            false
+        };
+        let ret = self.fold_item_recur(i);
+        if pushed {
+            self.path.pop();
+        }
+        Some(ret)
     }
 }
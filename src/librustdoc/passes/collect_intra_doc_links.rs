@@ -16,7 +16,7 @@
 use rustc_middle::ty::TyCtxt;
 use rustc_middle::{bug, ty};
 use rustc_resolve::ParentScope;
-use rustc_session::lint::Lint;
+use rustc_session::lint::{Level, Lint};
 use rustc_span::hygiene::{MacroKind, SyntaxContext};
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::DUMMY_SP;
@@ -33,8 +33,8 @@
 use crate::clean::{self, utils::find_nearest_parent_module, Crate, Item, ItemLink, PrimitiveType};
 use crate::core::DocContext;
 use crate::fold::DocFolder;
-use crate::html::markdown::{markdown_links, MarkdownLink};
-use crate::lint::{BROKEN_INTRA_DOC_LINKS, PRIVATE_INTRA_DOC_LINKS};
+use crate::html::markdown::{markdown_code_spans, markdown_links, MarkdownLink};
+use crate::lint::{BARE_CODE_REFERENCES, BROKEN_INTRA_DOC_LINKS, PRIVATE_INTRA_DOC_LINKS};
 use crate::passes::Pass;
 
 use super::span_of_attrs;
@@ -888,6 +888,13 @@ fn fold_item(&mut self, mut item: Item) -> Option<Item> {
             self.mod_ids.push(item.def_id);
         }
 
+        // Resolving the paths in plain backticks is only needed for `bare_code_references`, which
+        // is allowed by default, so don't bother unless it was enabled.
+        let check_code_references =
+            DocContext::as_local_hir_id(self.cx.tcx, item.def_id).map_or(false, |hir_id| {
+                self.cx.tcx.lint_level_at_node(BARE_CODE_REFERENCES, hir_id).0 != Level::Allow
+            });
+
         // We want to resolve in the lexical scope of the documentation.
         // In the presence of re-exports, this is not the same as the module of the item.
         // Rather than merging all documentation into one, resolve it one attribute at a time
@@ -908,6 +915,19 @@ fn fold_item(&mut self, mut item: Item) -> Option<Item> {
                     item.attrs.links.push(link);
                 }
             }
+            if check_code_references {
+                for (code, range) in markdown_code_spans(&doc) {
+                    self.check_code_reference(
+                        &item,
+                        &doc,
+                        &self_name,
+                        parent_node,
+                        krate,
+                        &code,
+                        range,
+                    );
+                }
+            }
         }
 
         Some(if item.is_mod() {
@@ -1005,7 +1025,7 @@ fn resolve_link(
             parent_node
         };
 
-        let mut module_id = if let Some(id) = base_node {
+        let module_id = if let Some(id) = base_node {
             id
         } else {
             // This is a bug.
@@ -1022,36 +1042,8 @@ fn resolve_link(
             return None;
         };
 
-        let resolved_self;
-        // replace `Self` with suitable item's parent name
-        let is_lone_self = path_str == "Self";
-        let is_lone_crate = path_str == "crate";
-        if path_str.starts_with("Self::") || is_lone_self {
-            if let Some(ref name) = self_name {
-                if is_lone_self {
-                    path_str = name;
-                } else {
-                    resolved_self = format!("{}::{}", name, &path_str[6..]);
-                    path_str = &resolved_self;
-                }
-            }
-        } else if path_str.starts_with("crate::") || is_lone_crate {
-            use rustc_span::def_id::CRATE_DEF_INDEX;
-
-            // HACK(jynelson): rustc_resolve thinks that `crate` is the crate currently being documented.
-            // But rustdoc wants it to mean the crate this item was originally present in.
-            // To work around this, remove it and resolve relative to the crate root instead.
-            // HACK(jynelson)(2): If we just strip `crate::` then suddenly primitives become ambiguous
-            // (consider `crate::char`). Instead, change it to `self::`. This works because 'self' is now the crate root.
-            // FIXME(#78696): This doesn't always work.
-            if is_lone_crate {
-                path_str = "self";
-            } else {
-                resolved_self = format!("self::{}", &path_str["crate::".len()..]);
-                path_str = &resolved_self;
-            }
-            module_id = DefId { krate, index: CRATE_DEF_INDEX };
-        }
+        let (resolved_self, module_id) = resolve_path_root(path_str, self_name, module_id, krate);
+        path_str = &resolved_self;
 
         // Strip generics from the path.
         let stripped_path_string;
@@ -1259,6 +1251,82 @@ fn resolve_link(
         }
     }
 
+    /// Checks a path written in plain backticks for the `bare_code_references` lint.
+    ///
+    /// Only code containing `::`, optionally followed by `()` or `!`, is considered to be a path,
+    /// so that the names of variables or parameters aren't reported.
+    fn check_code_reference(
+        &mut self,
+        item: &Item,
+        dox: &str,
+        self_name: &Option<String>,
+        parent_node: Option<DefId>,
+        krate: CrateNum,
+        code: &str,
+        range: Range<usize>,
+    ) {
+        let (path, is_macro) = match code.strip_suffix('!') {
+            Some(path) => (path, true),
+            None => (code.strip_suffix("()").unwrap_or(code), false),
+        };
+        let is_ident = |segment: &str| {
+            segment.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+                && segment.chars().all(|ch| ch.is_alphanumeric() || ch == '_')
+        };
+        if !path.contains("::") || !path.split("::").all(is_ident) {
+            return;
+        }
+
+        let base_node = if item.is_mod() && item.attrs.inner_docs {
+            self.mod_ids.last().copied()
+        } else {
+            parent_node
+        };
+        let module_id = match base_node {
+            Some(id) => id,
+            None => return,
+        };
+        let (path_str, module_id) = resolve_path_root(path, self_name, module_id, krate);
+
+        let resolved = if is_macro {
+            self.resolve_macro(&path_str, module_id).is_ok()
+        } else {
+            [TypeNS, ValueNS]
+                .iter()
+                .any(|&ns| self.resolve(&path_str, ns, module_id, &None).is_ok())
+        };
+        // Resolving associated items leaves their kind in the side channel, which isn't needed.
+        self.kind_side_channel.take();
+
+        let tcx = self.cx.tcx;
+        if resolved {
+            let msg = format!("`{}` could be an intra-doc link", code);
+            report_diagnostic(tcx, BARE_CODE_REFERENCES, &msg, item, dox, &range, |diag, sp| {
+                let link = format!("[{}]", &dox[range.clone()]);
+                if let Some(sp) = sp {
+                    diag.span_suggestion(
+                        sp,
+                        "use an intra-doc link instead",
+                        link,
+                        Applicability::MachineApplicable,
+                    );
+                } else {
+                    diag.help(&format!("use an intra-doc link instead: {}", link));
+                }
+            });
+        } else {
+            let msg = format!("unresolved code reference to `{}`", code);
+            report_diagnostic(tcx, BARE_CODE_REFERENCES, &msg, item, dox, &range, |diag, sp| {
+                let note = format!("no item named `{}` is in scope", path);
+                if let Some(sp) = sp {
+                    diag.span_label(sp, note);
+                } else {
+                    diag.note(&note);
+                }
+            });
+        }
+    }
+
     fn resolve_with_disambiguator_cached(
         &mut self,
         key: ResolutionInfo,
@@ -1664,6 +1732,43 @@ fn as_help(&self, path_str: &str) -> String {
     }
 }
 
+/// Replaces `Self` and `crate` at the start of a path. Returns the path to resolve, along with the
+/// module to resolve it in.
+fn resolve_path_root<'p>(
+    path_str: &'p str,
+    self_name: &Option<String>,
+    module_id: DefId,
+    krate: CrateNum,
+) -> (Cow<'p, str>, DefId) {
+    let is_lone_self = path_str == "Self";
+    let is_lone_crate = path_str == "crate";
+    if path_str.starts_with("Self::") || is_lone_self {
+        // replace `Self` with suitable item's parent name
+        match self_name {
+            Some(name) if is_lone_self => (name.clone().into(), module_id),
+            Some(name) => (format!("{}::{}", name, &path_str[6..]).into(), module_id),
+            None => (path_str.into(), module_id),
+        }
+    } else if path_str.starts_with("crate::") || is_lone_crate {
+        use rustc_span::def_id::CRATE_DEF_INDEX;
+
+        // HACK(jynelson): rustc_resolve thinks that `crate` is the crate currently being documented.
+        // But rustdoc wants it to mean the crate this item was originally present in.
+        // To work around this, remove it and resolve relative to the crate root instead.
+        // HACK(jynelson)(2): If we just strip `crate::` then suddenly primitives become ambiguous
+        // (consider `crate::char`). Instead, change it to `self::`. This works because 'self' is now the crate root.
+        // FIXME(#78696): This doesn't always work.
+        let path_str = if is_lone_crate {
+            "self".into()
+        } else {
+            format!("self::{}", &path_str["crate::".len()..]).into()
+        };
+        (path_str, DefId { krate, index: CRATE_DEF_INDEX })
+    } else {
+        (path_str.into(), module_id)
+    }
+}
+
 /// Reports a diagnostic for an intra-doc link.
 ///
 /// If no link range is provided, or the source span of the link cannot be determined, the span of
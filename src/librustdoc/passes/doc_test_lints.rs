@@ -42,12 +42,17 @@ fn fold_item(&mut self, item: Item) -> Option<Item> {
 
 pub(crate) struct Tests {
     pub(crate) found_tests: usize,
+    /// The number of tests which are run by `rustdoc --test`, and not only compiled.
+    pub(crate) found_runnable_tests: usize,
 }
 
 impl crate::doctest::Tester for Tests {
     fn add_test(&mut self, _: String, config: LangString, _: usize) {
         if config.rust && config.ignore == Ignore::None {
             self.found_tests += 1;
+            if !config.no_run && !config.compile_fail {
+                self.found_runnable_tests += 1;
+            }
         }
     }
 }
@@ -90,7 +95,7 @@ fn add_test(&mut self, _: String, config: LangString, _: usize) {
         }
     };
 
-    let mut tests = Tests { found_tests: 0 };
+    let mut tests = Tests { found_tests: 0, found_runnable_tests: 0 };
 
     find_testable_code(&dox, &mut tests, ErrorCodes::No, false, None);
 
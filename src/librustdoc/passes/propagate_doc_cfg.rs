@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
+use rustc_data_structures::fx::FxHashSet;
+use rustc_span::symbol::sym;
+
 use crate::clean::cfg::Cfg;
-use crate::clean::{Crate, Item};
+use crate::clean::{AttributesExt, Crate, Item};
 use crate::core::DocContext;
 use crate::fold::DocFolder;
 use crate::passes::Pass;
@@ -12,18 +15,66 @@
     description: "propagates `#[doc(cfg(...))]` to child items",
 };
 
-crate fn propagate_doc_cfg(cr: Crate, _: &mut DocContext<'_>) -> Crate {
-    CfgPropagator { parent_cfg: None }.fold_crate(cr)
+crate fn propagate_doc_cfg(cr: Crate, cx: &mut DocContext<'_>) -> Crate {
+    let auto_cfg = cx.tcx.hir().krate_attrs().lists(sym::doc).any(|a| a.has_name(sym::auto_cfg));
+    let hidden_cfg = hidden_cfg(cx, auto_cfg);
+    let hidden_cfg = if auto_cfg { Some(hidden_cfg) } else { None };
+    CfgPropagator { parent_cfg: None, hidden_cfg }.fold_crate(cr)
+}
+
+/// The cfgs left out of the `#[doc(cfg(...))]` inferred from `#[cfg(...)]`: the ones that are
+/// only set when documenting or testing, and the ones given with `#![doc(cfg_hide(...))]`.
+fn hidden_cfg(cx: &DocContext<'_>, auto_cfg: bool) -> FxHashSet<Cfg> {
+    let mut hidden_cfg: FxHashSet<Cfg> =
+        [sym::doc, sym::doctest, sym::test].iter().map(|&name| Cfg::Cfg(name, None)).collect();
+    for attr in cx.tcx.hir().krate_attrs().lists(sym::doc) {
+        if !attr.has_name(sym::cfg_hide) {
+            continue;
+        }
+        if !auto_cfg {
+            cx.sess()
+                .struct_span_warn(
+                    attr.span(),
+                    "`#![doc(cfg_hide(...))]` has no effect without `#![doc(auto_cfg)]`",
+                )
+                .emit();
+        }
+        for cfg in attr.meta_item_list().unwrap_or_default() {
+            match Cfg::parse_nested(cfg) {
+                Ok(cfg) => {
+                    hidden_cfg.insert(cfg);
+                }
+                Err(e) => cx.sess().span_err(e.span, e.msg),
+            }
+        }
+    }
+    hidden_cfg
 }
 
 struct CfgPropagator {
     parent_cfg: Option<Arc<Cfg>>,
+    /// Only set with `#![doc(auto_cfg)]`, in which case the `#[cfg(...)]` attributes of
+    /// items without a `#[doc(cfg(...))]` are used as if they were one.
+    hidden_cfg: Option<FxHashSet<Cfg>>,
 }
 
 impl DocFolder for CfgPropagator {
     fn fold_item(&mut self, mut item: Item) -> Option<Item> {
         let old_parent_cfg = self.parent_cfg.clone();
 
+        match &self.hidden_cfg {
+            Some(hidden_cfg) if item.attrs.cfg.is_none() => {
+                item.attrs.cfg = item
+                    .attrs
+                    .lists(sym::cfg)
+                    .filter_map(|cfg| Cfg::parse_nested(&cfg).ok())
+                    .filter_map(|cfg| cfg.strip_hidden(hidden_cfg))
+                    .fold(None, |all, cfg| Some(all.unwrap_or(Cfg::True) & cfg))
+                    .map(Arc::new);
+            }
+            _ => {}
+        }
+
         let new_cfg = match (self.parent_cfg.take(), item.attrs.cfg.take()) {
             (None, None) => None,
             (Some(rc), None) | (None, Some(rc)) => Some(rc),
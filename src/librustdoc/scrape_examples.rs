@@ -0,0 +1,265 @@
+//! Collection of call sites that can be shown as usage examples in the documentation.
+//!
+//! Running rustdoc with `--scrape-examples-output-path` on a crate that uses the crates given
+//! with `--scrape-examples-target-crate` (typically one of their `examples/`) records every call
+//! to a function of those crates, and writes them out as JSON. Passing the resulting files to
+//! `--with-examples` when documenting a target crate then shows the call sites under the
+//! documentation of the functions and methods they call.
+
+use std::fs;
+use std::path::PathBuf;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::ErrorReported;
+use rustc_hir::{
+    self as hir,
+    intravisit::{self, NestedVisitorMap, Visitor},
+};
+use rustc_middle::hir::map::Map;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::getopts;
+use rustc_span::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_span::edition::Edition;
+use rustc_span::source_map::FileName;
+use rustc_span::{BytePos, SourceFile};
+use serde::{Deserialize, Serialize};
+
+use crate::clean;
+use crate::config::RenderOptions;
+use crate::formats::cache::Cache;
+use crate::formats::FormatRenderer;
+use crate::html::render::Context;
+
+#[derive(Clone, Debug)]
+crate struct ScrapeExamplesOptions {
+    output_path: PathBuf,
+    target_crates: Vec<String>,
+}
+
+impl ScrapeExamplesOptions {
+    crate fn new(
+        matches: &getopts::Matches,
+        diag: &rustc_errors::Handler,
+    ) -> Result<Option<Self>, i32> {
+        let output_path = matches.opt_str("scrape-examples-output-path");
+        let target_crates = matches.opt_strs("scrape-examples-target-crate");
+        match (output_path, !target_crates.is_empty()) {
+            (Some(output_path), true) => Ok(Some(ScrapeExamplesOptions {
+                output_path: PathBuf::from(output_path),
+                target_crates,
+            })),
+            (Some(_), false) | (None, true) => {
+                diag.struct_err(
+                    "must use --scrape-examples-output-path and --scrape-examples-target-crate \
+                     together",
+                )
+                .emit();
+                Err(1)
+            }
+            (None, false) => Ok(None),
+        }
+    }
+}
+
+/// A range of source code, as byte offsets into the original file and as 1-based lines.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+crate struct SyntaxRange {
+    crate byte_span: (u32, u32),
+    crate line_span: (usize, usize),
+}
+
+impl SyntaxRange {
+    fn new(tcx: TyCtxt<'_>, span: rustc_span::Span, file: &SourceFile) -> Self {
+        let get_pos = |bytepos: BytePos| file.original_relative_byte_pos(bytepos).0;
+        let get_line = |bytepos: BytePos| tcx.sess.source_map().lookup_char_pos(bytepos).line;
+
+        SyntaxRange {
+            byte_span: (get_pos(span.lo()), get_pos(span.hi())),
+            line_span: (get_line(span.lo()), get_line(span.hi())),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+crate struct CallLocation {
+    /// The call expression itself.
+    crate call_expr: SyntaxRange,
+    /// The item containing the call, which is what gets shown in the documentation.
+    crate enclosing_item: SyntaxRange,
+}
+
+/// The calls to a function found in one file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+crate struct CallData {
+    /// Never empty.
+    crate locations: Vec<CallLocation>,
+    /// The url of the source page of the file, relative to the documentation root.
+    crate url: String,
+    crate display_name: String,
+    crate edition: String,
+}
+
+impl CallData {
+    crate fn edition(&self) -> Option<Edition> {
+        self.edition.parse().ok()
+    }
+}
+
+crate type FnCallLocations = FxHashMap<PathBuf, CallData>;
+/// Call sites keyed by the [`def_id_key`] of the called function, and then by file.
+crate type AllCallLocations = FxHashMap<String, FnCallLocations>;
+
+/// The key under which the call sites of `def_id` are stored. This is its `DefPathHash`, which
+/// is the same in the crate defining the function and in the crates calling it.
+crate fn def_id_key(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    tcx.def_path_hash(def_id).0.to_hex()
+}
+
+/// Visitor for traversing a crate and finding instances of function calls.
+struct FindCalls<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    map: Map<'tcx>,
+    cx: Context<'tcx>,
+    target_crates: Vec<CrateNum>,
+    calls: &'a mut AllCallLocations,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for FindCalls<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::All(self.map)
+    }
+
+    fn visit_expr(&mut self, ex: &'tcx hir::Expr<'tcx>) {
+        intravisit::walk_expr(self, ex);
+
+        // Calls written by macros can't be shown in a meaningful way.
+        let span = ex.span;
+        if span.from_expansion() {
+            return;
+        }
+
+        let tcx = self.tcx;
+        let body_owner = self.map.local_def_id(self.map.enclosing_body_owner(ex.hir_id));
+        let typeck_results = tcx.typeck(body_owner);
+        let def_id = match ex.kind {
+            hir::ExprKind::Call(f, _) => match typeck_results.node_type(f.hir_id).kind() {
+                ty::FnDef(def_id, _) => *def_id,
+                _ => return,
+            },
+            hir::ExprKind::MethodCall(..) => match typeck_results.type_dependent_def_id(ex.hir_id)
+            {
+                Some(def_id) => def_id,
+                None => return,
+            },
+            _ => return,
+        };
+        if !self.target_crates.contains(&def_id.krate) {
+            return;
+        }
+
+        let file = tcx.sess.source_map().lookup_char_pos(span.lo()).file;
+        let file_path = match file.name {
+            FileName::Real(ref real) => real.local_path().to_path_buf(),
+            _ => return,
+        };
+        // The source page is only generated for files containing documented items.
+        let url = match self.cx.href_from_span(&clean::Span::from_rustc_span(span), false) {
+            Some(url) => url,
+            None => return,
+        };
+        // The path is used to read the file when documenting the target crate, which may happen
+        // in a different working directory.
+        let abs_path = match fs::canonicalize(&file_path) {
+            Ok(abs_path) => abs_path,
+            Err(_) => return,
+        };
+
+        let enclosing_item_span = self.map.span_with_body(self.map.get_parent_item(ex.hir_id));
+        let location = CallLocation {
+            call_expr: SyntaxRange::new(tcx, span, &file),
+            enclosing_item: SyntaxRange::new(tcx, enclosing_item_span, &file),
+        };
+
+        let edition = span.edition();
+        self.calls
+            .entry(def_id_key(tcx, def_id))
+            .or_default()
+            .entry(abs_path)
+            .or_insert_with(|| CallData {
+                locations: Vec::new(),
+                url,
+                display_name: file_path.display().to_string(),
+                edition: edition.to_string(),
+            })
+            .locations
+            .push(location);
+    }
+}
+
+/// Finds the calls to the target crates in `krate` and writes them to the output path. This also
+/// renders the source pages of `krate`, which the scraped examples link to.
+crate fn run(
+    krate: clean::Crate,
+    renderopts: RenderOptions,
+    cache: Cache,
+    edition: Edition,
+    tcx: TyCtxt<'_>,
+    options: ScrapeExamplesOptions,
+) -> Result<(), ErrorReported> {
+    let inner = move || -> Result<(), String> {
+        let (cx, _) =
+            Context::init(krate, renderopts, edition, cache, tcx).map_err(|e| e.to_string())?;
+
+        // If several versions of a target crate are in the dependency graph, the calls to all of
+        // them are collected.
+        let target_crates = tcx
+            .crates()
+            .iter()
+            .copied()
+            .chain(Some(LOCAL_CRATE))
+            .filter(|&cnum| {
+                let name = tcx.crate_name(cnum);
+                options.target_crates.iter().any(|target| *target == *name.as_str())
+            })
+            .collect();
+
+        let mut calls = AllCallLocations::default();
+        let mut finder = FindCalls { tcx, map: tcx.hir(), cx, target_crates, calls: &mut calls };
+        intravisit::walk_crate(&mut finder, tcx.hir().krate());
+
+        let contents = serde_json::to_string(&calls).map_err(|e| e.to_string())?;
+        fs::write(&options.output_path, contents)
+            .map_err(|e| format!("{}: {}", options.output_path.display(), e))
+    };
+
+    inner().map_err(|e| {
+        tcx.sess.struct_err(&format!("failed to scrape examples: {}", e)).emit();
+        ErrorReported
+    })
+}
+
+/// Loads the call sites written by previous runs of rustdoc with `--scrape-examples-output-path`.
+crate fn load_call_locations(
+    with_examples: Vec<String>,
+    diag: &rustc_errors::Handler,
+) -> Result<AllCallLocations, i32> {
+    let mut all_calls: AllCallLocations = FxHashMap::default();
+    for path in with_examples {
+        let calls: AllCallLocations = match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string()))
+        {
+            Ok(calls) => calls,
+            Err(e) => {
+                diag.struct_err(&format!("failed to load examples from `{}`: {}", path, e)).emit();
+                return Err(1);
+            }
+        };
+        for (function, calls) in calls {
+            all_calls.entry(function).or_default().extend(calls);
+        }
+    }
+    Ok(all_calls)
+}
@@ -32,3 +32,18 @@ fn test_union_info_roundtrip() {
 
     assert_eq!(u, de_u);
 }
+
+#[cfg(feature = "schema")]
+#[test]
+fn test_schema_is_up_to_date() {
+    let schema = serde_json::to_string_pretty(&schemars::schema_for!(Crate)).unwrap() + "\n";
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/schema.json");
+    if std::env::var_os("RUSTDOC_JSON_BLESS").is_some() {
+        std::fs::write(path, schema).unwrap();
+    } else {
+        assert!(
+            std::fs::read_to_string(path).unwrap() == schema,
+            "schema.json is out of date, run this test with RUSTDOC_JSON_BLESS=1 to update it"
+        );
+    }
+}
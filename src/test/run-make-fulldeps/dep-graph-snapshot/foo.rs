@@ -0,0 +1,17 @@
+#[cfg(not(changed))]
+pub fn changed() -> u32 {
+    1
+}
+
+#[cfg(changed)]
+pub fn changed() -> u32 {
+    2
+}
+
+pub fn caller() -> u32 {
+    changed() + 1
+}
+
+pub fn unrelated() -> u32 {
+    3
+}
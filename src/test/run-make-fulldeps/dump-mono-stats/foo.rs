@@ -0,0 +1,7 @@
+pub fn generic_fn<T: Default>() -> T {
+    T::default()
+}
+
+pub fn user() -> (u8, u16, u32) {
+    (generic_fn(), generic_fn(), generic_fn())
+}
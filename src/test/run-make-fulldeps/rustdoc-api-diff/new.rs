@@ -0,0 +1,21 @@
+pub fn unchanged() {}
+
+pub fn changed(_: u64) {}
+
+pub struct Exhaustive {
+    pub a: u8,
+    pub b: u8,
+}
+
+#[non_exhaustive]
+pub enum NonExhaustive {
+    A,
+    B,
+}
+
+pub struct Pointer {
+    _p: *const u8,
+}
+
+/// Does nothing at all.
+pub fn documented() {}
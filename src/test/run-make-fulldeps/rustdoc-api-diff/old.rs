@@ -0,0 +1,21 @@
+pub fn unchanged() {}
+
+pub fn changed(_: u32) {}
+
+pub fn removed() {}
+
+pub struct Exhaustive {
+    pub a: u8,
+}
+
+#[non_exhaustive]
+pub enum NonExhaustive {
+    A,
+}
+
+pub struct Pointer {
+    _p: u8,
+}
+
+/// Does nothing.
+pub fn documented() {}
@@ -0,0 +1,17 @@
+/// ```
+/// let merged_a = 1;
+/// assert_eq!(merged_a, 1);
+/// ```
+///
+/// ```
+/// let merged_b = 2;
+/// assert_eq!(merged_b, 2);
+/// ```
+///
+/// ```
+/// fn main() {
+///     let standalone = 3;
+///     assert_eq!(standalone, 3);
+/// }
+/// ```
+pub fn foo() {}
@@ -0,0 +1,6 @@
+fn main() {
+    let total = foobar::add(1, 2);
+    let mut counter = foobar::Counter::new();
+    counter.bump();
+    assert_eq!(total, counter.get() + 2);
+}
@@ -0,0 +1,27 @@
+// @has foobar/fn.add.html '//*[@class="scraped-example-title"]' 'examples/ex.rs (line 2)'
+// @has - '//*[@class="scraped-example-title"]/a/@href' '../src/ex/ex.rs.html#2'
+// @has - '//*[@class="line-numbers"]/*[@class="line-highlighted"]' '2'
+// @has - '//*[@class="scraped-example"]' 'let total = foobar::add(1, 2);'
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+// @has foobar/struct.Counter.html
+pub struct Counter(u32);
+
+impl Counter {
+    // @has - '//*[@class="scraped-example-title"]' 'examples/ex.rs (line 3)'
+    pub fn new() -> Counter {
+        Counter(0)
+    }
+
+    // @has - '//*[@class="scraped-example-title"]' 'examples/ex.rs (line 4)'
+    pub fn bump(&mut self) {
+        self.0 += 1;
+    }
+
+    // @count - '//*[@class="scraped-example"]' 3
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    println!("{}", (0..10).map(|i| i * 2).sum::<i32>());
+}
@@ -0,0 +1,21 @@
+macro_rules! square {
+    (0) => {
+        0
+    };
+    ($e:expr) => {
+        $e * $e
+    };
+}
+
+macro_rules! outer {
+    ($e:expr) => {
+        square!($e) + 1
+    };
+}
+
+pub fn f(x: i32) -> i32 {
+    outer!(x)
+}
+
+#[derive(Clone)]
+pub struct S;
@@ -0,0 +1,11 @@
+#![feature(proc_macro_tracked_path)]
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+use proc_macro::*;
+
+#[proc_macro]
+pub fn access_tracked_path(_: TokenStream) -> TokenStream {
+    tracked_path::path(env!("TRACKED_PATH"));
+    TokenStream::new()
+}
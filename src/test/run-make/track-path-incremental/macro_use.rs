@@ -0,0 +1,6 @@
+#![crate_type = "rlib"]
+
+#[macro_use]
+extern crate macro_def;
+
+access_tracked_path!();
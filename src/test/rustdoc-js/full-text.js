@@ -0,0 +1,12 @@
+const QUERY = ['dropped pool', 'hidden'];
+
+const EXPECTED = [
+    {
+        'others': [
+            { 'path': 'full_text', 'name': 'start' },
+        ],
+    },
+    {
+        'others': [],
+    },
+];
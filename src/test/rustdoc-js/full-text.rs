@@ -0,0 +1,16 @@
+// compile-flags: -Z unstable-options --full-text-search
+
+#![crate_name = "full_text"]
+
+/// Spawns the worker threads.
+///
+/// The threads keep running until the pool is dropped.
+pub fn start() {}
+
+/// A running thread.
+pub struct Handle;
+
+/// ```
+/// let drop = "hidden inside a code block";
+/// ```
+pub fn other() {}
@@ -0,0 +1,52 @@
+const QUERY = [
+    'Vec<T> -> Option<T>',
+    'impl Iterator<Item = u8> -> String',
+    'usize, &str -> bool',
+    'Display -> String',
+    'Wrapper -> u8',
+    'T, U -> (U, T)',
+    'Vec<u8> -> *',
+];
+
+const EXPECTED = [
+    {
+        'in_args': [
+            { 'path': 'type_signature', 'name': 'first' },
+        ],
+        'returned': [
+            { 'path': 'type_signature', 'name': 'first' },
+            { 'path': 'type_signature', 'name': 'lengths' },
+        ],
+    },
+    {
+        'in_args': [
+            { 'path': 'type_signature', 'name': 'sum' },
+        ],
+    },
+    {
+        'in_args': [
+            { 'path': 'type_signature', 'name': 'contains' },
+        ],
+    },
+    {
+        'in_args': [
+            { 'path': 'type_signature', 'name': 'show' },
+        ],
+    },
+    {
+        'in_args': [
+            { 'path': 'type_signature::Wrapper', 'name': 'get' },
+        ],
+    },
+    {
+        'in_args': [
+            { 'path': 'type_signature', 'name': 'swap' },
+        ],
+    },
+    {
+        'in_args': [
+            { 'path': 'type_signature', 'name': 'lengths' },
+            { 'path': 'type_signature', 'name': 'first' },
+        ],
+    },
+];
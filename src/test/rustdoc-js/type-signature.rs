@@ -0,0 +1,36 @@
+use std::fmt::Display;
+
+pub struct Wrapper(u8);
+
+impl Wrapper {
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+pub fn first<T>(v: Vec<T>) -> Option<T> {
+    v.into_iter().next()
+}
+
+pub fn lengths(v: Vec<u8>) -> Option<String> {
+    None
+}
+
+pub fn sum(iter: impl Iterator<Item = u8>) -> String {
+    iter.map(|x| x.to_string()).collect()
+}
+
+pub fn contains(s: &str, n: usize) -> bool {
+    s.len() > n
+}
+
+pub fn show<T>(x: T) -> String
+where
+    T: Display,
+{
+    x.to_string()
+}
+
+pub fn swap<T, U>(a: T, b: U) -> (U, T) {
+    (b, a)
+}
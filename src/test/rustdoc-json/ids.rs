@@ -0,0 +1,26 @@
+// Ids are made of the namespace and the path of the item, so that they are the same in every run.
+
+// @is ids.json "$.root" \"t:ids\"
+// @is - "$.index['t:ids'].name" \"ids\"
+
+// @is - "$.index['t:ids::foo'].kind" \"module\"
+pub mod foo {
+    // @is ids.json "$.index['t:ids::foo::Foo'].kind" \"struct\"
+    // @has - "$.index['t:ids::foo::Foo'].inner.impls[*]" \"a:ids::foo::{impl#0}\"
+    pub struct Foo;
+
+    // @is - "$.index['a:ids::foo::{impl#0}'].kind" \"impl\"
+    impl Foo {
+        // @is - "$.index['v:ids::foo::{impl#0}::new'].kind" \"method\"
+        pub fn new() -> Self {
+            Foo
+        }
+    }
+
+    // The auto trait impls synthesized by rustdoc are identified by their type and trait.
+    // @is - "$.index['a:ids::foo::Foo::{synthetic impl core::marker::Send}'].inner.synthetic" true
+}
+
+// Modules and functions are in different namespaces, so they can have the same path.
+// @is - "$.index['v:ids::foo'].kind" \"function\"
+pub fn foo() {}
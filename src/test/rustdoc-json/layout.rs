@@ -0,0 +1,33 @@
+// @is layout.json "$.index[*][?(@.name=='Packed')].layout.size" 3
+// @is - "$.index[*][?(@.name=='Packed')].layout.align" 1
+// @is - "$.index[*][?(@.name=='Packed')].layout.discriminants" {}
+#[repr(C, packed)]
+pub struct Packed {
+    pub a: u8,
+    pub b: u16,
+}
+
+// @is - "$.index[*][?(@.name=='Aligned')].layout.size" 8
+// @is - "$.index[*][?(@.name=='Aligned')].layout.align" 8
+#[repr(align(8))]
+pub union Aligned {
+    pub a: u8,
+}
+
+// @is - "$.index[*][?(@.name=='Enum')].layout.size" 1
+// @is - "$.index[*][?(@.name=='Enum')].layout.discriminants['t:layout::Enum::A']" \"-1\"
+// @is - "$.index[*][?(@.name=='Enum')].layout.discriminants['t:layout::Enum::B']" \"5\"
+// @is - "$.index[*][?(@.name=='Enum')].layout.discriminants['t:layout::Enum::C']" \"6\"
+#[repr(i8)]
+pub enum Enum {
+    A = -1,
+    B = 5,
+    C,
+}
+
+// The layout of generic types depends on their parameters.
+// @is - "$.index[*][?(@.name=='Generic')].layout" null
+pub struct Generic<T>(pub T);
+
+// @is - "$.index[*][?(@.name=='Unsized')].layout" null
+pub struct Unsized(pub [u8]);
@@ -0,0 +1,25 @@
+// check-pass
+// compile-flags:-Z unstable-options --output-format json --show-coverage
+
+//! ```
+//! let x = 12;
+//! ```
+
+/// ```no_run
+/// loop {}
+/// ```
+pub fn no_run() {}
+
+/// ```compile_fail
+/// let x: u8 = "";
+/// ```
+///
+/// ```
+/// let x = 12;
+/// ```
+pub fn compile_fail_and_run() {}
+
+/// ```ignore
+/// let x = 12;
+/// ```
+pub fn ignored() {}
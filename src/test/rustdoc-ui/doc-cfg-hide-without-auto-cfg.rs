@@ -0,0 +1,7 @@
+// check-pass
+
+#![feature(doc_cfg_hide)]
+#![doc(cfg_hide(test))]
+//~^ WARNING has no effect without `#![doc(auto_cfg)]`
+
+pub fn foo() {}
@@ -0,0 +1,18 @@
+// compile-flags:--test -Z unstable-options --output-format json --test-args=--test-threads=1
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// normalize-stdout-test "[0-9]+\.[0-9]+(e-[0-9]+)?" -> "$$TIME"
+// failure-status: 101
+
+/// ```
+/// assert_eq!(1 + 1, 2);
+/// ```
+///
+/// ```compile_fail
+/// let x = 12;
+/// ```
+pub mod foo {
+    /// ```
+    /// assert!(true);
+    /// ```
+    pub fn bar() {}
+}
@@ -0,0 +1,28 @@
+#![deny(rustdoc::bare_code_references)]
+
+pub struct Foo;
+
+impl Foo {
+    pub fn bar() {}
+}
+
+#[macro_export]
+macro_rules! foo {
+    () => {};
+}
+
+/// Calls `Foo::bar` and `Foo::bar()`.
+//~^ ERROR `Foo::bar` could be an intra-doc link
+//~| ERROR `Foo::bar()` could be an intra-doc link
+///
+/// Used to call `Foo::baz`, which was renamed.
+//~^ ERROR unresolved code reference to `Foo::baz`
+///
+/// Uses `crate::foo!` and `self::missing!`.
+//~^ ERROR `crate::foo!` could be an intra-doc link
+//~| ERROR unresolved code reference to `self::missing!`
+///
+/// Names which aren't paths are ignored: `x`, `bar()`, `a + b::c`, `Vec<u8>::new`.
+///
+/// Links are already checked: [`Foo::bar`], [`Foo::bar`](Foo::bar).
+pub fn qux() {}
@@ -0,0 +1,56 @@
+// edition:2018
+// compile-flags:--test -Z unstable-options --merge-doctests --test-args=--test-threads=1
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// check-pass
+
+//! ```
+//! assert_eq!(merged_doctests::add(1, 1), 2);
+//! ```
+
+/// Tests defining items with the same name don't conflict with each other.
+///
+/// ```
+/// struct Foo(u8);
+/// assert_eq!(Foo(1).0, 1);
+/// ```
+///
+/// ```
+/// struct Foo(&'static str);
+/// assert_eq!(Foo("a").0, "a");
+/// ```
+pub fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+/// ```
+/// let n: u8 = "1".parse()?;
+/// assert_eq!(n, 1);
+/// Ok::<(), std::num::ParseIntError>(())
+/// ```
+///
+/// ```should_panic
+/// panic!("expected");
+/// ```
+///
+/// ```
+/// fn main() {
+///     assert!(true);
+/// }
+/// ```
+///
+/// ```compile_fail
+/// let x: u8 = "not a number";
+/// ```
+///
+/// ```
+/// #![allow(dead_code)]
+/// fn unused() {}
+/// ```
+///
+/// Merged doctests are not compiled with `--test` either.
+///
+/// ```
+/// assert!(!cfg!(test));
+/// ```
+pub fn others() {}
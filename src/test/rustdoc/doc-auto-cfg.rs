@@ -0,0 +1,44 @@
+// compile-flags: --cfg feature="foo" --cfg feature="bar" --cfg feature="hidden"
+
+#![crate_name = "foo"]
+#![feature(doc_auto_cfg, doc_cfg, doc_cfg_hide)]
+#![doc(auto_cfg)]
+#![doc(cfg_hide(feature = "hidden"))]
+
+// @has foo/fn.foo.html '//*[@class="stab portability"]' \
+//  'This is supported on crate feature foo only.'
+#[cfg(feature = "foo")]
+pub fn foo() {}
+
+// `doc` and `test` are hidden by default.
+// @has foo/fn.unix.html '//*[@class="stab portability"]' 'This is supported on Unix only.'
+#[cfg(any(doc, unix))]
+pub fn unix() {}
+
+// @has foo/fn.not_test.html
+// @!has - '//*[@class="stab portability"]' ''
+#[cfg(not(test))]
+pub fn not_test() {}
+
+// @has foo/fn.bar.html '//*[@class="stab portability"]' \
+//  'This is supported on crate feature bar only.'
+#[cfg(all(feature = "bar", feature = "hidden"))]
+pub fn bar() {}
+
+// An explicit `#[doc(cfg(...))]` replaces the inferred one.
+// @has foo/fn.explicit.html '//*[@class="stab portability"]' 'This is supported on Unix only.'
+// @!has - '//*[@class="stab portability"]' 'crate feature foo'
+#[cfg(feature = "foo")]
+#[doc(cfg(unix))]
+pub fn explicit() {}
+
+// @has foo/module/index.html '//*[@class="stab portability"]' \
+//  'This is supported on crate feature foo only.'
+// @matches - '//*[@class="module-item"]//*[@class="stab portability"]' '^bar$'
+#[cfg(feature = "foo")]
+pub mod module {
+    // @has foo/module/fn.nested.html '//*[@class="stab portability"]' \
+    //  'This is supported on crate features foo and bar only.'
+    #[cfg(feature = "bar")]
+    pub fn nested() {}
+}
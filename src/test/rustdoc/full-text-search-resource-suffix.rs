@@ -0,0 +1,10 @@
+// compile-flags: -Z unstable-options --full-text-search --resource-suffix=-suffix
+
+#![crate_name = "foo"]
+
+// @has search-fulltext/foo-suffix.js
+// @!has search-fulltext/foo.js
+// @has foo/index.html '//*[@id="rustdoc-vars"]/@data-resource-suffix' '-suffix'
+
+/// Parses the configuration file.
+pub fn parse() {}
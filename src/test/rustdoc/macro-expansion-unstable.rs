@@ -0,0 +1,15 @@
+#![crate_name = "foo"]
+
+// Without `#![feature(doc_hidden_arms)]`, `expand` is ignored.
+// @has foo/macro.double.html
+// @has - '//h2[@id="grammar"]' 'Grammar'
+// @!has - '//h2[@id="expansions"]' 'Expansions'
+// @!has - '//pre[@class="rust macro-expansion"]' 'Expands to:'
+/// ```rust,expand
+/// # use foo::double;
+/// assert_eq!(double!(2), 4);
+/// ```
+#[macro_export]
+macro_rules! double {
+    ($e:expr) => { $e * 2 };
+}
@@ -0,0 +1,50 @@
+#![feature(doc_hidden_arms)]
+#![crate_name = "foo"]
+
+// @has foo/macro.sum.html
+// @has - '//h2[@id="grammar"]' 'Grammar'
+// @has - '//pre[@class="rust macro-grammar"]/span[@class="macro-var"]' '$x'
+// @has - '//pre[@class="rust macro-grammar"]/a[contains(@href, "/expressions.html")]' 'expr'
+// @has - '//pre[@class="rust macro-grammar"]/span[@class="macro-repetition"]' '),*'
+// @!has - '//pre[@class="rust macro-grammar"]' '@acc'
+// @!has - '//pre[@class="rust macro"]' '@acc'
+// @has - '//h2[@id="expansions"]' 'Expansions'
+// @has - '//pre[@class="rust macro-expansion"]' 'Expands to:'
+/// Adds numbers together.
+///
+/// ```rust,expand
+/// # use foo::sum;
+/// assert_eq!(sum!(1, 2), 3);
+/// ```
+#[doc(hidden_arms = "@")]
+#[macro_export]
+macro_rules! sum {
+    ($($x:expr),*) => { $crate::sum!(@acc 0; $($x),*) };
+    (@acc $acc:expr;) => { $acc };
+    (@acc $acc:expr; $x:expr $(, $rest:expr)*) => { $crate::sum!(@acc $acc + $x; $($rest),*) };
+}
+
+// @has foo/macro.ident.html
+// @has - '//pre[@class="rust macro-grammar"]/a[contains(@href, "/identifiers.html")]' 'ident'
+// @!has - '//h2[@id="expansions"]' 'Expansions'
+#[macro_export]
+macro_rules! ident {
+    ($i:ident) => { $i };
+}
+
+// The invocation doesn't parse as an expression, so the compiler expands it with the second arm.
+// @has foo/macro.pick.html
+// @has - '//pre[@class="rust macro-expansion"]' 'tokens()'
+// @!has - '//pre[@class="rust macro-expansion"]' 'expr('
+/// ```rust,expand
+/// # use foo::pick;
+/// fn tokens() {}
+/// fn main() {
+///     pick!(a b);
+/// }
+/// ```
+#[macro_export]
+macro_rules! pick {
+    ($e:expr) => { expr($e) };
+    ($($t:tt)*) => { tokens() };
+}
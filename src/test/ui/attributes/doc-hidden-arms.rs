@@ -0,0 +1,14 @@
+#![crate_type = "lib"]
+#![feature(doc_hidden_arms)]
+#![deny(invalid_doc_attributes)]
+
+#[doc(hidden_arms = "@")] //~ ERROR
+//~^ WARN
+pub fn foo() {}
+
+#[doc(hidden_arms)] //~ ERROR
+//~^ WARN
+#[macro_export]
+macro_rules! bar {
+    () => {};
+}
@@ -0,0 +1,21 @@
+#![feature(derive_cmp_attrs)]
+
+#[derive(PartialEq)]
+struct Malformed {
+    #[cmp] //~ ERROR expected `#[cmp(skip)]` or `#[cmp(order = N)]`
+    a: u8,
+    #[cmp(ignore)] //~ ERROR expected `skip` or `order = N`
+    b: u8,
+    #[cmp(order = "first")] //~ ERROR expected an integer, like `order = 0`
+    c: u8,
+}
+
+#[derive(PartialOrd, PartialEq)]
+struct DuplicateOrder {
+    #[cmp(order = 1)]
+    a: u8,
+    #[cmp(order = 1)]
+    b: u8, //~ ERROR multiple fields have `#[cmp(order = 1)]`
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+// run-pass
+#![feature(derive_cmp_attrs)]
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+struct Entry {
+    name: &'static str,
+    #[cmp(order = 0)]
+    priority: u32,
+    #[cmp(skip)]
+    cached_len: usize,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+struct Tuple(u8, #[cmp(skip)] u8);
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+enum Shape {
+    Circle { radius: u32, #[cmp(skip)] id: u32 },
+    Square(#[cmp(order = 0)] u32, u32),
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn main() {
+    let a = Entry { name: "b", priority: 1, cached_len: 1 };
+    let b = Entry { name: "a", priority: 2, cached_len: 1 };
+    let c = Entry { name: "b", priority: 1, cached_len: 5 };
+
+    // `priority` is compared before `name`, and `cached_len` is not compared at all.
+    assert_eq!(a.cmp(&b), Ordering::Less);
+    assert_eq!(a.partial_cmp(&b), Some(Ordering::Less));
+    assert_eq!(a, c);
+    assert_eq!(hash(&a), hash(&c));
+    assert_eq!(format!("{:?}", a), r#"Entry { name: "b", priority: 1 }"#);
+
+    assert_eq!(Tuple(1, 2), Tuple(1, 3));
+    assert_eq!(hash(&Tuple(1, 2)), hash(&Tuple(1, 3)));
+    assert_eq!(format!("{:?}", Tuple(1, 2)), "Tuple(1)");
+
+    assert_eq!(Shape::Circle { radius: 1, id: 1 }, Shape::Circle { radius: 1, id: 2 });
+    assert!(Shape::Square(1, 2) < Shape::Square(2, 1));
+    assert_eq!(format!("{:?}", Shape::Square(1, 2)), "Square(1, 2)");
+}
@@ -0,0 +1,49 @@
+#![feature(derive_default_enum)]
+
+#[derive(Default)] //~ ERROR no default declared
+enum NoDeclaredDefault {
+    Foo,
+    Bar,
+}
+
+#[derive(Default)] //~ ERROR multiple declared defaults
+enum MultipleDefaults {
+    #[default]
+    Foo,
+    #[default]
+    Bar,
+}
+
+#[derive(Default)]
+enum NonUnitDefault {
+    #[default]
+    Foo(i32), //~ ERROR the `#[default]` attribute may only be used on unit enum variants
+}
+
+#[derive(Default)]
+enum NonExhaustiveDefault {
+    #[default]
+    #[non_exhaustive]
+    Foo, //~ ERROR default variant must be exhaustive
+}
+
+#[derive(Default)]
+enum RepeatedDefault {
+    #[default]
+    #[default]
+    Foo, //~ ERROR multiple `#[default]` attributes
+}
+
+#[derive(Default)]
+enum DefaultWithValue {
+    #[default = 1] //~ ERROR `#[default]` attribute does not accept a value
+    Foo,
+}
+
+#[derive(Default)]
+struct DefaultOnField {
+    #[default] //~ ERROR the `#[default]` attribute may only be used on unit enum variants
+    field: i32,
+}
+
+fn main() {}
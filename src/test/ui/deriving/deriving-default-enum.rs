@@ -0,0 +1,31 @@
+// run-pass
+
+#![feature(derive_default_enum)]
+
+// nb: does not impl Default
+#[derive(Debug, PartialEq)]
+struct NotDefault;
+
+#[derive(Debug, Default, PartialEq)]
+enum Foo {
+    #[default]
+    Alpha,
+    #[allow(dead_code)]
+    Beta(NotDefault),
+}
+
+// `#[default]` may sit next to `#[non_exhaustive]` on other variants.
+#[derive(Debug, Default, PartialEq)]
+#[non_exhaustive]
+enum Bar {
+    #[non_exhaustive]
+    #[allow(dead_code)]
+    Alpha,
+    #[default]
+    Beta,
+}
+
+fn main() {
+    assert_eq!(Foo::default(), Foo::Alpha);
+    assert_eq!(Bar::default(), Bar::Beta);
+}
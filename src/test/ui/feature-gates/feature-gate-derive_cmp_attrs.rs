@@ -0,0 +1,8 @@
+#[derive(PartialEq, Debug)]
+struct Foo {
+    a: u8,
+    #[cmp(skip)] //~ ERROR the `#[cmp]` attribute is experimental
+    b: u8,
+}
+
+fn main() {}
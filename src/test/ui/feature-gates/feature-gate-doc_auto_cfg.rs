@@ -0,0 +1,3 @@
+#![doc(auto_cfg)] //~ ERROR `#[doc(auto_cfg)]` is experimental
+
+fn main() {}
@@ -0,0 +1,3 @@
+#![doc(cfg_hide(test))] //~ ERROR `#[doc(cfg_hide)]` is experimental
+
+fn main() {}
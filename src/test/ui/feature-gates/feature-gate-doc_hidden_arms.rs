@@ -0,0 +1,6 @@
+#[doc(hidden_arms = "@")] //~ ERROR `#[doc(hidden_arms)]` is experimental
+macro_rules! foo {
+    () => {};
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+macro_rules! count {
+    ($($x:ident),*) => {
+        ${count(x)} //~ ERROR meta-variable expressions are unstable
+    };
+}
+
+fn main() {
+    assert_eq!(count!(a, b, c), 3);
+}
@@ -0,0 +1,22 @@
+#![feature(format_args_capture)]
+
+struct Foo {
+    ids: Vec<u32>,
+}
+
+impl Foo {
+    fn name(&self) -> &str {
+        "foo"
+    }
+}
+
+fn main() {
+    let foo = Foo { ids: vec![1] };
+    format!("{foo.name()}");
+    //~^ ERROR invalid format string: method calls are not supported in format arguments
+    format!("{foo.ids[0]}");
+    //~^ ERROR invalid format string: indexing is not supported in format arguments
+    format!("{} {}", foo.name(), foo.ids[0]);
+    format!("{f:?} {f.ids:?}", f = foo.ids);
+    //~^ ERROR cannot access fields of the named argument `f`
+}
@@ -6,6 +6,7 @@ fn main() {
     named_argument_takes_precedence_to_captured();
     formatting_parameters_can_be_captured();
     capture_raw_strings_and_idents();
+    field_accesses_can_be_captured();
 
     #[cfg(panic = "unwind")]
     {
@@ -79,3 +80,39 @@ fn formatting_parameters_can_be_captured() {
     let s = format!("{x:-^width$.precision$}");
     assert_eq!(&s, "--7.000--");
 }
+
+struct Config {
+    timeout: u32,
+    width: usize,
+    retries: (u8, u8),
+}
+
+struct Connection {
+    id: &'static str,
+    config: Config,
+}
+
+impl Connection {
+    fn describe(&self) -> String {
+        format!("{self.id} ({self.config.timeout}ms)")
+    }
+}
+
+fn field_accesses_can_be_captured() {
+    let conn = Connection {
+        id: "db",
+        config: Config { timeout: 30, width: 6, retries: (1, 3) },
+    };
+    assert_eq!(conn.describe(), "db (30ms)");
+
+    let cfg = &conn.config;
+    let s = format!("{cfg.timeout:?} {cfg.retries.1}/{cfg.retries:?}");
+    assert_eq!(&s, "30 3/(1, 3)");
+
+    let s = format!("[{conn.id:>cfg.width$}] [{cfg.timeout:<width$}]", width = 4);
+    assert_eq!(&s, "[    db] [30  ]");
+
+    // The argument is only evaluated once, even when used several times.
+    let s = format!("{conn.id}-{conn.id:?}");
+    assert_eq!(&s, "db-\"db\"");
+}
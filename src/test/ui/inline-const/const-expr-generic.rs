@@ -0,0 +1,30 @@
+// run-pass
+
+#![allow(incomplete_features)]
+#![feature(inline_const)]
+
+use std::cell::Cell;
+
+fn empty_vecs<T, const N: usize>() -> [Vec<T>; N] {
+    [const { Vec::new() }; N]
+}
+
+fn size_of<T>() -> usize {
+    const { std::mem::size_of::<T>() }
+}
+
+fn main() {
+    let cells: [Cell<u8>; 3] = [const { Cell::new(1) }; 3];
+    cells[0].set(2);
+    assert_eq!(cells.iter().map(Cell::get).sum::<u8>(), 4);
+
+    let vecs: [Vec<String>; 4] = empty_vecs();
+    assert!(vecs.iter().all(Vec::is_empty));
+
+    let in_closure = || const { Some(5) };
+    let x: Option<i64> = in_closure();
+    assert_eq!(x, Some(5));
+
+    assert_eq!(size_of::<u64>(), 8);
+    assert_eq!(size_of::<[u16; 3]>(), 6);
+}
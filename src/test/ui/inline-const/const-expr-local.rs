@@ -0,0 +1,8 @@
+#![allow(incomplete_features)]
+#![feature(inline_const)]
+
+fn main() {
+    let n = 1;
+    let _ = const { n + 1 };
+    //~^ ERROR attempt to use a non-constant value in a constant [E0435]
+}
@@ -0,0 +1,27 @@
+// run-pass
+
+#![allow(incomplete_features)]
+#![feature(inline_const)]
+
+const HALF: u8 = 128;
+
+fn classify(x: u8) -> &'static str {
+    // Inline constant patterns are evaluated before exhaustiveness checking, so no wildcard arm
+    // is needed here.
+    match x {
+        0..=const { HALF - 1 } => "low",
+        const { HALF }..=u8::MAX => "high",
+    }
+}
+
+fn main() {
+    assert_eq!(classify(3), "low");
+    assert_eq!(classify(200), "high");
+
+    let flag = true;
+    let s = match flag {
+        const { !false } => "yes",
+        const { 1 > 2 } => "no",
+    };
+    assert_eq!(s, "yes");
+}
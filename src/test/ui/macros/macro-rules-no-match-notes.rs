@@ -0,0 +1,13 @@
+// Tests the notes explaining how far each rule got when no rule of a macro matched.
+
+// compile-flags: -Z macro-backtrace
+
+macro_rules! dsl {
+    (let $name:ident = $value:expr;) => {};
+    (print $($value:expr),+;) => {};
+}
+
+fn main() {
+    dsl!(let x = 1); //~ ERROR unexpected end of macro invocation
+    dsl!(print 1, 2 => 3;); //~ ERROR no rules expected the token `=>`
+}
@@ -4,7 +4,7 @@
 // immediately, so that we get more errors listed at a time.
 
 #![feature(asm, llvm_asm)]
-#![feature(trace_macros, concat_idents)]
+#![feature(trace_macros, concat_idents, derive_default_enum)]
 
 #[derive(Default)] //~ ERROR
 enum OrDeriveThis {}
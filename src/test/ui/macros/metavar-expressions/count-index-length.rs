@@ -0,0 +1,46 @@
+// run-pass
+
+#![feature(macro_metavar_expr)]
+
+macro_rules! count_idents {
+    ($($x:ident),*) => {
+        ${count(x)}
+    };
+}
+
+macro_rules! count_nested {
+    ($($($x:ident)*);*) => {
+        (${count(x)}, ${count(x, 0)})
+    };
+}
+
+macro_rules! indices {
+    ($($x:ident),*) => {
+        [$((stringify!($x), ${index()}, ${length()})),*]
+    };
+}
+
+macro_rules! outer_indices {
+    ($($($x:ident)*);*) => {
+        [$($((${index(1)}, ${index()}, ${length(1)}),)*)*]
+    };
+}
+
+macro_rules! ignored {
+    ($($x:ident),*) => {
+        [$(${ignore(x)} ()),*].len()
+    };
+}
+
+fn main() {
+    assert_eq!(count_idents!(), 0);
+    assert_eq!(count_idents!(a, b, c), 3);
+
+    assert_eq!(count_nested!(a b; c; d e f), (6, 3));
+
+    assert_eq!(indices!(a, b), [("a", 0, 2), ("b", 1, 2)]);
+
+    assert_eq!(outer_indices!(a b; c), [(0, 0, 2), (0, 1, 2), (1, 0, 2)]);
+
+    assert_eq!(ignored!(a, b, c, d), 4);
+}
@@ -0,0 +1,57 @@
+#![feature(macro_metavar_expr)]
+
+macro_rules! unknown_expr {
+    ($x:ident) => {
+        ${sum(x)} //~ ERROR unrecognized meta-variable expression `sum`
+    };
+}
+
+macro_rules! missing_parens {
+    ($x:ident) => {
+        ${count} //~ ERROR meta-variable expression parameters must be wrapped in parentheses
+    };
+}
+
+macro_rules! bad_depth {
+    ($($x:ident),*) => {
+        ${count(x, 1u8)} //~ ERROR meta-variable expression depth must be a literal integer
+    };
+}
+
+macro_rules! trailing_tokens {
+    ($($x:ident),*) => {
+        ${count(x) + 1} //~ ERROR unexpected token: +
+    };
+}
+
+macro_rules! count_not_repeating {
+    ($x:ident) => {
+        ${count(x)} //~ ERROR `count` can only be used on meta-variables that repeat more deeply
+    };
+}
+
+macro_rules! count_too_deep {
+    ($($x:ident),*) => {
+        ${count(x, 1)} //~ ERROR `count` depth `1` is out of range
+    };
+}
+
+macro_rules! count_unknown {
+    ($($x:ident),*) => {
+        ${count(y)} //~ ERROR unknown macro variable `y`
+    };
+}
+
+macro_rules! index_outside_repetition {
+    ($($x:ident),*) => {
+        ${index()} //~ ERROR `index` with depth `0` must be used inside at least 1 repetition
+    };
+}
+
+macro_rules! length_too_deep {
+    ($($x:ident),*) => {
+        $(${length(1)})* //~ ERROR `length` with depth `1` must be used inside at least 2 repetitions
+    };
+}
+
+fn main() {}
@@ -0,0 +1,64 @@
+// force-host
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+#![feature(proc_macro_diagnostic)]
+
+extern crate proc_macro;
+
+use proc_macro::{Applicability, Diagnostic, Level, TokenStream, TokenTree};
+
+fn uppercase_idents(input: TokenStream) -> Vec<Diagnostic> {
+    let mut diags = vec![];
+    for tree in input {
+        if let TokenTree::Ident(ident) = tree {
+            let name = ident.to_string();
+            if name.chars().any(|c| c.is_uppercase()) {
+                diags.push(
+                    Diagnostic::spanned(ident.span(), Level::Error, "identifier is not lowercase")
+                        .span_label(ident.span(), "contains uppercase letters")
+                        .help("the macro only accepts lowercase identifiers")
+                        .span_suggestion(
+                            ident.span(),
+                            "use a lowercase identifier",
+                            name.to_lowercase(),
+                            Applicability::MachineApplicable,
+                        ),
+                );
+            }
+        }
+    }
+    diags
+}
+
+#[proc_macro]
+pub fn lowercase(input: TokenStream) -> TokenStream {
+    for diag in uppercase_idents(input) {
+        diag.emit();
+    }
+    TokenStream::new()
+}
+
+#[proc_macro]
+pub fn lowercase_lint(input: TokenStream) -> TokenStream {
+    for diag in uppercase_idents(input) {
+        diag.emit_lint("my_tool::uppercase");
+    }
+    TokenStream::new()
+}
+
+#[proc_macro]
+pub fn unscoped_lint(input: TokenStream) -> TokenStream {
+    for diag in uppercase_idents(input) {
+        diag.emit_lint("uppercase");
+    }
+    TokenStream::new()
+}
+
+#[proc_macro]
+pub fn unknown_tool_lint(input: TokenStream) -> TokenStream {
+    for diag in uppercase_idents(input) {
+        diag.emit_lint("unknown_tool::uppercase");
+    }
+    TokenStream::new()
+}
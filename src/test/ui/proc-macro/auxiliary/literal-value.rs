@@ -0,0 +1,71 @@
+// force-host
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+#![feature(proc_macro_expand, proc_macro_value)]
+
+extern crate proc_macro;
+
+use proc_macro::{ConversionErrorKind, Literal, TokenStream, TokenTree};
+
+fn lit(src: &str) -> Literal {
+    match src.parse::<TokenStream>().unwrap().into_iter().next() {
+        Some(TokenTree::Literal(lit)) => lit,
+        _ => panic!("`{}` is not a literal", src),
+    }
+}
+
+#[proc_macro]
+pub fn check_literal_values(_input: TokenStream) -> TokenStream {
+    assert_eq!(lit(r#""a\tb\u{1F600}""#).str_value(), Ok("a\tb\u{1F600}".to_string()));
+    assert_eq!(lit(r###"r#"raw \n"#"###).str_value(), Ok("raw \\n".to_string()));
+    assert_eq!(lit(r#"b"\x00\xFF""#).byte_str_value(), Ok(vec![0, 255]));
+    assert_eq!(lit(r"'\n'").char_value(), Ok('\n'));
+    assert_eq!(lit("0x1F_u8").int_value(), Ok((31, Some("u8".to_string()))));
+    assert_eq!(lit("1_000").int_value(), Ok((1000, None)));
+    assert_eq!(lit("2.5e3f64").float_value(), Ok((2500.0, Some("f64".to_string()))));
+    assert_eq!(lit("1f32").float_value(), Ok((1.0, Some("f32".to_string()))));
+
+    assert_eq!(lit("1").str_value(), Err(ConversionErrorKind::InvalidLiteralKind));
+    assert_eq!(lit("1u7").int_value(), Err(ConversionErrorKind::InvalidSuffix));
+    assert_eq!(
+        lit("340282366920938463463374607431768211456").int_value(),
+        Err(ConversionErrorKind::OutOfRange),
+    );
+    assert_eq!(
+        Literal::i32_suffixed(-1).int_value(),
+        Err(ConversionErrorKind::InvalidLiteralKind),
+    );
+
+    // Constructed literals round-trip.
+    assert_eq!(Literal::string("round\ntrip").str_value(), Ok("round\ntrip".to_string()));
+    assert_eq!(Literal::byte_string(b"\0\x7F").byte_str_value(), Ok(vec![0, 127]));
+    assert_eq!(Literal::character('\'').char_value(), Ok('\''));
+
+    TokenStream::new()
+}
+
+#[proc_macro]
+pub fn expand_expr_is(input: TokenStream) -> TokenStream {
+    let mut iter = input.into_iter();
+    let mut expected_tts = Vec::new();
+    loop {
+        match iter.next() {
+            Some(TokenTree::Punct(ref p)) if p.as_char() == ',' => break,
+            Some(tt) => expected_tts.push(tt),
+            None => panic!("expected comma"),
+        }
+    }
+
+    let expected = expected_tts.into_iter().collect::<TokenStream>();
+    let expanded = iter.collect::<TokenStream>().expand_expr().expect("expand_expr failed");
+    assert_eq!(expected.to_string(), expanded.to_string());
+
+    TokenStream::new()
+}
+
+#[proc_macro]
+pub fn expand_expr_fails(input: TokenStream) -> TokenStream {
+    assert!(input.expand_expr().is_err());
+    TokenStream::new()
+}
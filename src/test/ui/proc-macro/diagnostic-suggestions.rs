@@ -0,0 +1,11 @@
+// aux-build:diagnostic-suggestions.rs
+
+extern crate diagnostic_suggestions;
+
+use diagnostic_suggestions::lowercase;
+
+fn main() {
+    lowercase!(foo bar);
+    lowercase!(Foo); //~ ERROR identifier is not lowercase
+    lowercase!(foo BAR baz); //~ ERROR identifier is not lowercase
+}
@@ -0,0 +1,39 @@
+// aux-build:diagnostic-suggestions.rs
+
+#![feature(register_tool)]
+#![register_tool(my_tool)]
+
+extern crate diagnostic_suggestions;
+
+use diagnostic_suggestions::{lowercase_lint, unknown_tool_lint, unscoped_lint};
+
+fn warned() {
+    lowercase_lint!(Foo); //~ WARN identifier is not lowercase
+}
+
+#[allow(my_tool::uppercase)]
+fn allowed() {
+    lowercase_lint!(Foo);
+}
+
+#[deny(my_tool::uppercase)]
+fn denied() {
+    lowercase_lint!(Foo); //~ ERROR identifier is not lowercase
+}
+
+fn invalid_name() {
+    unscoped_lint!(Foo); //~ ERROR invalid lint name `uppercase` emitted by a procedural macro
+}
+
+fn unknown_tool() {
+    unknown_tool_lint!(Foo);
+    //~^ ERROR unknown tool name `unknown_tool` in lint emitted by a procedural macro
+}
+
+fn main() {
+    warned();
+    allowed();
+    denied();
+    invalid_name();
+    unknown_tool();
+}
@@ -0,0 +1,21 @@
+// check-pass
+// aux-build:literal-value.rs
+
+extern crate literal_value;
+
+use literal_value::*;
+
+check_literal_values!();
+
+expand_expr_is!("helloworld", concat!("hello", "world"));
+expand_expr_is!("ab", concat!("a", stringify!(b)));
+expand_expr_is!("Included file contents\n", include_str!("auxiliary/included-file.txt"));
+expand_expr_is!(10, 10);
+expand_expr_is!(-1, -1);
+expand_expr_is!(true, true);
+
+// Only expressions expanding to literals are supported.
+expand_expr_fails!(std::f64::consts::PI);
+expand_expr_fails!(vec![1, 2, 3]);
+
+fn main() {}
@@ -2999,6 +2999,10 @@ fn run_rmake_test(&self) {
             cmd.env("RUST_DEMANGLER", cwd.join(rust_demangler));
         }
 
+        if let Some(ref dep_graph_explain) = self.config.dep_graph_explain_path {
+            cmd.env("DEP_GRAPH_EXPLAIN", cwd.join(dep_graph_explain));
+        }
+
         if let Some(ref node) = self.config.nodejs {
             cmd.env("NODE", node);
         }
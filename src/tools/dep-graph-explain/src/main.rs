@@ -0,0 +1,164 @@
+//! Answers "why was this query re-run?" from a dependency graph snapshot.
+//!
+//! The snapshot is written by an incremental compilation session with
+//! `-Z dep-graph-snapshot=<file>`. For every dep-node matching the given filter
+//! this tool prints whether it was re-executed and, if so, follows the chain of
+//! dependencies that could not be marked green back to the input that changed.
+//!
+//! ```shell
+//! $ rustc -C incremental=incr -Z dep-graph-snapshot=dep-graph.json lib.rs
+//! $ dep-graph-explain dep-graph.json 'typeck & my_function'
+//! ```
+//!
+//! The filter uses the same syntax as `RUST_DEP_GRAPH_FILTER`: a `&`-separated
+//! list of strings that must all appear in the `Debug` output of the dep-node.
+
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{self, Write};
+use std::process;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Deserialize)]
+struct Snapshot {
+    nodes: Vec<Node>,
+}
+
+#[derive(Deserialize)]
+struct Node {
+    node: String,
+    fingerprint: String,
+    status: Status,
+    edges: Vec<usize>,
+    cause: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+enum Status {
+    New,
+    Red,
+    LightGreen,
+    DarkGreen,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Status::New => "new",
+            Status::Red => "red",
+            Status::LightGreen => "light green",
+            Status::DarkGreen => "dark green",
+        })
+    }
+}
+
+fn matches_filter(node: &str, filter: &str) -> bool {
+    filter.split('&').map(|s| s.trim()).all(|f| node.contains(f))
+}
+
+fn explain(out: &mut impl Write, by_name: &HashMap<&str, &Node>, node: &Node) -> io::Result<()> {
+    match node.status {
+        Status::New => {
+            return writeln!(
+                out,
+                "{} is new in this session ({} dependencies)",
+                node.node,
+                node.edges.len()
+            );
+        }
+        Status::DarkGreen => return writeln!(out, "{} was not re-executed", node.node),
+        Status::Red | Status::LightGreen => {}
+    }
+
+    writeln!(
+        out,
+        "{} was re-executed ({}, fingerprint {})",
+        node.node, node.status, node.fingerprint
+    )?;
+
+    let mut seen = HashSet::new();
+    let mut current = node;
+    let mut depth = 1;
+    while let Some(cause) = &current.cause {
+        if !seen.insert(cause.as_str()) {
+            return writeln!(out, "{:indent$}... (cycle)", "", indent = depth * 2);
+        }
+        match by_name.get(cause.as_str()) {
+            Some(&cause_node) => {
+                writeln!(
+                    out,
+                    "{:indent$}because {} could not be marked green ({})",
+                    "",
+                    cause,
+                    cause_node.status,
+                    indent = depth * 2
+                )?;
+                current = cause_node;
+            }
+            None => {
+                return writeln!(
+                    out,
+                    "{:indent$}because {} no longer exists",
+                    "",
+                    cause,
+                    indent = depth * 2
+                );
+            }
+        }
+        depth += 1;
+    }
+
+    // The last node of the chain has no recorded cause: it was not subject to
+    // red-green marking at all, i.e. it is an `eval_always` input.
+    writeln!(
+        out,
+        "{:indent$}{} is an input that is always re-evaluated",
+        "",
+        if depth == 1 { "it" } else { current.node.as_str() },
+        indent = depth * 2
+    )
+}
+
+/// Explains every node of `snapshot` matching `filter`, returning `false` if
+/// no node matched.
+fn explain_matching(out: &mut impl Write, snapshot: &Snapshot, filter: &str) -> io::Result<bool> {
+    let by_name: HashMap<_, _> = snapshot.nodes.iter().map(|n| (n.node.as_str(), n)).collect();
+
+    let mut found = false;
+    for node in snapshot.nodes.iter().filter(|n| matches_filter(&n.node, filter)) {
+        found = true;
+        explain(out, &by_name, node)?;
+    }
+    Ok(found)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 3 {
+        eprintln!("usage: {} <snapshot.json> <filter>", args[0]);
+        process::exit(1);
+    }
+
+    let contents = std::fs::read_to_string(&args[1]).unwrap_or_else(|err| {
+        eprintln!("failed to read `{}`: {}", args[1], err);
+        process::exit(1);
+    });
+    let snapshot: Snapshot = serde_json::from_str(&contents).unwrap_or_else(|err| {
+        eprintln!("failed to parse `{}`: {}", args[1], err);
+        process::exit(1);
+    });
+
+    let stdout = io::stdout();
+    let found = explain_matching(&mut stdout.lock(), &snapshot, &args[2]).unwrap_or_else(|err| {
+        eprintln!("failed to write to stdout: {}", err);
+        process::exit(1);
+    });
+
+    if !found {
+        eprintln!("no dep-node matches `{}`", args[2]);
+        process::exit(1);
+    }
+}
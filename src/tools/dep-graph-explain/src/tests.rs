@@ -0,0 +1,108 @@
+use super::*;
+
+fn snapshot(json: &str) -> Snapshot {
+    serde_json::from_str(json).unwrap()
+}
+
+fn explain_to_string(snapshot: &Snapshot, filter: &str) -> Option<String> {
+    let mut out = Vec::new();
+    let found = explain_matching(&mut out, snapshot, filter).unwrap();
+    if found { Some(String::from_utf8(out).unwrap()) } else { None }
+}
+
+const SNAPSHOT: &str = r#"{"nodes":[
+    {"node":"hir_owner(foo[..]::changed)","fingerprint":"1","status":"Red","edges":[],"cause":null},
+    {"node":"typeck(foo[..]::changed)","fingerprint":"2","status":"Red","edges":[0],
+     "cause":"hir_owner(foo[..]::changed)"},
+    {"node":"typeck(foo[..]::caller)","fingerprint":"3","status":"LightGreen","edges":[1],
+     "cause":"typeck(foo[..]::changed)"},
+    {"node":"typeck(foo[..]::unrelated)","fingerprint":"4","status":"DarkGreen","edges":[],
+     "cause":null},
+    {"node":"typeck(foo[..]::added)","fingerprint":"5","status":"New","edges":[0,1],"cause":null},
+    {"node":"optimized_mir(foo[..]::gone)","fingerprint":"6","status":"Red","edges":[],
+     "cause":"hir_owner(foo[..]::gone)"},
+    {"node":"mir_built(foo[..]::a)","fingerprint":"7","status":"Red","edges":[],
+     "cause":"mir_built(foo[..]::b)"},
+    {"node":"mir_built(foo[..]::b)","fingerprint":"8","status":"Red","edges":[],
+     "cause":"mir_built(foo[..]::a)"}
+]}"#;
+
+#[test]
+fn filter() {
+    assert!(matches_filter("typeck(foo[..]::caller)", "typeck"));
+    assert!(matches_filter("typeck(foo[..]::caller)", "typeck & caller"));
+    assert!(matches_filter("typeck(foo[..]::caller)", "caller&typeck"));
+    assert!(!matches_filter("typeck(foo[..]::caller)", "typeck & changed"));
+    assert!(!matches_filter("optimized_mir(foo[..]::caller)", "typeck & caller"));
+}
+
+#[test]
+fn no_match() {
+    assert_eq!(explain_to_string(&snapshot(SNAPSHOT), "type_of"), None);
+}
+
+#[test]
+fn not_re_executed() {
+    assert_eq!(
+        explain_to_string(&snapshot(SNAPSHOT), "typeck & unrelated").unwrap(),
+        "typeck(foo[..]::unrelated) was not re-executed\n"
+    );
+}
+
+#[test]
+fn new_node() {
+    assert_eq!(
+        explain_to_string(&snapshot(SNAPSHOT), "added").unwrap(),
+        "typeck(foo[..]::added) is new in this session (2 dependencies)\n"
+    );
+}
+
+#[test]
+fn cause_chain() {
+    assert_eq!(
+        explain_to_string(&snapshot(SNAPSHOT), "typeck & caller").unwrap(),
+        "typeck(foo[..]::caller) was re-executed (light green, fingerprint 3)
+  because typeck(foo[..]::changed) could not be marked green (red)
+    because hir_owner(foo[..]::changed) could not be marked green (red)
+      hir_owner(foo[..]::changed) is an input that is always re-evaluated
+"
+    );
+}
+
+#[test]
+fn input() {
+    assert_eq!(
+        explain_to_string(&snapshot(SNAPSHOT), "hir_owner").unwrap(),
+        "hir_owner(foo[..]::changed) was re-executed (red, fingerprint 1)
+  it is an input that is always re-evaluated
+"
+    );
+}
+
+#[test]
+fn removed_cause() {
+    assert_eq!(
+        explain_to_string(&snapshot(SNAPSHOT), "gone").unwrap(),
+        "optimized_mir(foo[..]::gone) was re-executed (red, fingerprint 6)
+  because hir_owner(foo[..]::gone) no longer exists
+"
+    );
+}
+
+#[test]
+fn cycle() {
+    assert_eq!(
+        explain_to_string(&snapshot(SNAPSHOT), "mir_built & ::a").unwrap(),
+        "mir_built(foo[..]::a) was re-executed (red, fingerprint 7)
+  because mir_built(foo[..]::b) could not be marked green (red)
+    because mir_built(foo[..]::a) could not be marked green (red)
+      ... (cycle)
+"
+    );
+}
+
+#[test]
+fn multiple_matches() {
+    let out = explain_to_string(&snapshot(SNAPSHOT), "typeck").unwrap();
+    assert_eq!(out.lines().filter(|line| !line.starts_with(' ')).count(), 4);
+}
@@ -8,10 +8,12 @@
 mod cache;
 mod config;
 mod error;
+mod schema;
 
 use cache::Cache;
 use config::parse_config;
 use error::CkError;
+use schema::Schema;
 
 fn main() -> Result<(), String> {
     let config = parse_config(env::args().collect());
@@ -27,16 +29,37 @@ fn main() -> Result<(), String> {
         }
     }
 
-    if failed.is_empty() {
+    let schema_errors = check_schema(&config.doc_dir)
+        .map_err(|e| format!("Failed to read {}: {}", &config.doc_dir, e))?;
+
+    if failed.is_empty() && schema_errors.is_empty() {
         Ok(())
     } else {
         for i in failed {
             eprintln!("{}", i);
         }
+        for e in schema_errors {
+            eprintln!("{}", e);
+        }
         Err(format!("Jsondocck failed for {}", &config.template))
     }
 }
 
+/// Validates every JSON file in `doc_dir` against the schema of the rustdoc JSON output.
+fn check_schema(doc_dir: &str) -> Result<Vec<String>, CkError> {
+    let schema = Schema::new();
+    let mut errors = Vec::new();
+    for entry in fs::read_dir(doc_dir)? {
+        let path = entry?.path();
+        if path.extension().map_or(false, |ext| ext == "json") {
+            let value = serde_json::from_str::<Value>(&fs::read_to_string(&path)?)?;
+            let file_errors = schema.validate(&value);
+            errors.extend(file_errors.into_iter().map(|e| format!("{}: {}", path.display(), e)));
+        }
+    }
+    Ok(errors)
+}
+
 #[derive(Debug)]
 pub struct Command {
     negated: bool,
@@ -0,0 +1,168 @@
+//! Validation of the output of rustdoc against the JSON Schema generated from the types of
+//! `rustdoc-json-types`. Only the parts of JSON Schema used by that schema are supported.
+
+use serde_json::{Map, Value};
+
+const SCHEMA: &str = include_str!("../../../rustdoc-json-types/schema.json");
+
+pub struct Schema {
+    root: Value,
+}
+
+impl Schema {
+    pub fn new() -> Schema {
+        Schema { root: serde_json::from_str(SCHEMA).unwrap() }
+    }
+
+    /// Returns a description of each place where `value` doesn't match the schema.
+    pub fn validate(&self, value: &Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        self.check(&self.root, value, "$", &mut errors);
+        errors
+    }
+
+    fn matches(&self, schema: &Value, value: &Value) -> bool {
+        let mut errors = Vec::new();
+        self.check(schema, value, "$", &mut errors);
+        errors.is_empty()
+    }
+
+    fn check(&self, schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+        let schema = match schema {
+            Value::Bool(true) => return,
+            Value::Bool(false) => {
+                errors.push(format!("{}: no value is allowed", path));
+                return;
+            }
+            Value::Object(schema) => schema,
+            _ => panic!("invalid schema: {}", schema),
+        };
+
+        if let Some(reference) = schema.get("$ref") {
+            let name = reference
+                .as_str()
+                .and_then(|r| r.strip_prefix("#/definitions/"))
+                .unwrap_or_else(|| panic!("unsupported reference: {}", reference));
+            self.check(&self.root["definitions"][name], value, path, errors);
+        }
+
+        if let Some(types) = schema.get("type") {
+            let matches_type = |ty: &Value| match ty.as_str().unwrap() {
+                "null" => value.is_null(),
+                "boolean" => value.is_boolean(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "number" => value.is_number(),
+                "string" => value.is_string(),
+                "array" => value.is_array(),
+                "object" => value.is_object(),
+                ty => panic!("unsupported type: {}", ty),
+            };
+            let ok = match types {
+                Value::Array(types) => types.iter().any(matches_type),
+                ty => matches_type(ty),
+            };
+            if !ok {
+                errors.push(format!("{}: expected {}, found {}", path, types, value));
+                return;
+            }
+        }
+
+        if let Some(Value::Array(values)) = schema.get("enum") {
+            if !values.contains(value) {
+                errors.push(format!("{}: {} is not one of {:?}", path, value, values));
+            }
+        }
+
+        if let Some(Value::Array(schemas)) = schema.get("allOf") {
+            for schema in schemas {
+                self.check(schema, value, path, errors);
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+            if !schemas.iter().any(|schema| self.matches(schema, value)) {
+                errors.push(format!("{}: {} doesn't match any allowed schema", path, value));
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("oneOf") {
+            if schemas.iter().filter(|schema| self.matches(schema, value)).count() != 1 {
+                errors.push(format!("{}: {} doesn't match exactly one schema", path, value));
+            }
+        }
+
+        if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+            if value.as_f64().map_or(false, |value| value < minimum) {
+                errors.push(format!("{}: {} is less than {}", path, value, minimum));
+            }
+        }
+
+        match value {
+            Value::Object(object) => self.check_object(schema, object, path, errors),
+            Value::Array(array) => self.check_array(schema, array, path, errors),
+            _ => {}
+        }
+    }
+
+    fn check_object(
+        &self,
+        schema: &Map<String, Value>,
+        object: &Map<String, Value>,
+        path: &str,
+        errors: &mut Vec<String>,
+    ) {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for field in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(field) {
+                    errors.push(format!("{}: missing field `{}`", path, field));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (field, value) in object {
+            let path = format!("{}.{}", path, field);
+            match properties.and_then(|properties| properties.get(field)) {
+                Some(schema) => self.check(schema, value, &path, errors),
+                None => {
+                    if let Some(schema) = schema.get("additionalProperties") {
+                        self.check(schema, value, &path, errors);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_array(
+        &self,
+        schema: &Map<String, Value>,
+        array: &[Value],
+        path: &str,
+        errors: &mut Vec<String>,
+    ) {
+        let len = array.len() as u64;
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+            if len < min {
+                errors.push(format!("{}: expected at least {} items, found {}", path, min, len));
+            }
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+            if len > max {
+                errors.push(format!("{}: expected at most {} items, found {}", path, max, len));
+            }
+        }
+
+        match schema.get("items") {
+            // A tuple, with a schema for each item.
+            Some(Value::Array(schemas)) => {
+                for (i, (schema, value)) in schemas.iter().zip(array).enumerate() {
+                    self.check(schema, value, &format!("{}[{}]", path, i), errors);
+                }
+            }
+            Some(schema) => {
+                for (i, value) in array.iter().enumerate() {
+                    self.check(schema, value, &format!("{}[{}]", path, i), errors);
+                }
+            }
+            None => {}
+        }
+    }
+}
@@ -246,7 +246,7 @@ function lookForEntry(entry, data) {
     return null;
 }
 
-function loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate) {
+function loadMainJsAndIndex(mainJs, searchIndex, fullTextIndex, storageJs, crate) {
     if (searchIndex[searchIndex.length - 1].length === 0) {
         searchIndex.pop();
     }
@@ -255,16 +255,17 @@ function loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate) {
     searchIndex = loadContent(fullSearchIndex);
     var finalJS = "";
 
-    var arraysToLoad = ["itemTypes"];
+    var arraysToLoad = ["itemTypes", "FULL_TEXT_STOP_WORDS"];
     var variablesToLoad = ["MAX_LEV_DISTANCE", "MAX_RESULTS", "NO_TYPE_FILTER",
-                           "GENERICS_DATA", "NAME", "INPUTS_DATA", "OUTPUT_DATA",
-                           "TY_PRIMITIVE", "TY_KEYWORD",
+                           "GENERICS_DATA", "NAME", "INPUTS_DATA", "OUTPUT_DATA", "BOUNDS_DATA",
+                           "TY_PRIMITIVE", "TY_KEYWORD", "CRATE_OFFSETS", "FULL_TEXT_INDEXES",
                            "levenshtein_row2"];
     // execQuery first parameter is built in getQuery (which takes in the search input).
     // execQuery last parameter is built in buildIndex.
     // buildIndex requires the hashmap from search-index.
     var functionsToLoad = ["buildHrefAndPath", "pathSplitter", "levenshtein", "validateResult",
-                           "handleAliases", "getQuery", "buildIndex", "execQuery", "execSearch"];
+                           "handleAliases", "getQuery", "buildIndex", "execQuery", "execSearch",
+                           "stemWord", "fullTextTerms", "fullTextScores"];
 
     ALIASES = {};
     finalJS += 'window = { "currentCrate": "' + crate + '", rootPath: "../" };\n';
@@ -275,6 +276,14 @@ function loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate) {
 
     var loaded = loadContent(finalJS);
     var index = loaded.buildIndex(searchIndex.rawSearchIndex);
+    // The full-text index is normally loaded by the first search.
+    if (fullTextIndex !== null) {
+        var fullText = loadContent("function addFullTextIndex(crate, fullTextIndex) {\n" +
+                                   "    exports.crate = crate;\n" +
+                                   "    exports.fullTextIndex = fullTextIndex;\n" +
+                                   "}\n" + fullTextIndex);
+        loaded.FULL_TEXT_INDEXES[fullText.crate] = fullText.fullTextIndex;
+    }
 
     return [loaded, index];
 }
@@ -385,8 +394,10 @@ function load_files(doc_folder, resource_suffix, crate) {
     var storageJs = readFile(path.join(doc_folder, "storage" + resource_suffix + ".js"));
     var searchIndex = readFile(
         path.join(doc_folder, "search-index" + resource_suffix + ".js")).split("\n");
+    var fullTextPath = path.join(doc_folder, "search-fulltext", crate + ".js");
+    var fullTextIndex = fs.existsSync(fullTextPath) ? readFile(fullTextPath) : null;
 
-    return loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate);
+    return loadMainJsAndIndex(mainJs, searchIndex, fullTextIndex, storageJs, crate);
 }
 
 function showHelp() {